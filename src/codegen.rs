@@ -0,0 +1,232 @@
+use crate::{Action, ColumnInfo, Executor, PrimaryKeyType, Result, TableRef, Value};
+use convert_case::{Case, Casing};
+use std::fmt::Write;
+
+/// Identifiers that cannot be used as field names.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self",
+    "static", "struct", "super", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while", "yield",
+];
+
+/// Generates the source of a `#[derive(Entity)]` struct for every table in `schema` (the default one when empty).
+pub async fn generate_entities(executor: &mut impl Executor, schema: &str) -> Result<String> {
+    let mut result = String::from("use tank::Entity;\n");
+    for table in executor.list_tables(schema).await? {
+        let columns = executor.describe_table(&table).await?;
+        result.push('\n');
+        result.push_str(&entity_source(&table, &columns));
+    }
+    Ok(result)
+}
+
+/// Source of the entity mapping a table described by [`Executor::describe_table`].
+pub fn entity_source(table: &TableRef, columns: &[ColumnInfo]) -> String {
+    let mut out = String::with_capacity(128 + columns.len() * 64);
+    let ident = table.name.to_case(Case::Pascal);
+    let primary_key = columns
+        .iter()
+        .filter(|c| c.primary_key != PrimaryKeyType::None)
+        .collect::<Vec<_>>();
+    let _ = write!(out, "#[derive(Entity, Debug, Clone, PartialEq)]\n#[tank(");
+    if !table.schema.is_empty() {
+        let _ = write!(out, "schema = {:?}, ", table.schema);
+    }
+    let _ = write!(out, "name = {:?}", table.name);
+    if primary_key.len() > 1 {
+        let _ = write!(
+            out,
+            ", primary_key = ({})",
+            primary_key
+                .iter()
+                .map(|c| format!("{:?}", c.name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let _ = write!(out, ")]\npub struct {ident} {{\n");
+    for column in columns {
+        for line in column.comment.lines() {
+            let _ = writeln!(out, "    /// {line}");
+        }
+        let mut field = column.name().to_case(Case::Snake);
+        if KEYWORDS.contains(&field.as_str()) {
+            field.push('_');
+        }
+        let mut attributes = Vec::new();
+        if field != column.name() {
+            attributes.push(format!("name = {:?}", column.name()));
+        }
+        if column.primary_key == PrimaryKeyType::PrimaryKey && primary_key.len() == 1 {
+            attributes.push("primary_key".into());
+        }
+        if column.clustering_key {
+            attributes.push("clustering_key".into());
+        }
+        if column.unique && column.primary_key == PrimaryKeyType::None {
+            attributes.push("unique".into());
+        }
+        let default = column
+            .default
+            .as_deref()
+            .map(|v| (v, default_literal(v, &column.value)));
+        if let Some((_, Some(literal))) = &default {
+            attributes.push(format!("default = {literal}"));
+        }
+        if let Some(references) = &column.references {
+            let table = if references.schema.is_empty() {
+                references.table.to_string()
+            } else {
+                format!("{}.{}", references.schema, references.table)
+            };
+            attributes.push(format!("references = {table}({})", references.name));
+            if let Some(action) = column.on_delete.filter(|v| *v != Action::NoAction) {
+                attributes.push(format!("on_delete = {}", action_name(action)));
+            }
+            if let Some(action) = column.on_update.filter(|v| *v != Action::NoAction) {
+                attributes.push(format!("on_update = {}", action_name(action)));
+            }
+        }
+        if let Some((default, None)) = &default {
+            // Database expressions cannot be translated, the database keeps applying them
+            let _ = writeln!(out, "    // Default: {default}");
+        }
+        let ty = rust_type(&column.value);
+        if ty.is_none() {
+            let _ = writeln!(out, "    // Unsupported type: {}", column.column_type);
+        }
+        if !attributes.is_empty() {
+            let _ = writeln!(out, "    #[tank({})]", attributes.join(", "));
+        }
+        let ty = ty.unwrap_or_else(|| "String".into());
+        if column.nullable && column.primary_key == PrimaryKeyType::None {
+            let _ = writeln!(out, "    pub {field}: Option<{ty}>,");
+        } else {
+            let _ = writeln!(out, "    pub {field}: {ty},");
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Rust type decoding to `value`, the inverse of the mapping used by `#[derive(Entity)]`.
+///
+/// Returns `None` for types without a Rust counterpart.
+pub fn rust_type(value: &Value) -> Option<String> {
+    Some(match value {
+        Value::Boolean(..) => "bool".into(),
+        Value::Int8(..) => "i8".into(),
+        Value::Int16(..) => "i16".into(),
+        Value::Int32(..) => "i32".into(),
+        Value::Int64(..) => "i64".into(),
+        Value::Int128(..) => "i128".into(),
+        Value::UInt8(..) => "u8".into(),
+        Value::UInt16(..) => "u16".into(),
+        Value::UInt32(..) => "u32".into(),
+        Value::UInt64(..) => "u64".into(),
+        Value::UInt128(..) => "u128".into(),
+        Value::Float32(..) => "f32".into(),
+        Value::Float64(..) => "f64".into(),
+        Value::Decimal(.., 0, _) => "rust_decimal::Decimal".into(),
+        Value::Decimal(.., precision, scale) => {
+            format!("tank::FixedDecimal<{precision}, {scale}>")
+        }
+        Value::Char(..) => "char".into(),
+        Value::Varchar(..) => "String".into(),
+        Value::Blob(..) => "Box<[u8]>".into(),
+        Value::Date(..) => "time::Date".into(),
+        Value::Time(..) => "time::Time".into(),
+        Value::Timestamp(..) => "time::PrimitiveDateTime".into(),
+        Value::TimestampWithTimezone(..) => "time::OffsetDateTime".into(),
+        Value::Interval(..) => "tank::Interval".into(),
+        Value::Uuid(..) => "uuid::Uuid".into(),
+        Value::Array(.., ty, len) => format!("[{}; {len}]", rust_type(ty)?),
+        Value::List(.., ty) => format!("Vec<{}>", rust_type(ty)?),
+        Value::Map(.., key, value) => format!(
+            "std::collections::HashMap<{}, {}>",
+            rust_type(key)?,
+            rust_type(value)?
+        ),
+        Value::Null | Value::Json(..) | Value::Struct(..) | Value::Unknown(..) => return None,
+    })
+}
+
+/// Translates a default reported by the database into the `default` attribute, when it is a plain literal of the column type.
+fn default_literal(value: &str, ty: &Value) -> Option<String> {
+    let mut value = value.trim();
+    // Postgres reports the type of the literal, like 'abc'::character varying
+    if let Some((literal, _)) = value.rsplit_once("::")
+        && !literal.ends_with(':')
+    {
+        value = literal.trim();
+    }
+    let value = value
+        .strip_prefix('(')
+        .and_then(|v| v.strip_suffix(')'))
+        .unwrap_or(value);
+    let quoted = value.len() >= 2
+        && value.starts_with('\'')
+        && value.ends_with('\'')
+        && !value[1..value.len() - 1].replace("''", "").contains('\'');
+    // Numbers can be quoted as well, like '1'::integer
+    let text = if quoted {
+        value[1..value.len() - 1].replace("''", "'")
+    } else {
+        value.to_string()
+    };
+    let finite = text.trim().parse::<f64>().is_ok_and(f64::is_finite);
+    macro_rules! number {
+        ($variant:ident, $ty:ty) => {
+            text.trim()
+                .parse::<$ty>()
+                .ok()
+                .map(|v| format!("tank::Value::{}(Some({v:?}))", stringify!($variant)))
+        };
+    }
+    match ty {
+        Value::Boolean(..) => match text.trim().to_ascii_lowercase().as_str() {
+            "true" | "t" | "1" => Some("true".into()),
+            "false" | "f" | "0" => Some("false".into()),
+            _ => None,
+        },
+        Value::Int8(..) => number!(Int8, i8),
+        Value::Int16(..) => number!(Int16, i16),
+        Value::Int32(..) => number!(Int32, i32),
+        Value::Int64(..) => text.trim().parse::<i64>().ok().map(|v| v.to_string()),
+        Value::Int128(..) => number!(Int128, i128),
+        Value::UInt8(..) => number!(UInt8, u8),
+        Value::UInt16(..) => number!(UInt16, u16),
+        Value::UInt32(..) => number!(UInt32, u32),
+        Value::UInt64(..) => number!(UInt64, u64),
+        Value::UInt128(..) => number!(UInt128, u128),
+        Value::Float32(..) => number!(Float32, f32).filter(|_| finite),
+        Value::Float64(..) => number!(Float64, f64).filter(|_| finite),
+        Value::Decimal(.., precision, scale) => finite.then(|| {
+            format!(
+                "tank::Value::Decimal(Some({:?}.parse().unwrap()), {precision}, {scale})",
+                text.trim()
+            )
+        }),
+        Value::Char(..) if quoted => {
+            let mut chars = text.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(format!("tank::Value::Char(Some({c:?}))")),
+                _ => None,
+            }
+        }
+        Value::Varchar(..) if quoted => Some(format!("{text:?}")),
+        _ => None,
+    }
+}
+
+fn action_name(action: Action) -> &'static str {
+    match action {
+        Action::NoAction => "no_action",
+        Action::Restrict => "restrict",
+        Action::Cascade => "cascade",
+        Action::SetNull => "set_null",
+        Action::SetDefault => "set_default",
+    }
+}
@@ -1,2 +1,9 @@
+extern crate self as tank;
+
+mod codegen;
+mod migration;
+
+pub use codegen::*;
+pub use migration::*;
 pub use tank_core::*;
 pub use tank_macros::*;
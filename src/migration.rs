@@ -0,0 +1,619 @@
+use crate::{
+    AsEntity, AsQuery, ColumnInfo, Connection, Driver, DynQuery, Entity, ErrorContext, Executor,
+    Query, QueryResult, Result, RowsAffected, SqlWriter, TableRef, Transaction, anyhow, expr,
+    future::{BoxFuture, Either},
+    split_sql_statements,
+    stream::{Stream, TryStreamExt},
+};
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter, Write},
+    fs, io,
+    path::Path,
+    time::Instant,
+};
+use time::OffsetDateTime;
+
+/// Name of the lock held while migrating.
+const LOCK_NAME: &str = "tank_migrations";
+
+/// Migration applied to the database, a row of the `_tank_migrations` table.
+#[derive(Entity, Debug, Clone, PartialEq)]
+#[tank(name = "_tank_migrations")]
+pub struct AppliedMigration {
+    /// Identifier of the migration.
+    #[tank(primary_key)]
+    pub id: String,
+    /// Description of the migration.
+    pub name: String,
+    /// Checksum of the migration when it was applied.
+    pub checksum: String,
+    /// When the migration was applied.
+    pub applied_at: OffsetDateTime,
+    /// Time taken to apply the migration.
+    pub execution_ms: i64,
+}
+
+/// Lock row, used on databases without advisory locks.
+#[derive(Entity, Debug)]
+#[tank(name = "_tank_migrations_lock")]
+struct MigrationLock {
+    #[tank(primary_key)]
+    name: String,
+    locked_at: OffsetDateTime,
+}
+
+/// Async function implementing a migration in Rust.
+pub type MigrationFn<D> = Box<
+    dyn for<'a, 'c> Fn(&'a mut MigrationExecutor<'c, D>) -> BoxFuture<'a, Result<()>> + Send + Sync,
+>;
+
+enum MigrationAction<D: Driver> {
+    Sql(Cow<'static, str>),
+    Rust(MigrationFn<D>),
+}
+
+impl<D: Driver> MigrationAction<D> {
+    fn sql_script(&self) -> Option<&str> {
+        match self {
+            Self::Sql(sql) => Some(sql),
+            Self::Rust(..) => None,
+        }
+    }
+
+    async fn run(&self, executor: &mut MigrationExecutor<'_, D>) -> Result<()> {
+        match self {
+            Self::Sql(sql) => {
+                if executor.accepts_multiple_statements() {
+                    executor.execute(sql.as_ref()).await?;
+                } else {
+                    for statement in split_sql_statements(sql) {
+                        executor.execute(statement).await?;
+                    }
+                }
+            }
+            Self::Rust(migration) => migration(executor).await?,
+        }
+        Ok(())
+    }
+}
+
+/// Hand-written migration, identified by an id and applied at most once.
+pub struct Migration<D: Driver> {
+    /// Unique identifier, recorded in the `_tank_migrations` table once applied.
+    pub id: Cow<'static, str>,
+    /// Description.
+    pub name: Cow<'static, str>,
+    /// SHA-256 of the migration, detects changes to migrations already applied.
+    pub checksum: String,
+    action: MigrationAction<D>,
+    down: Option<MigrationAction<D>>,
+}
+
+impl<D: Driver> Migration<D> {
+    /// Migration executing a SQL script, it can contain multiple statements.
+    pub fn sql(
+        id: impl Into<Cow<'static, str>>,
+        name: impl Into<Cow<'static, str>>,
+        sql: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let sql = sql.into();
+        Self {
+            id: id.into(),
+            name: name.into(),
+            checksum: checksum(&sql.replace("\r\n", "\n")),
+            action: MigrationAction::Sql(sql),
+            down: None,
+        }
+    }
+
+    /// Migration running an async function.
+    ///
+    /// The checksum covers only the id and the name, changes to the code are not detected.
+    pub fn rust<F>(
+        id: impl Into<Cow<'static, str>>,
+        name: impl Into<Cow<'static, str>>,
+        migration: F,
+    ) -> Self
+    where
+        F: for<'a, 'c> Fn(&'a mut MigrationExecutor<'c, D>) -> BoxFuture<'a, Result<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let id = id.into();
+        let name = name.into();
+        Self {
+            checksum: checksum(&format!("{id}\n{name}")),
+            id,
+            name,
+            action: MigrationAction::Rust(Box::new(migration)),
+            down: None,
+        }
+    }
+
+    /// Sets the SQL script reverting the migration, see [`Migrator::revert`].
+    ///
+    /// It is not part of the checksum.
+    pub fn with_down_sql(mut self, sql: impl Into<Cow<'static, str>>) -> Self {
+        self.down = Some(MigrationAction::Sql(sql.into()));
+        self
+    }
+
+    /// Sets the async function reverting the migration, see [`Migrator::revert`].
+    pub fn with_down<F>(mut self, migration: F) -> Self
+    where
+        F: for<'a, 'c> Fn(&'a mut MigrationExecutor<'c, D>) -> BoxFuture<'a, Result<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.down = Some(MigrationAction::Rust(Box::new(migration)));
+        self
+    }
+
+    /// The SQL script, `None` for migrations written in Rust.
+    pub fn sql_script(&self) -> Option<&str> {
+        self.action.sql_script()
+    }
+
+    /// The SQL script reverting the migration, `None` when missing or written in Rust.
+    pub fn down_sql_script(&self) -> Option<&str> {
+        self.down.as_ref().and_then(|v| v.sql_script())
+    }
+
+    /// Whether the migration can be reverted.
+    pub fn is_reversible(&self) -> bool {
+        self.down.is_some()
+    }
+}
+
+impl<D: Driver> Debug for Migration<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("checksum", &self.checksum)
+            .field("sql", &self.sql_script())
+            .field("down_sql", &self.down_sql_script())
+            .finish()
+    }
+}
+
+/// Executor a migration runs on.
+///
+/// A transaction when the driver supports transactional DDL, the connection otherwise.
+pub enum MigrationExecutor<'c, D: Driver> {
+    Connection(&'c mut D::Connection),
+    Transaction(D::Transaction<'c>),
+}
+
+impl<'c, D: Driver> Executor for MigrationExecutor<'c, D> {
+    type Driver = D;
+
+    fn accepts_multiple_statements(&self) -> bool {
+        match self {
+            Self::Connection(v) => v.accepts_multiple_statements(),
+            Self::Transaction(v) => v.accepts_multiple_statements(),
+        }
+    }
+
+    fn driver(&self) -> D {
+        match self {
+            Self::Connection(v) => v.driver(),
+            Self::Transaction(v) => v.driver(),
+        }
+    }
+
+    fn do_prepare(&mut self, sql: String) -> impl Future<Output = Result<Query<D>>> + Send {
+        match self {
+            Self::Connection(v) => Either::Left(v.do_prepare(sql)),
+            Self::Transaction(v) => Either::Right(v.do_prepare(sql)),
+        }
+    }
+
+    fn run<'s>(
+        &'s mut self,
+        query: impl AsQuery<D> + 's,
+    ) -> impl Stream<Item = Result<QueryResult>> + Send {
+        match self {
+            Self::Connection(v) => Either::Left(v.run(query)),
+            Self::Transaction(v) => Either::Right(v.run(query)),
+        }
+    }
+
+    fn append<It>(&mut self, entities: It) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        It: IntoIterator + Send,
+        It::IntoIter: Send,
+        It::Item: AsEntity,
+    {
+        match self {
+            Self::Connection(v) => Either::Left(v.append(entities)),
+            Self::Transaction(v) => Either::Right(v.append(entities)),
+        }
+    }
+
+    fn describe_table(
+        &mut self,
+        table: &TableRef,
+    ) -> impl Future<Output = Result<Vec<ColumnInfo>>> + Send {
+        match self {
+            Self::Connection(v) => Either::Left(v.describe_table(table)),
+            Self::Transaction(v) => Either::Right(v.describe_table(table)),
+        }
+    }
+
+    fn list_tables(&mut self, schema: &str) -> impl Future<Output = Result<Vec<TableRef>>> + Send {
+        match self {
+            Self::Connection(v) => Either::Left(v.list_tables(schema)),
+            Self::Transaction(v) => Either::Right(v.list_tables(schema)),
+        }
+    }
+}
+
+/// Runner applying hand-written migrations in order.
+///
+/// Applied migrations are recorded in the `_tank_migrations` table, an applied migration whose
+/// checksum changed is an error. Every migration runs in its own transaction when the driver
+/// supports transactional DDL, and the whole run holds a lock (an advisory lock where available,
+/// a row of the `_tank_migrations_lock` table otherwise) so that concurrent instances don't
+/// migrate at the same time. On ScyllaDB and MongoDB the lock and the failures are best-effort.
+///
+/// ```ignore
+/// let migrator = Migrator::<PostgresDriver>::new()
+///     .with_directory("migrations")?
+///     .with_migration(Migration::rust("0003", "seed", |executor| {
+///         async move {
+///             Operator::insert_many(executor, &operators).await?;
+///             Ok(())
+///         }
+///         .boxed()
+///     }));
+/// let applied = migrator.run(&mut connection).await?;
+/// ```
+pub struct Migrator<D: Driver> {
+    migrations: Vec<Migration<D>>,
+}
+
+impl<D: Driver> Migrator<D> {
+    pub fn new() -> Self {
+        Self {
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Appends a migration, migrations are applied in the order they are added.
+    pub fn with_migration(mut self, migration: Migration<D>) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Appends the `.sql` files of a directory, sorted by file name.
+    ///
+    /// The file name is the id followed by the name: `0001_create_users.sql` is the migration
+    /// `0001` named `create users`, reverted by `0001_create_users.down.sql` when present.
+    pub fn with_directory(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let context = || format!("While reading the migrations in {}", path.display());
+        let mut files = fs::read_dir(path)
+            .with_context(context)?
+            .map(|v| v.map(|v| v.path()))
+            .collect::<io::Result<Vec<_>>>()
+            .with_context(context)?;
+        files.retain(|v| v.is_file() && v.extension().is_some_and(|v| v == "sql"));
+        files.sort();
+        let (down, files): (Vec<_>, Vec<_>) = files.into_iter().partition(|v| {
+            v.file_stem()
+                .is_some_and(|v| Path::new(v).extension().is_some_and(|v| v == "down"))
+        });
+        for file in down
+            .iter()
+            .filter(|v| !files.contains(&v.with_extension("").with_extension("sql")))
+        {
+            log::warn!(
+                "The down migration {} has no matching migration",
+                file.display()
+            );
+        }
+        for file in files {
+            let stem = file
+                .file_stem()
+                .and_then(|v| v.to_str())
+                .ok_or_else(|| anyhow!("Invalid migration file name {}", file.display()))?;
+            let (id, name) = stem.split_once('_').unwrap_or((stem, ""));
+            let sql = fs::read_to_string(&file)
+                .with_context(|| format!("While reading the migration {}", file.display()))?;
+            let mut migration = Migration::sql(id.to_string(), name.replace('_', " "), sql);
+            let down_file = file.with_extension("down.sql");
+            if down.contains(&down_file) {
+                let sql = fs::read_to_string(&down_file).with_context(|| {
+                    format!("While reading the migration {}", down_file.display())
+                })?;
+                migration = migration.with_down_sql(sql);
+            }
+            self.migrations.push(migration);
+        }
+        Ok(self)
+    }
+
+    /// The migrations, in order.
+    pub fn migrations(&self) -> &[Migration<D>] {
+        &self.migrations
+    }
+
+    /// Reads the migrations applied to the database, sorted by id.
+    pub async fn applied(&self, executor: &mut impl Executor) -> Result<Vec<AppliedMigration>> {
+        AppliedMigration::create_table(executor, true, false).await?;
+        let mut result = AppliedMigration::find_many(executor, true, None)
+            .try_collect::<Vec<_>>()
+            .await?;
+        result.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(result)
+    }
+
+    /// The migrations not yet applied to the database, in order.
+    ///
+    /// Fails if a migration was modified after being applied.
+    pub async fn pending(&self, executor: &mut impl Executor) -> Result<Vec<&Migration<D>>> {
+        self.check_ids()?;
+        let applied = self.applied(executor).await?;
+        let applied = applied
+            .iter()
+            .map(|v| (v.id.as_str(), v))
+            .collect::<HashMap<_, _>>();
+        let mut result: Vec<&Migration<D>> = Vec::new();
+        for migration in &self.migrations {
+            match applied.get(migration.id.as_ref()) {
+                Some(applied) if applied.checksum != migration.checksum => {
+                    let error = anyhow!(
+                        "The migration {} was modified after being applied (checksum {} in the database, {} now)",
+                        migration.id,
+                        applied.checksum,
+                        migration.checksum,
+                    );
+                    log::error!("{error:#}");
+                    return Err(error);
+                }
+                Some(..) => {
+                    if let Some(pending) = result.first() {
+                        log::warn!(
+                            "The migration {} is applied after {}, which follows it",
+                            pending.id,
+                            migration.id,
+                        );
+                    }
+                }
+                None => result.push(migration),
+            }
+        }
+        for id in applied.keys() {
+            if !self.migrations.iter().any(|v| v.id == *id) {
+                log::warn!("The migration {id} was applied but it is not defined anymore");
+            }
+        }
+        Ok(result)
+    }
+
+    /// Applies the pending migrations, returning them.
+    ///
+    /// Stops at the first migration failing, the ones applied before it stay applied.
+    pub async fn run(&self, connection: &mut D::Connection) -> Result<Vec<AppliedMigration>> {
+        lock(connection).await?;
+        let result = self.run_locked(connection).await;
+        let unlocked = unlock(connection).await;
+        let result = result?;
+        unlocked?;
+        Ok(result)
+    }
+
+    async fn run_locked(&self, connection: &mut D::Connection) -> Result<Vec<AppliedMigration>> {
+        let mut result = Vec::new();
+        for migration in self.pending(connection).await? {
+            log::info!("Applying the migration {} {}", migration.id, migration.name);
+            let applied = self.step(connection, migration, None).await.map_err(|e| {
+                let error = e.context(format!(
+                    "While applying the migration {} {}",
+                    migration.id, migration.name
+                ));
+                log::error!("{error:#}");
+                error
+            })?;
+            result.push(applied);
+        }
+        Ok(result)
+    }
+
+    /// Reverts the last `count` applied migrations, most recent first, returning them.
+    ///
+    /// Every migration to revert must be defined and have a down migration, otherwise nothing is
+    /// reverted. Stops at the first migration failing, the ones reverted before it stay reverted.
+    pub async fn revert(
+        &self,
+        connection: &mut D::Connection,
+        count: usize,
+    ) -> Result<Vec<AppliedMigration>> {
+        lock(connection).await?;
+        let result = self.revert_locked(connection, count).await;
+        let unlocked = unlock(connection).await;
+        let result = result?;
+        unlocked?;
+        Ok(result)
+    }
+
+    async fn revert_locked(
+        &self,
+        connection: &mut D::Connection,
+        count: usize,
+    ) -> Result<Vec<AppliedMigration>> {
+        self.check_ids()?;
+        let applied = self.applied(connection).await?;
+        let reverted = applied
+            .iter()
+            .rev()
+            .take(count)
+            .map(|applied| {
+                let Some(migration) = self.migrations.iter().find(|v| v.id == applied.id) else {
+                    return Err(anyhow!(
+                        "The migration {} cannot be reverted because it is not defined",
+                        applied.id
+                    ));
+                };
+                if !migration.is_reversible() {
+                    return Err(anyhow!(
+                        "The migration {} {} cannot be reverted because it has no down migration",
+                        migration.id,
+                        migration.name
+                    ));
+                }
+                Ok((applied, migration))
+            })
+            .collect::<Result<Vec<_>>>()
+            .inspect_err(|e| log::error!("{e:#}"))?;
+        let mut result = Vec::new();
+        for (applied, migration) in reverted {
+            log::info!(
+                "Reverting the migration {} {}",
+                migration.id,
+                migration.name
+            );
+            let reverted = self
+                .step(connection, migration, Some(applied))
+                .await
+                .map_err(|e| {
+                    let error = e.context(format!(
+                        "While reverting the migration {} {}",
+                        migration.id, migration.name
+                    ));
+                    log::error!("{error:#}");
+                    error
+                })?;
+            result.push(reverted);
+        }
+        Ok(result)
+    }
+
+    /// Applies a migration, or reverts it when `applied` is its record, in a transaction when supported.
+    async fn step(
+        &self,
+        connection: &mut D::Connection,
+        migration: &Migration<D>,
+        applied: Option<&AppliedMigration>,
+    ) -> Result<AppliedMigration> {
+        let transactional = connection.driver().supports_transactional_ddl();
+        let start = Instant::now();
+        let mut executor = if transactional {
+            MigrationExecutor::Transaction(connection.begin().await?)
+        } else {
+            MigrationExecutor::Connection(&mut *connection)
+        };
+        let result = async {
+            if let Some(applied) = applied {
+                if let Some(down) = &migration.down {
+                    down.run(&mut executor).await?;
+                }
+                applied.delete(&mut executor).await?;
+                Ok(applied.clone())
+            } else {
+                migration.action.run(&mut executor).await?;
+                let applied = AppliedMigration {
+                    id: migration.id.to_string(),
+                    name: migration.name.to_string(),
+                    checksum: migration.checksum.clone(),
+                    applied_at: OffsetDateTime::now_utc(),
+                    execution_ms: start.elapsed().as_millis() as _,
+                };
+                AppliedMigration::insert_one(&mut executor, &applied).await?;
+                Ok(applied)
+            }
+        }
+        .await;
+        match (result, executor) {
+            (Ok(result), MigrationExecutor::Transaction(transaction)) => {
+                transaction.commit().await.map(|_| result)
+            }
+            (Err(e), MigrationExecutor::Transaction(transaction)) => {
+                let _ = transaction.rollback().await;
+                Err(e)
+            }
+            (result, MigrationExecutor::Connection(..)) => result,
+        }
+    }
+
+    fn check_ids(&self) -> Result<()> {
+        for (i, migration) in self.migrations.iter().enumerate() {
+            if self.migrations[..i].iter().any(|v| v.id == migration.id) {
+                let error = anyhow!("The migration id {} is not unique", migration.id);
+                log::error!("{error:#}");
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<D: Driver> Default for Migrator<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn checksum(content: &str) -> String {
+    let mut result = String::with_capacity(64);
+    for b in Sha256::digest(content.as_bytes()) {
+        let _ = write!(result, "{b:02x}");
+    }
+    result
+}
+
+async fn lock(connection: &mut impl Connection) -> Result<()> {
+    let mut query = DynQuery::default();
+    connection
+        .driver()
+        .sql_writer()
+        .write_advisory_lock(&mut query, LOCK_NAME);
+    if !query.is_empty() {
+        connection
+            .execute(query)
+            .await
+            .context("While acquiring the migrations lock")?;
+        return Ok(());
+    }
+    MigrationLock::create_table(connection, true, false).await?;
+    // Not atomic on databases where inserting does not fail on duplicate keys
+    if let Some(lock) =
+        MigrationLock::find_one(connection, expr!(MigrationLock::name == #LOCK_NAME)).await?
+    {
+        let error = anyhow!(
+            "The migrations are locked by another instance since {}, delete the row of {} if it is stale",
+            lock.locked_at,
+            MigrationLock::table().full_name("."),
+        );
+        log::error!("{error:#}");
+        return Err(error);
+    }
+    let lock = MigrationLock {
+        name: LOCK_NAME.into(),
+        locked_at: OffsetDateTime::now_utc(),
+    };
+    MigrationLock::insert_one(connection, &lock)
+        .await
+        .context("While acquiring the migrations lock")?;
+    Ok(())
+}
+
+async fn unlock(connection: &mut impl Connection) -> Result<()> {
+    let mut query = DynQuery::default();
+    connection
+        .driver()
+        .sql_writer()
+        .write_advisory_unlock(&mut query, LOCK_NAME);
+    if query.is_empty() {
+        MigrationLock::delete_many(connection, expr!(MigrationLock::name == #LOCK_NAME)).await?;
+    } else {
+        connection.execute(query).await?;
+    }
+    Ok(())
+}
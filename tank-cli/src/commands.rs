@@ -0,0 +1,333 @@
+use crate::output::{self, Format};
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    pin::pin,
+};
+use tank::{
+    Connection, Context, Driver, DynQuery, ErrorContext, Executor, Fragment, Migrator,
+    PrimaryKeyType, QueryBuilder, QueryResult, Result, Row, SqlWriter, TableRef, Transaction,
+    anyhow, cols, split_sql_statements, stream::TryStreamExt,
+};
+
+/// Rows per INSERT statement written by `dump`.
+const DUMP_BATCH: usize = 100;
+
+/// Url schemes of the drivers that `dump` and `load` cannot work with, they do not speak SQL.
+const NON_SQL_SCHEMES: [&str; 5] = ["mongodb", "redis", "rediss", "valkey", "valkeys"];
+
+/// Operation selected by the command line arguments.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Ping,
+    Describe(String),
+    Sql(String, Format),
+    MigrateUp(PathBuf),
+    MigrateDown(PathBuf, usize),
+    MigrateStatus(PathBuf),
+    Dump(String, Vec<String>),
+    Load(String),
+}
+
+const USAGE: &str = "\
+Usage: tank [--url <url>] <command>
+
+The url defaults to the TANK_URL environment variable, its scheme selects the driver.
+
+Commands:
+  ping                                    Checks the connection
+  describe <table>                        Prints the columns of a table, as schema.table or table
+  sql <query> [--format table|json|csv]   Runs a query and prints the rows
+  migrate up [--dir <path>]               Applies the pending migrations (from ./migrations)
+  migrate down [<count>] [--dir <path>]   Reverts the last <count> migrations, 1 by default
+  migrate status [--dir <path>]           Lists the migrations and whether they are applied
+  dump [<table>...] [--schema <schema>]   Prints the rows as INSERT statements, all tables by default,
+                                          generated and identity columns included
+  load <file>                             Runs a SQL script (like a dump) in a transaction, - for stdin";
+
+/// Splits the arguments into the url, the options and the positional arguments, then parses the command.
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<(String, Command)> {
+    let mut url = env::var("TANK_URL").ok();
+    let mut format = Format::default();
+    let mut dir = PathBuf::from("migrations");
+    let mut schema = String::new();
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| anyhow!("The option {name} requires a value"))
+        };
+        match arg.as_str() {
+            "--url" => url = Some(value("--url")?),
+            "--format" => format = value("--format")?.parse()?,
+            "--dir" => dir = value("--dir")?.into(),
+            "--schema" => schema = value("--schema")?,
+            "-h" | "--help" => return Err(anyhow!("{USAGE}")),
+            v if v.starts_with("--") => return Err(anyhow!("Unknown option {v}\n\n{USAGE}")),
+            _ => positional.push(arg),
+        }
+    }
+    let url = url.ok_or_else(|| anyhow!("Missing --url and TANK_URL is not set\n\n{USAGE}"))?;
+    let positional = positional.iter().map(String::as_str).collect::<Vec<_>>();
+    let command = match positional.as_slice() {
+        ["ping"] => Command::Ping,
+        ["describe", table] => Command::Describe(table.to_string()),
+        ["sql", query] => Command::Sql(query.to_string(), format),
+        ["migrate", "up"] => Command::MigrateUp(dir),
+        ["migrate", "down"] => Command::MigrateDown(dir, 1),
+        ["migrate", "down", count] => Command::MigrateDown(
+            dir,
+            count
+                .parse()
+                .map_err(|_| anyhow!("Invalid migrations count `{count}`"))?,
+        ),
+        ["migrate", "status"] => Command::MigrateStatus(dir),
+        ["dump", tables @ ..] => {
+            Command::Dump(schema, tables.iter().map(|v| v.to_string()).collect())
+        }
+        ["load", file] => Command::Load(file.to_string()),
+        _ => return Err(anyhow!("{USAGE}")),
+    };
+    if matches!(command, Command::Dump(..) | Command::Load(..)) {
+        let scheme = url.split_once("://").map(|v| v.0).unwrap_or_default();
+        if NON_SQL_SCHEMES.contains(&scheme) {
+            return Err(anyhow!(
+                "The dump and load commands require a SQL database, `{scheme}://` is not supported"
+            ));
+        }
+    }
+    Ok((url, command))
+}
+
+impl Command {
+    /// Runs the command on the connection, printing the result.
+    pub async fn run<D: Driver>(self, connection: &mut D::Connection) -> Result<()> {
+        match self {
+            Command::Ping => {
+                connection.ping().await?;
+                println!("Connected to {}", connection.driver().name());
+            }
+            Command::Describe(table) => {
+                let columns = connection.describe_table(&table_ref(&table)).await?;
+                let rows = columns
+                    .iter()
+                    .map(|c| {
+                        vec![
+                            c.name().to_string(),
+                            c.column_type.clone(),
+                            if c.nullable { "yes" } else { "no" }.into(),
+                            c.default.clone().unwrap_or_default(),
+                            match c.primary_key {
+                                PrimaryKeyType::None if c.unique => "unique".into(),
+                                PrimaryKeyType::None if c.clustering_key => "clustering".into(),
+                                PrimaryKeyType::None => String::new(),
+                                _ => "primary".into(),
+                            },
+                            c.references
+                                .as_ref()
+                                .map(|v| format!("{}({})", v.table().full_name("."), v.name))
+                                .unwrap_or_default(),
+                            c.comment.clone(),
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let labels = [
+                    "column",
+                    "type",
+                    "nullable",
+                    "default",
+                    "key",
+                    "references",
+                    "comment",
+                ]
+                .map(String::from);
+                print!("{}", output::table(&labels, &rows));
+            }
+            Command::Sql(query, format) => {
+                let mut labels = Vec::new();
+                let mut rows = Vec::new();
+                let mut affected = None;
+                let mut stream = pin!(connection.run(query));
+                while let Some(result) = stream.try_next().await? {
+                    match result {
+                        QueryResult::Row(Row {
+                            labels: row_labels,
+                            values,
+                        }) => {
+                            if labels.is_empty() {
+                                labels = row_labels.to_vec();
+                            }
+                            rows.push(values.into_vec());
+                        }
+                        QueryResult::Affected(v) => {
+                            affected = Some(affected.unwrap_or(0) + v.rows_affected.unwrap_or(0))
+                        }
+                    }
+                }
+                if !labels.is_empty() || affected.is_none() {
+                    print!("{}", output::render(format, &labels, &rows));
+                }
+                if let Some(affected) = affected {
+                    eprintln!("{affected} rows affected");
+                }
+            }
+            Command::MigrateUp(dir) => {
+                let migrator = Migrator::<D>::new().with_directory(dir)?;
+                let applied = migrator.run(connection).await?;
+                for migration in &applied {
+                    println!(
+                        "Applied {} {} ({} ms)",
+                        migration.id, migration.name, migration.execution_ms
+                    );
+                }
+                if applied.is_empty() {
+                    println!("No pending migrations");
+                }
+            }
+            Command::MigrateDown(dir, count) => {
+                let migrator = Migrator::<D>::new().with_directory(dir)?;
+                for migration in migrator.revert(connection, count).await? {
+                    println!("Reverted {} {}", migration.id, migration.name);
+                }
+            }
+            Command::MigrateStatus(dir) => {
+                let migrator = Migrator::<D>::new().with_directory(dir)?;
+                let applied = migrator.applied(connection).await?;
+                let mut status = applied
+                    .iter()
+                    .map(|v| (v.id.as_str(), v))
+                    .collect::<HashMap<_, _>>();
+                let mut rows = Vec::new();
+                for migration in migrator.migrations() {
+                    let state = match status.remove(migration.id.as_ref()) {
+                        Some(v) if v.checksum != migration.checksum => "modified".into(),
+                        Some(v) => format!("applied {}", v.applied_at),
+                        None => "pending".into(),
+                    };
+                    rows.push(vec![
+                        migration.id.to_string(),
+                        migration.name.to_string(),
+                        state,
+                    ]);
+                }
+                for migration in applied
+                    .iter()
+                    .filter(|v| status.contains_key(v.id.as_str()))
+                {
+                    rows.push(vec![
+                        migration.id.clone(),
+                        migration.name.clone(),
+                        format!("applied {}, not defined", migration.applied_at),
+                    ]);
+                }
+                let labels = ["id", "name", "status"].map(String::from);
+                print!("{}", output::table(&labels, &rows));
+            }
+            Command::Dump(schema, tables) => {
+                let tables = if tables.is_empty() {
+                    connection.list_tables(&schema).await?
+                } else {
+                    tables.iter().map(|v| table_ref(v)).collect()
+                };
+                for table in &tables {
+                    dump(connection, table).await.with_context(|| {
+                        format!("While dumping the table {}", table.full_name("."))
+                    })?;
+                }
+            }
+            Command::Load(file) => {
+                let mut sql = String::new();
+                if file == "-" {
+                    io::stdin().read_to_string(&mut sql)?;
+                } else {
+                    sql = fs::read_to_string(&file)
+                        .with_context(|| format!("While reading {file}"))?;
+                }
+                let mut transaction = connection.begin().await?;
+                let result = if transaction.accepts_multiple_statements() {
+                    transaction.execute(sql.as_str()).await
+                } else {
+                    let mut result = Ok(Default::default());
+                    for statement in split_sql_statements(&sql) {
+                        result = transaction.execute(statement).await;
+                        if result.is_err() {
+                            break;
+                        }
+                    }
+                    result
+                };
+                match result {
+                    Ok(..) => transaction.commit().await?,
+                    Err(e) => {
+                        let _ = transaction.rollback().await;
+                        return Err(e.context(format!("While loading {file}")));
+                    }
+                }
+                eprintln!("Loaded {file}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses `schema.table` or `table`.
+fn table_ref(value: &str) -> TableRef {
+    let (schema, name) = value.split_once('.').unwrap_or(("", value));
+    let mut result = TableRef::new(name.to_string().into());
+    result.schema = schema.to_string().into();
+    result
+}
+
+/// Prints the rows of a table as INSERT statements written by the driver.
+///
+/// Every column is written, generated columns and `GENERATED ALWAYS` identity columns included:
+/// loading the result into tables having them requires removing those columns first.
+async fn dump(connection: &mut impl Connection, table: &TableRef) -> Result<()> {
+    let writer = connection.driver().sql_writer();
+    let mut context = Context::new(Fragment::SqlInsertInto, false);
+    let mut values = Context::new(Fragment::SqlInsertIntoValues, false);
+    // Built by the driver, not every backend understands raw SQL
+    let query = QueryBuilder::new()
+        .select(cols!(*))
+        .from(table)
+        .where_expr(true)
+        .build(&connection.driver());
+    let mut stream = pin!(connection.fetch(query));
+    let mut batch = Vec::<Row>::with_capacity(DUMP_BATCH);
+    loop {
+        let row = stream.try_next().await?;
+        let flush = row.is_none() || batch.len() == DUMP_BATCH;
+        if flush && let Some(first) = batch.first() {
+            let mut out = DynQuery::default();
+            out.push_str("INSERT INTO ");
+            writer.write_table_ref(&mut context, &mut out, table);
+            out.push_str(" (");
+            for (i, label) in first.labels.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                writer.write_identifier(&mut context, &mut out, label, true);
+            }
+            out.push_str(") VALUES");
+            for (i, row) in batch.drain(..).enumerate() {
+                out.push_str(if i == 0 { "\n(" } else { ",\n(" });
+                for (j, value) in row.values.iter().enumerate() {
+                    if j > 0 {
+                        out.push_str(", ");
+                    }
+                    writer.write_value(&mut values, &mut out, value);
+                }
+                out.push(')');
+            }
+            out.push(';');
+            println!("{}", out.buffer());
+        }
+        match row {
+            Some(row) => batch.push(row),
+            None => break,
+        }
+    }
+    Ok(())
+}
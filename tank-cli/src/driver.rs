@@ -0,0 +1,45 @@
+use std::future::Future;
+use tank::{Driver, Result, anyhow};
+
+/// Work done with the driver selected by the url scheme, see [`with_driver`].
+pub trait DriverTask {
+    type Output;
+    /// Runs the task with the driver `D`.
+    fn run<D: Driver>(self, url: String) -> impl Future<Output = Result<Self::Output>>;
+}
+
+/// Runs the task with the enabled driver whose name matches the scheme of the url.
+pub async fn with_driver<T: DriverTask>(url: String, task: T) -> Result<T::Output> {
+    let scheme = url.split_once("://").map(|v| v.0).unwrap_or_default();
+    match scheme {
+        #[cfg(feature = "duckdb")]
+        v if tank_duckdb::DuckDBDriver::NAME.contains(&v) => {
+            task.run::<tank_duckdb::DuckDBDriver>(url).await
+        }
+        #[cfg(feature = "mongodb")]
+        v if tank_mongodb::MongoDBDriver::NAME.contains(&v) => {
+            task.run::<tank_mongodb::MongoDBDriver>(url).await
+        }
+        #[cfg(feature = "mysql")]
+        v if tank_mysql::MySQLDriver::NAME.contains(&v) => {
+            task.run::<tank_mysql::MySQLDriver>(url).await
+        }
+        #[cfg(feature = "postgres")]
+        v if tank_postgres::PostgresDriver::NAME.contains(&v) => {
+            task.run::<tank_postgres::PostgresDriver>(url).await
+        }
+        #[cfg(feature = "scylladb")]
+        v if tank_scylladb::ScyllaDBDriver::NAME.contains(&v) => {
+            task.run::<tank_scylladb::ScyllaDBDriver>(url).await
+        }
+        #[cfg(feature = "sqlite")]
+        v if tank_sqlite::SQLiteDriver::NAME.contains(&v) => {
+            task.run::<tank_sqlite::SQLiteDriver>(url).await
+        }
+        #[cfg(feature = "valkey")]
+        v if tank_valkey::ValkeyDriver::NAME.contains(&v) => {
+            task.run::<tank_valkey::ValkeyDriver>(url).await
+        }
+        _ => Err(anyhow!("No driver enabled for the url scheme `{scheme}`")),
+    }
+}
@@ -0,0 +1,8 @@
+//! Commands and driver selection of the Tank command-line tools.
+
+mod commands;
+mod driver;
+pub mod output;
+
+pub use commands::*;
+pub use driver::*;
@@ -0,0 +1,33 @@
+use std::{env, process::ExitCode};
+use tank::{Connection, Driver, Result};
+use tank_cli::{Command, DriverTask, parse, with_driver};
+
+/// Connects to the database and runs the command.
+struct Run(Command);
+
+impl DriverTask for Run {
+    type Output = ();
+    async fn run<D: Driver>(self, url: String) -> Result<()> {
+        let mut connection = D::Connection::connect(&D::default(), url.into()).await?;
+        self.0.run::<D>(&mut connection).await
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let (url, command) = match parse(env::args().skip(1)) {
+        Ok(v) => v,
+        Err(error) => {
+            eprintln!("{error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = with_driver(url, Run(command)).await;
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
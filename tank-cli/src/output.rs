@@ -0,0 +1,155 @@
+use serde_json::Value as JsonValue;
+use std::{fmt::Write, str::FromStr};
+use tank::{Error, Result, Value, anyhow};
+
+/// How `tank sql` prints the rows.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(anyhow!(
+                "Unknown format `{s}`, expected one of: table, json, csv"
+            )),
+        }
+    }
+}
+
+/// Renders the rows in the requested format.
+pub fn render(format: Format, labels: &[String], rows: &[Vec<Value>]) -> String {
+    match format {
+        Format::Table => table(
+            labels,
+            &rows
+                .iter()
+                .map(|row| row.iter().map(text).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+        ),
+        Format::Json => {
+            let mut out = String::from("[");
+            for (i, row) in rows.iter().enumerate() {
+                out.push_str(if i == 0 { "\n  {" } else { ",\n  {" });
+                for (j, (label, value)) in labels.iter().zip(row).enumerate() {
+                    if j > 0 {
+                        out.push_str(", ");
+                    }
+                    let _ = write!(out, "{}: {}", JsonValue::from(label.as_str()), json(value));
+                }
+                out.push('}');
+            }
+            out.push_str(if rows.is_empty() { "]\n" } else { "\n]\n" });
+            out
+        }
+        Format::Csv => {
+            let mut out = String::new();
+            csv_line(&mut out, labels.iter().map(String::as_str));
+            for row in rows {
+                let row = row.iter().map(text).collect::<Vec<_>>();
+                csv_line(&mut out, row.iter().map(String::as_str));
+            }
+            out
+        }
+    }
+}
+
+/// Aligned plain text table, with a header separated by a line of dashes.
+pub fn table(labels: &[String], rows: &[Vec<String>]) -> String {
+    let mut widths = labels.iter().map(|v| v.chars().count()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    let line = |out: &mut String, cells: &mut dyn Iterator<Item = &str>| {
+        let mut line = String::new();
+        for (i, (cell, width)) in cells.zip(&widths).enumerate() {
+            if i > 0 {
+                line.push_str(" | ");
+            }
+            let _ = write!(line, "{cell:width$}");
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    };
+    line(&mut out, &mut labels.iter().map(String::as_str));
+    let dashes = widths.iter().map(|v| "-".repeat(*v)).collect::<Vec<_>>();
+    out.push_str(&dashes.join("-+-"));
+    out.push('\n');
+    for row in rows {
+        line(&mut out, &mut row.iter().map(String::as_str));
+    }
+    out
+}
+
+/// Human readable value: strings unquoted, null empty, everything else as a SQL literal.
+pub fn text(value: &Value) -> String {
+    match value {
+        v if v.is_null() => String::new(),
+        Value::Char(Some(v)) => v.to_string(),
+        Value::Varchar(Some(v)) => v.to_string(),
+        Value::Json(Some(v)) => v.to_string(),
+        Value::Unknown(Some(v)) => v.clone(),
+        v => {
+            let literal = v.to_string();
+            match literal
+                .strip_prefix('\'')
+                .and_then(|v| v.strip_suffix('\''))
+            {
+                Some(v) => v.replace("''", "'"),
+                None => literal,
+            }
+        }
+    }
+}
+
+/// JSON value, numbers and booleans are kept, the rest becomes strings.
+pub fn json(value: &Value) -> JsonValue {
+    match value {
+        v if v.is_null() => JsonValue::Null,
+        Value::Boolean(Some(v)) => (*v).into(),
+        Value::Int8(Some(v)) => (*v).into(),
+        Value::Int16(Some(v)) => (*v).into(),
+        Value::Int32(Some(v)) => (*v).into(),
+        Value::Int64(Some(v)) => (*v).into(),
+        Value::UInt8(Some(v)) => (*v).into(),
+        Value::UInt16(Some(v)) => (*v).into(),
+        Value::UInt32(Some(v)) => (*v).into(),
+        Value::UInt64(Some(v)) => (*v).into(),
+        Value::Float32(Some(v)) => (*v).into(),
+        Value::Float64(Some(v)) => (*v).into(),
+        Value::Json(Some(v)) => v.clone(),
+        Value::Array(Some(v), ..) => v.iter().map(json).collect(),
+        Value::List(Some(v), ..) => v.iter().map(json).collect(),
+        Value::Map(Some(v), ..) => v.iter().map(|(k, v)| (text(k), json(v))).collect(),
+        Value::Struct(Some(v), ..) => v.iter().map(|(k, v)| (k.clone(), json(v))).collect(),
+        v => text(v).into(),
+    }
+}
+
+fn csv_line<'a>(out: &mut String, cells: impl Iterator<Item = &'a str>) {
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(cell);
+        }
+    }
+    out.push('\n');
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use serde_json::json;
+    use tank::Value;
+    use tank_cli::output::{Format, json, render, table, text};
+
+    fn labels() -> Vec<String> {
+        ["id", "name", "score"].map(String::from).to_vec()
+    }
+
+    fn rows() -> Vec<Vec<Value>> {
+        vec![
+            vec![
+                Value::Int64(Some(1)),
+                Value::Varchar(Some("O'Brien".into())),
+                Value::Float64(Some(7.5)),
+            ],
+            vec![
+                Value::Int64(Some(20)),
+                Value::Varchar(Some("Smith, \"Jr\"".into())),
+                Value::Float64(None),
+            ],
+        ]
+    }
+
+    #[test]
+    fn test_output_table() {
+        assert_eq!(
+            table(
+                &labels(),
+                &[
+                    vec!["1".into(), "Ann".into(), "7.5".into()],
+                    vec!["20".into(), "Bartholomew".into(), String::new()],
+                ]
+            ),
+            indoc! {"
+                id | name        | score
+                ---+-------------+------
+                1  | Ann         | 7.5
+                20 | Bartholomew |
+            "}
+        );
+    }
+
+    #[test]
+    fn test_output_formats() {
+        assert_eq!(
+            render(Format::Csv, &labels(), &rows()),
+            indoc! {r#"
+                id,name,score
+                1,O'Brien,7.5
+                20,"Smith, ""Jr""",
+            "#}
+        );
+        assert_eq!(
+            render(Format::Json, &labels(), &rows()),
+            indoc! {r#"
+                [
+                  {"id": 1, "name": "O'Brien", "score": 7.5},
+                  {"id": 20, "name": "Smith, \"Jr\"", "score": null}
+                ]
+            "#}
+        );
+        assert_eq!(render(Format::Json, &labels(), &[]), "[]\n");
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_output_values() {
+        assert_eq!(text(&Value::Varchar(Some("O'Brien".into()))), "O'Brien");
+        assert_eq!(text(&Value::Int32(None)), "");
+        assert_eq!(text(&Value::Boolean(Some(true))), "true");
+        assert_eq!(json(&Value::UInt8(Some(3))), json!(3));
+        assert_eq!(
+            json(&Value::List(
+                Some(vec![Value::Int32(Some(1)), Value::Int32(None)]),
+                Box::new(Value::Int32(None))
+            )),
+            json!([1, null])
+        );
+    }
+}
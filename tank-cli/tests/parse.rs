@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use tank_cli::{Command, output::Format, parse};
+
+    fn args(line: &str) -> impl Iterator<Item = String> {
+        line.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let url = "sqlite://:memory:";
+        assert_eq!(
+            parse(args("--url sqlite://:memory: ping")).unwrap(),
+            (url.into(), Command::Ping)
+        );
+        assert_eq!(
+            parse(args("describe shop.orders --url sqlite://:memory:"))
+                .unwrap()
+                .1,
+            Command::Describe("shop.orders".into())
+        );
+        assert_eq!(
+            parse(args("--url sqlite://:memory: sql SELECT --format csv"))
+                .unwrap()
+                .1,
+            Command::Sql("SELECT".into(), Format::Csv)
+        );
+        assert_eq!(
+            parse(args(
+                "--url sqlite://:memory: dump --schema shop orders items"
+            ))
+            .unwrap()
+            .1,
+            Command::Dump("shop".into(), vec!["orders".into(), "items".into()])
+        );
+        assert_eq!(
+            parse(args("--url sqlite://:memory: load -")).unwrap().1,
+            Command::Load("-".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_migrate() {
+        assert_eq!(
+            parse(args("--url sqlite://:memory: migrate up")).unwrap().1,
+            Command::MigrateUp(PathBuf::from("migrations"))
+        );
+        assert_eq!(
+            parse(args("--url sqlite://:memory: migrate down --dir db"))
+                .unwrap()
+                .1,
+            Command::MigrateDown(PathBuf::from("db"), 1)
+        );
+        assert_eq!(
+            parse(args("--url sqlite://:memory: migrate down 3"))
+                .unwrap()
+                .1,
+            Command::MigrateDown(PathBuf::from("migrations"), 3)
+        );
+        assert_eq!(
+            parse(args("--url sqlite://:memory: migrate status --dir db"))
+                .unwrap()
+                .1,
+            Command::MigrateStatus(PathBuf::from("db"))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |line| parse(args(line)).unwrap_err().to_string();
+        assert!(error("--url sqlite://:memory: migrate down many").contains("`many`"));
+        assert!(error("--url sqlite://:memory: sql SELECT --format xml").contains("`xml`"));
+        assert!(error("--url sqlite://:memory: ping --verbose").contains("--verbose"));
+        assert!(error("--url sqlite://:memory: ping --dir").contains("requires a value"));
+        assert!(error("--url sqlite://:memory: shout").starts_with("Usage"));
+        assert!(error("--help").starts_with("Usage"));
+        assert!(error("--url mongodb://localhost dump").contains("require a SQL database"));
+        assert!(error("--url redis://localhost load data.sql").contains("`redis://`"));
+    }
+}
@@ -0,0 +1,35 @@
+use std::{env, process::ExitCode};
+use tank::{Connection, Driver, Result, generate_entities};
+use tank_cli::{DriverTask, with_driver};
+
+/// Connects to the database and generates the entities of its tables.
+struct Generate(String);
+
+impl DriverTask for Generate {
+    type Output = String;
+    async fn run<D: Driver>(self, url: String) -> Result<String> {
+        let mut connection = D::Connection::connect(&D::default(), url.into()).await?;
+        generate_entities(&mut connection, &self.0).await
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let Some(url) = args.next() else {
+        eprintln!("Usage: tank-codegen <url> [schema] > entities.rs");
+        return ExitCode::FAILURE;
+    };
+    let schema = args.next().unwrap_or_default();
+    let result = with_driver(url, Generate(schema)).await;
+    match result {
+        Ok(source) => {
+            print!("{source}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(unused_imports)]
 use crate::{
-    Error, FixedDecimal, Interval, Result, Value, consume_while, extract_number, truncate_long,
+    EnumDef, Error, FixedDecimal, Interval, Result, TypeKind, Value, consume_while, extract_number,
+    truncate_long,
 };
 use crate::{month_to_number, number_to_month};
 use anyhow::{Context, anyhow};
@@ -44,6 +45,14 @@ pub trait AsValue {
             any::type_name::<Self>()
         ))
     }
+    /// Enum definition, for types stored as one of a fixed set of values.
+    fn enum_def() -> Option<&'static EnumDef> {
+        None
+    }
+    /// Whether the type can hold NULL, like `Option<T>`.
+    const NULLABLE: bool = false;
+    /// Kind of the type, used by `expr!` to check the operands at compile time.
+    const KIND: TypeKind = TypeKind::Unknown;
 }
 
 impl AsValue for Value {
@@ -70,6 +79,7 @@ impl From<&'static str> for Value {
 macro_rules! impl_as_value {
     ($source:ty, $destination:path $(, $pat_rest:pat => $expr_rest:expr)* $(,)?) => {
         impl AsValue for $source {
+            const KIND: TypeKind = TypeKind::of(&$destination(None));
             fn as_empty_value() -> Value {
                 $destination(None)
             }
@@ -358,6 +368,7 @@ impl_as_value!(
 macro_rules! impl_as_value {
     ($source:ty, $helper:path) => {
         impl AsValue for $source {
+            const KIND: TypeKind = <$helper as AsValue>::KIND;
             fn as_empty_value() -> Value {
                 <$helper as AsValue>::as_empty_value()
             }
@@ -390,6 +401,7 @@ impl_as_value!(NonZeroUsize, usize);
 macro_rules! impl_as_value {
     ($source:ty, $destination:path, $extract:expr $(, $pat_rest:pat => $expr_rest:expr)* $(,)?) => {
         impl AsValue for $source {
+            const KIND: TypeKind = TypeKind::of(&$destination(None));
             fn as_empty_value() -> Value {
                 $destination(None)
             }
@@ -842,6 +854,7 @@ impl_as_value!(
 );
 
 impl AsValue for UtcDateTime {
+    const KIND: TypeKind = TypeKind::Date;
     fn as_empty_value() -> Value {
         time::PrimitiveDateTime::as_empty_value()
     }
@@ -889,6 +902,7 @@ impl_as_value!(
 
 #[cfg(feature = "chrono")]
 impl AsValue for chrono::NaiveDate {
+    const KIND: TypeKind = TypeKind::Date;
     fn as_empty_value() -> Value {
         Value::Date(None)
     }
@@ -928,6 +942,7 @@ impl AsValue for chrono::NaiveDate {
 
 #[cfg(feature = "chrono")]
 impl AsValue for chrono::NaiveTime {
+    const KIND: TypeKind = TypeKind::Time;
     fn as_empty_value() -> Value {
         Value::Time(None)
     }
@@ -963,6 +978,7 @@ impl AsValue for chrono::NaiveTime {
 
 #[cfg(feature = "chrono")]
 impl AsValue for chrono::NaiveDateTime {
+    const KIND: TypeKind = TypeKind::Date;
     fn as_empty_value() -> Value {
         Value::Timestamp(None)
     }
@@ -1005,6 +1021,7 @@ impl AsValue for chrono::NaiveDateTime {
 
 #[cfg(feature = "chrono")]
 impl AsValue for chrono::DateTime<chrono::FixedOffset> {
+    const KIND: TypeKind = TypeKind::Date;
     fn as_empty_value() -> Value {
         Value::TimestampWithTimezone(None)
     }
@@ -1056,6 +1073,7 @@ impl AsValue for chrono::DateTime<chrono::FixedOffset> {
 
 #[cfg(feature = "chrono")]
 impl AsValue for chrono::DateTime<chrono::Utc> {
+    const KIND: TypeKind = TypeKind::Date;
     fn as_empty_value() -> Value {
         Value::TimestampWithTimezone(None)
     }
@@ -1077,6 +1095,7 @@ impl AsValue for chrono::DateTime<chrono::Utc> {
 }
 
 impl AsValue for Decimal {
+    const KIND: TypeKind = TypeKind::Numeric;
     fn as_empty_value() -> Value {
         Value::Decimal(None, 0, 0)
     }
@@ -1130,6 +1149,7 @@ impl AsValue for Decimal {
 }
 
 impl<const W: u8, const S: u8> AsValue for FixedDecimal<W, S> {
+    const KIND: TypeKind = TypeKind::Numeric;
     fn as_empty_value() -> Value {
         Decimal::as_empty_value()
     }
@@ -1148,6 +1168,7 @@ impl<const W: u8, const S: u8> AsValue for FixedDecimal<W, S> {
 }
 
 impl<T: AsValue, const N: usize> AsValue for [T; N] {
+    const KIND: TypeKind = TypeKind::List(&T::KIND);
     fn as_empty_value() -> Value {
         Value::Array(None, Box::new(T::as_empty_value()), N as u32)
     }
@@ -1197,6 +1218,7 @@ impl<T: AsValue, const N: usize> AsValue for [T; N] {
 macro_rules! impl_as_value {
     ($source:ident) => {
         impl<T: AsValue> AsValue for $source<T> {
+            const KIND: TypeKind = TypeKind::List(&T::KIND);
             fn as_empty_value() -> Value {
                 Value::List(None, Box::new(T::as_empty_value()))
             }
@@ -1237,6 +1259,7 @@ impl_as_value!(LinkedList);
 macro_rules! impl_as_value {
     ($source:ident, $($key_trait:ident),+) => {
         impl<K: AsValue $(+ $key_trait)+, V: AsValue> AsValue for $source<K, V> {
+            const KIND: TypeKind = TypeKind::Map;
             fn as_empty_value() -> Value {
                 Value::Map(None, K::as_empty_value().into(), V::as_empty_value().into())
             }
@@ -1289,6 +1312,7 @@ impl_as_value!(BTreeMap, Ord);
 impl_as_value!(HashMap, Eq, Hash);
 
 impl AsValue for &'static str {
+    const KIND: TypeKind = TypeKind::Text;
     fn as_empty_value() -> Value {
         Value::Varchar(None)
     }
@@ -1309,6 +1333,7 @@ impl AsValue for &'static str {
 }
 
 impl AsValue for Cow<'static, str> {
+    const KIND: TypeKind = TypeKind::Text;
     fn as_empty_value() -> Value {
         Value::Varchar(None)
     }
@@ -1330,9 +1355,14 @@ impl AsValue for Cow<'static, str> {
 }
 
 impl<T: AsValue> AsValue for Option<T> {
+    const NULLABLE: bool = true;
+    const KIND: TypeKind = T::KIND;
     fn as_empty_value() -> Value {
         T::as_empty_value()
     }
+    fn enum_def() -> Option<&'static EnumDef> {
+        T::enum_def()
+    }
     fn as_value(self) -> Value {
         match self {
             Some(v) => v.as_value(),
@@ -1361,6 +1391,8 @@ impl<T: AsValue> AsValue for Option<T> {
 
 // TODO: Use the macro below once box_into_inner is stabilized
 impl<T: AsValue> AsValue for Box<T> {
+    const NULLABLE: bool = T::NULLABLE;
+    const KIND: TypeKind = T::KIND;
     fn as_empty_value() -> Value {
         T::as_empty_value()
     }
@@ -1381,6 +1413,8 @@ impl<T: AsValue> AsValue for Box<T> {
 macro_rules! impl_as_value {
     ($source:ident) => {
         impl<T: AsValue + ToOwned<Owned = impl AsValue>> AsValue for $source<T> {
+            const NULLABLE: bool = T::NULLABLE;
+            const KIND: TypeKind = T::KIND;
             fn as_empty_value() -> Value {
                 T::as_empty_value()
             }
@@ -1398,6 +1432,8 @@ impl_as_value!(Cell);
 impl_as_value!(RefCell);
 
 impl<T: AsValue> AsValue for RwLock<T> {
+    const NULLABLE: bool = T::NULLABLE;
+    const KIND: TypeKind = T::KIND;
     fn as_empty_value() -> Value {
         T::as_empty_value()
     }
@@ -1420,6 +1456,8 @@ impl<T: AsValue> AsValue for RwLock<T> {
 macro_rules! impl_as_value {
     ($source:ident) => {
         impl<T: AsValue + ToOwned<Owned = impl AsValue>> AsValue for $source<T> {
+            const NULLABLE: bool = T::NULLABLE;
+            const KIND: TypeKind = T::KIND;
             fn as_empty_value() -> Value {
                 T::as_empty_value()
             }
@@ -1444,6 +1482,7 @@ impl_as_value!(Arc);
 impl_as_value!(Rc);
 
 impl AsValue for serde_json::Value {
+    const KIND: TypeKind = TypeKind::Json;
     fn as_empty_value() -> Value {
         Value::Json(None)
     }
@@ -1,6 +1,6 @@
 use crate::{
-    DefaultValueType, DynQuery, Expression, ExpressionVisitor, OpPrecedence, SqlWriter, TableRef,
-    Value, writer::Context,
+    DefaultValueType, DynQuery, EnumDef, Expression, ExpressionVisitor, OpPrecedence, SqlWriter,
+    TableRef, Value, writer::Context,
 };
 use proc_macro2::TokenStream;
 use quote::{ToTokens, TokenStreamExt, quote};
@@ -67,7 +67,7 @@ impl ToTokens for PrimaryKeyType {
 }
 
 /// Referential action for foreign key updates or deletes.
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Action {
     /// No action.
     #[default]
@@ -103,6 +103,8 @@ pub struct ColumnDef {
     pub column_type: BTreeMap<&'static str, &'static str>,
     /// Type descriptor.
     pub value: Value,
+    /// Enum stored, for columns of a `#[derive(Enum)]` type.
+    pub enum_def: Option<&'static EnumDef>,
     /// Is nullable.
     pub nullable: bool,
     /// Default expression.
@@ -111,6 +113,8 @@ pub struct ColumnDef {
     pub primary_key: PrimaryKeyType,
     /// Clustering key (relevant for ScyllaDB/Cassandra).
     pub clustering_key: bool,
+    /// Value generated by the database (identity or auto increment).
+    pub auto_increment: bool,
     /// Single-column unique constraint.
     pub unique: bool,
     /// Foreign key target.
@@ -119,6 +123,12 @@ pub struct ColumnDef {
     pub on_delete: Option<Action>,
     /// On update action.
     pub on_update: Option<Action>,
+    /// Check constraint, built on demand.
+    pub check: Option<fn() -> Box<dyn Expression>>,
+    /// Expression computing a generated column, built on demand.
+    pub generated: Option<fn() -> Box<dyn Expression>>,
+    /// Generated column is stored rather than computed when read.
+    pub stored: bool,
     /// Comment.
     pub comment: &'static str,
 }
@@ -0,0 +1,87 @@
+use crate::{ColumnDef, ColumnRef, Expression, FieldAccess, Result, Row, RowValues};
+use std::borrow::Cow;
+
+/// Column name (without the prefix) stored in a field path, with the number of segments consumed.
+pub type FieldColumn = Option<(Cow<'static, str>, usize)>;
+
+/// Group of columns embedded into entities with `#[tank(flatten)]`.
+///
+/// Use `#[derive(Columns)]` to implement this trait.
+pub trait Columns {
+    /// Column definitions, named with `prefix` and belonging to the table provided.
+    fn columns(prefix: &str, table: &'static str, schema: &'static str) -> Vec<ColumnDef>;
+
+    /// Whether the column (name without the prefix) belongs to the group.
+    fn contains(name: &str) -> bool;
+
+    /// Column stored in the field path, `None` if the path does not reach a column.
+    fn field_column(path: &[&str]) -> FieldColumn;
+
+    /// Values of the columns, in the same order as `columns()`.
+    fn row_values(&self) -> RowValues;
+
+    /// Reconstruct `Self` from a labeled row, labels without the prefix.
+    fn from_row(row: Row) -> Result<Self>
+    where
+        Self: Sized;
+}
+
+/// Reference to a flattened field, like `Order::price` in `expr!`.
+#[derive(Clone, Copy, Debug)]
+pub struct ColumnsRef {
+    /// Prefix of the column names.
+    pub prefix: &'static str,
+    /// Name of the table.
+    pub table: &'static str,
+    /// Name of the schema.
+    pub schema: &'static str,
+    /// Resolves a field path, see `Columns::field_column`.
+    pub field_column: fn(&[&str]) -> FieldColumn,
+}
+
+impl ColumnsRef {
+    /// Column stored in the field path, the remaining segments access its fields.
+    pub fn column<'a>(&self, fields: &'a [&'a str]) -> FieldAccess<'a, ColumnRef> {
+        let (name, consumed) = (self.field_column)(fields).unwrap_or_else(|| {
+            log::error!(
+                "Field `{}` is not a column of the group prefixed by `{}`",
+                fields.join("."),
+                self.prefix
+            );
+            (Cow::Owned(fields.join("_")), fields.len())
+        });
+        FieldAccess {
+            expression: ColumnRef {
+                name: Cow::Owned(format!("{}{name}", self.prefix)),
+                table: Cow::Borrowed(self.table),
+                schema: Cow::Borrowed(self.schema),
+            },
+            fields: &fields[consumed..],
+        }
+    }
+}
+
+/// Field access in `expr!`, like `User::address.city` or `Order::price.amount`.
+pub trait AccessFields<'a> {
+    /// Resulting expression.
+    type Output: Expression;
+    /// Access the fields, from the outermost to the innermost.
+    fn access_fields(self, fields: &'a [&'a str]) -> Self::Output;
+}
+
+impl<'a, E: Expression> AccessFields<'a> for E {
+    type Output = FieldAccess<'a, E>;
+    fn access_fields(self, fields: &'a [&'a str]) -> Self::Output {
+        FieldAccess {
+            expression: self,
+            fields,
+        }
+    }
+}
+
+impl<'a> AccessFields<'a> for ColumnsRef {
+    type Output = FieldAccess<'a, ColumnRef>;
+    fn access_fields(self, fields: &'a [&'a str]) -> Self::Output {
+        self.column(fields)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{Driver, Executor, Result};
 use anyhow::anyhow;
+use futures::TryFutureExt;
 use std::{
     borrow::Cow,
     future::{self, Future},
@@ -65,6 +66,24 @@ pub trait Connection: Executor {
         &mut self,
     ) -> impl Future<Output = Result<<Self::Driver as Driver>::Transaction<'_>>> + Send;
 
+    /// Checks that the connection is still usable.
+    ///
+    /// Used by the connection pool to discard connections broken by a failover or an idle
+    /// timeout on the network. The default implementation runs `SELECT 1`, drivers that have a
+    /// cheaper native probe override it.
+    fn ping(&mut self) -> impl Future<Output = Result<()>> + Send {
+        self.execute("SELECT 1").map_ok(|_| ())
+    }
+
+    /// Clears the session state (settings, temporary objects, open transactions, locks).
+    ///
+    /// Used by the connection pool before reusing a connection. Returns true if the session was
+    /// actually reset, in which case the pool runs the `after_connect` hook again. The default
+    /// implementation does nothing.
+    fn reset(&mut self) -> impl Future<Output = Result<bool>> + Send {
+        future::ready(Ok(false))
+    }
+
     /// Closes the connection and releases any session resources.
     fn disconnect(self) -> impl Future<Output = Result<()>> + Send
     where
@@ -91,6 +110,14 @@ impl<S: Connection> Connection for &mut S {
         (**self).begin()
     }
 
+    fn ping(&mut self) -> impl Future<Output = Result<()>> + Send {
+        (**self).ping()
+    }
+
+    fn reset(&mut self) -> impl Future<Output = Result<bool>> + Send {
+        (**self).reset()
+    }
+
     fn disconnect(self) -> impl Future<Output = Result<()>> + Send
     where
         Self: Sized,
@@ -244,7 +244,8 @@ pub fn decode_type(ty: &Type) -> TypeDecoded {
             }
             break 'data_type Value::List(None, Box::new(decode_type(&*elem).value));
         } else {
-            panic!("Unexpected type `{}`", ty.to_token_stream().to_string())
+            // Resolved by the AsValue implementation of the type
+            Value::Unknown(None)
         }
     };
     TypeDecoded {
@@ -1,8 +1,8 @@
 use crate::{
-    Connection, ConnectionPool, DBConnectionManager, Error, PoolConfig, Prepared, Result,
-    Transaction, writer::SqlWriter,
+    Connection, ConnectionPool, DBConnectionManager, Error, Executor, PoolConfig, PoolHooks,
+    Prepared, Result, Transaction, spawn_reaper, spawn_releaser, writer::SqlWriter,
 };
-use deadpool::managed::Pool;
+use deadpool::{Runtime, managed::Pool};
 use std::{borrow::Cow, fmt::Debug, future::Future};
 
 /// Backend connector and dialect.
@@ -14,7 +14,7 @@ pub trait Driver: Default + Clone + Sync + Send + Debug {
     /// Prepared statement implementation.
     type Prepared: Prepared;
     /// Transaction implementation.
-    type Transaction<'c>: Transaction<'c>;
+    type Transaction<'c>: Transaction<'c, Driver = Self>;
 
     /// Human-readable backend name.
     const NAME: &'static [&'static str];
@@ -24,18 +24,54 @@ pub trait Driver: Default + Clone + Sync + Send + Debug {
         Self::NAME[0]
     }
 
-    /// Creates a new connection to the database at the specified URL.
+    /// Checks if schema changes (DDL) are transactional, meaning they can be rolled back.
+    fn supports_transactional_ddl(&self) -> bool {
+        false
+    }
+
+    /// Creates a new connection pool to the database at the specified URL.
+    ///
+    /// The pool is itself an [`Executor`], checking out a connection for every call.
+    /// Clones are cheap handles sharing the same connections, give one to every task.
+    ///
+    /// When `config` sets a `max_lifetime`, `idle_timeout` or `min_idle`, a
+    /// background task maintaining the idle connections is spawned on the
+    /// current Tokio runtime.
     fn connect_pool(
         &self,
         url: Cow<'static, str>,
         config: PoolConfig,
-    ) -> impl Future<Output = Result<impl ConnectionPool<Self>>> + Send {
-        let config = config.into();
+    ) -> impl Future<Output = Result<impl ConnectionPool<Self> + Executor<Driver = Self> + Clone>> + Send
+    where
+        Self: 'static,
+    {
+        self.connect_pool_with_hooks(url, config, PoolHooks::new())
+    }
+
+    /// Creates a new connection pool running `hooks` during the lifecycle of its connections.
+    ///
+    /// Use it to apply per-session settings (`SET search_path`, `PRAGMA foreign_keys=ON`, ...)
+    /// to every connection of the pool.
+    fn connect_pool_with_hooks(
+        &self,
+        url: Cow<'static, str>,
+        config: PoolConfig,
+        hooks: PoolHooks<Self>,
+    ) -> impl Future<Output = Result<impl ConnectionPool<Self> + Executor<Driver = Self> + Clone>> + Send
+    where
+        Self: 'static,
+    {
         async move {
-            Ok(Pool::builder(DBConnectionManager::new(self.clone(), url))
-                .config(config)
+            let manager = DBConnectionManager::new(self.clone(), url)
+                .with_config(config)
+                .with_hooks(hooks);
+            let pool = Pool::builder(spawn_releaser(manager))
+                .config(config.into())
+                .runtime(Runtime::Tokio1)
                 .build()
-                .map_err(Error::new)?)
+                .map_err(Error::new)?;
+            spawn_reaper(&pool);
+            Ok(pool)
         }
     }
 
@@ -1,10 +1,11 @@
 use crate::{
-    ColumnDef, Context, Dataset, Driver, DynQuery, Executor, Expression, Query, QueryBuilder,
-    RawQuery, Result, Row, RowValues, RowsAffected, TableRef, future::Either, stream::Stream,
-    truncate_long, writer::SqlWriter,
+    AsValue, ColumnDef, Context, Dataset, Driver, DynQuery, Executor, Expression, IndexDef, Query,
+    QueryBuilder, QueryResult, RawQuery, Result, Row, RowValues, RowsAffected, SchemaDiff,
+    TableRef, ViewDef, future::Either, split_sql_statements, stream::Stream, truncate_long,
+    writer::SqlWriter,
 };
 use anyhow::anyhow;
-use futures::{FutureExt, StreamExt};
+use futures::{FutureExt, StreamExt, TryStreamExt};
 use log::Level;
 use std::{
     future::{self, Future},
@@ -45,6 +46,20 @@ pub trait Entity: AsEntity + Expression {
     fn unique_defs()
     -> impl ExactSizeIterator<Item = impl ExactSizeIterator<Item = &'static ColumnDef>>;
 
+    /// Table level check constraints, built on demand.
+    fn check_defs() -> &'static [fn() -> Box<dyn Expression>];
+
+    /// Secondary index definitions.
+    fn indexes() -> &'static [IndexDef];
+
+    /// View definition, `None` when the entity maps a table.
+    fn view() -> Option<&'static ViewDef> {
+        None
+    }
+
+    /// Writes the query defining the view, nothing for tables.
+    fn write_view_query(_writer: &impl SqlWriter, _out: &mut DynQuery) {}
+
     /// Full row representation including all persisted columns.
     fn row_values(&self) -> RowValues;
 
@@ -66,7 +81,7 @@ pub trait Entity: AsEntity + Expression {
     where
         Self: Sized;
 
-    /// Create table (and optional schema).
+    /// Create table (and optional schema), or the view for entities mapping a view.
     ///
     /// - `if_not_exists`: Emits `IF NOT EXISTS` if supported.
     /// - `create_schema`: Attempts schema creation first.
@@ -91,12 +106,25 @@ pub trait Entity: AsEntity + Expression {
                 query = q.into();
                 query.clear();
             }
-            writer.write_create_table::<Self>(&mut query, if_not_exists);
+            if Self::view().is_some() {
+                writer.write_create_view::<Self>(&mut query, if_not_exists);
+            } else {
+                writer.write_create_table::<Self>(&mut query, if_not_exists);
+            }
+            if !executor.accepts_multiple_statements()
+                && let DynQuery::Raw(RawQuery(sql)) = &query
+            {
+                // The indexes are created by separate statements
+                for statement in split_sql_statements(sql) {
+                    executor.execute(statement).await?;
+                }
+                return Ok(());
+            }
             executor.execute(query).await.map(|_| ())
         }
     }
 
-    /// Drop the table (and optional schema).
+    /// Drop the table (and optional schema), or the view for entities mapping a view.
     ///
     /// - `if_exists`: Emits `IF EXISTS` if supported.
     /// - `drop_schema`: Drops schema *after* table removal (if empty).
@@ -111,7 +139,11 @@ pub trait Entity: AsEntity + Expression {
         async move {
             let mut query = DynQuery::with_capacity(256);
             let writer = executor.driver().sql_writer();
-            writer.write_drop_table::<Self>(&mut query, if_exists);
+            if Self::view().is_some() {
+                writer.write_drop_view::<Self>(&mut query, if_exists);
+            } else {
+                writer.write_drop_table::<Self>(&mut query, if_exists);
+            }
             if drop_schema && !Self::table().schema.is_empty() {
                 if !executor.accepts_multiple_statements() {
                     let mut q = query.into_query(executor.driver());
@@ -126,17 +158,107 @@ pub trait Entity: AsEntity + Expression {
         }
     }
 
+    /// Refresh the rows of a materialized view.
+    fn refresh(executor: &mut impl Executor) -> impl Future<Output = Result<()>> + Send
+    where
+        Self: Sized,
+    {
+        async move {
+            let mut query = DynQuery::with_capacity(128);
+            executor
+                .driver()
+                .sql_writer()
+                .write_refresh_view::<Self>(&mut query);
+            if query.is_empty() {
+                let error = anyhow!(
+                    "The view {} cannot be refreshed",
+                    Self::table().full_name(".")
+                );
+                log::error!("{error:#}");
+                return Err(error);
+            }
+            executor.execute(query).await.map(|_| ())
+        }
+    }
+
+    /// Compare the entity with its table in the database.
+    ///
+    /// The changes needed to bring the table in line are written in the driver dialect.
+    fn diff_table(executor: &mut impl Executor) -> impl Future<Output = Result<SchemaDiff>> + Send
+    where
+        Self: Sized,
+    {
+        async move {
+            if Self::view().is_some() {
+                let error = anyhow!(
+                    "The view {} cannot be migrated, drop it and create it again",
+                    Self::table().full_name(".")
+                );
+                log::error!("{error:#}");
+                return Err(error);
+            }
+            let current = executor.describe_table(Self::table()).await?;
+            let writer = executor.driver().sql_writer();
+            Ok(SchemaDiff::new::<Self, _>(&writer, &current))
+        }
+    }
+
+    /// Migrate the table to match the entity, creating it if missing.
+    ///
+    /// - `allow_destructive`: Also applies changes that can lose data, fails otherwise.
+    /// - `dry_run`: Only computes the changes, print the result to see the SQL.
+    ///
+    /// Fails on changes the driver cannot express, use [`Entity::diff_table`] and
+    /// [`SchemaDiff::skip_unsupported`] to apply the others.
+    fn migrate_table(
+        executor: &mut impl Executor,
+        allow_destructive: bool,
+        dry_run: bool,
+    ) -> impl Future<Output = Result<SchemaDiff>> + Send
+    where
+        Self: Sized,
+    {
+        async move {
+            let diff = Self::diff_table(executor).await?;
+            if !dry_run {
+                diff.apply(executor, allow_destructive).await?;
+            }
+            Ok(diff)
+        }
+    }
+
     /// Insert a single entity.
+    ///
+    /// The value generated for an unset auto increment column is reported in `last_affected_id`.
     fn insert_one(
         executor: &mut impl Executor,
         entity: impl AsEntity,
-    ) -> impl Future<Output = Result<RowsAffected>> + Send {
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        Self: Writable,
+    {
         let mut query = DynQuery::with_capacity(128);
         executor
             .driver()
             .sql_writer()
             .write_insert(&mut query, [entity], false);
-        executor.execute(query)
+        executor
+            .run(query)
+            .try_fold(RowsAffected::default(), |mut result, v| async move {
+                match v {
+                    QueryResult::Affected(v) => result.extend([v]),
+                    QueryResult::Row(row) => {
+                        // Returned by drivers writing a RETURNING clause
+                        result.rows_affected = Some(result.rows_affected.unwrap_or_default() + 1);
+                        result.last_affected_id = row
+                            .values
+                            .into_iter()
+                            .next()
+                            .and_then(|v| i64::try_from_value(v).ok());
+                    }
+                }
+                Ok(result)
+            })
     }
 
     /// Bulk insert entities.
@@ -145,7 +267,7 @@ pub trait Entity: AsEntity + Expression {
         items: It,
     ) -> impl Future<Output = Result<RowsAffected>> + Send
     where
-        Self: Sized,
+        Self: Sized + Writable,
         It: IntoIterator + Send,
         It::IntoIter: Send,
         It::Item: AsEntity,
@@ -222,7 +344,7 @@ pub trait Entity: AsEntity + Expression {
         condition: impl Expression,
     ) -> impl Future<Output = Result<RowsAffected>> + Send
     where
-        Self: Sized,
+        Self: Sized + Writable,
     {
         let mut query = DynQuery::with_capacity(128);
         executor
@@ -239,7 +361,7 @@ pub trait Entity: AsEntity + Expression {
     /// - Execution failures from underlying driver.
     fn save(&self, executor: &mut impl Executor) -> impl Future<Output = Result<()>> + Send
     where
-        Self: Sized,
+        Self: Sized + Writable,
         for<'s> &'s Self: AsEntity,
     {
         if Self::primary_key_def().is_empty() {
@@ -280,7 +402,7 @@ pub trait Entity: AsEntity + Expression {
     /// - Execution failures from underlying driver.
     fn delete(&self, executor: &mut impl Executor) -> impl Future<Output = Result<()>> + Send
     where
-        Self: Sized,
+        Self: Sized + Writable,
     {
         if Self::primary_key_def().is_empty() {
             let error =
@@ -314,6 +436,16 @@ pub trait Entity: AsEntity + Expression {
     }
 }
 
+/// Entity whose rows can be inserted, saved and deleted.
+///
+/// `#[derive(Entity)]` implements it for every entity except views, calling a writing method on
+/// a view does not compile (see the examples on the derive).
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` maps a view, its rows cannot be written",
+    label = "read-only entity"
+)]
+pub trait Writable: Entity {}
+
 impl<E: Entity> Dataset for E {
     /// Indicates whether column names should be fully qualified with schema and table name.
     ///
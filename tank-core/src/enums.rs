@@ -0,0 +1,30 @@
+use crate::Value;
+
+/// Enum stored by a column, implemented by `#[derive(Enum)]`.
+#[derive(Debug)]
+pub struct EnumDef {
+    /// Type name, for databases with named enum types.
+    pub name: &'static str,
+    /// Schema of the type, empty for the default one.
+    pub schema: &'static str,
+    /// Stored value of each variant, in declaration order.
+    pub values: &'static [Value],
+    /// Restricts the column to the values with a CHECK constraint, where enums are not native.
+    pub check: bool,
+}
+
+impl EnumDef {
+    /// The variants are stored as strings rather than as their discriminant.
+    pub fn is_textual(&self) -> bool {
+        self.values
+            .iter()
+            .all(|v| matches!(v, Value::Varchar(Some(..))))
+    }
+    /// The strings stored, empty when the variants are stored as their discriminant.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.values.iter().filter_map(|v| match v {
+            Value::Varchar(Some(v)) => Some(v.as_ref()),
+            _ => None,
+        })
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
-    AsEntity, AsQuery, Driver, DynQuery, Query, QueryResult, RawQuery, Result, Row, RowsAffected,
+    AsEntity, AsQuery, ColumnInfo, Driver, DynQuery, IntoParams, Params, Prepared, Query,
+    QueryResult, RawQuery, Result, Row, RowsAffected, TableRef, rewrite_named_parameters,
     stream::{Stream, StreamExt, TryStreamExt},
+    table_refs_from_rows,
     writer::SqlWriter,
 };
 use anyhow::anyhow;
@@ -12,7 +14,11 @@ use std::{
 
 /// Async query execution.
 ///
-/// Implemented by connections.
+/// Implemented by connections, transactions and connection pools. A prepared statement belongs
+/// to the connection that prepared it, so pools cannot [`prepare`](Executor::prepare): this
+/// rules out [`execute_batch`](Executor::execute_batch), `Entity::prepare_find` and the `sql!`
+/// queries (`Sql::prepare`, `Sql::fetch`, `Sql::execute`) on them. Check out a connection from
+/// the pool first, or use `Sql::inline`.
 pub trait Executor: Send {
     /// Associated driver.
     type Driver: Driver;
@@ -28,15 +34,27 @@ pub trait Executor: Send {
     }
 
     /// Prepares a query for execution, returning a handle to the prepared statement.
+    ///
+    /// Named placeholders (`:name`, `@name` or `$name`) are rewritten into the native ones of
+    /// the driver and can be bound with `Prepared::bind_named`.
     fn prepare<'s>(
         &'s mut self,
         query: impl AsQuery<Self::Driver> + 's,
     ) -> impl Future<Output = Result<Query<Self::Driver>>> + Send {
         let mut query = query.as_query();
         let query = mem::take(query.as_mut());
-        async {
+        async move {
             match query {
-                Query::Raw(RawQuery(sql)) => self.do_prepare(sql).await,
+                Query::Raw(RawQuery(sql)) => {
+                    let (sql, names) = rewrite_named_parameters(&sql, &self.driver().sql_writer())?;
+                    let mut query = self.do_prepare(sql).await?;
+                    if let Query::Prepared(prepared) = &mut query
+                        && !names.is_empty()
+                    {
+                        prepared.set_parameter_names(names)?;
+                    }
+                    Ok(query)
+                }
                 Query::Prepared(..) => Ok(query),
             }
         }
@@ -89,6 +107,53 @@ pub trait Executor: Send {
             .try_collect()
     }
 
+    /// Executes a prepared query once for each set of params, returning the affected rows of
+    /// each execution.
+    ///
+    /// Drivers send the executions together when supported (pipelining, bulk writes, batches).
+    /// The `rows_affected` of an execution is `None` when the driver cannot tell it apart, like
+    /// MySQL/MariaDB and ScyllaDB/Cassandra.
+    fn execute_batch<'s, It>(
+        &'s mut self,
+        query: &'s mut Query<Self::Driver>,
+        params: It,
+    ) -> impl Future<Output = Result<Vec<RowsAffected>>> + Send
+    where
+        It: IntoIterator,
+        It::Item: IntoParams,
+    {
+        let params = params
+            .into_iter()
+            .map(IntoParams::into_params)
+            .collect::<Vec<_>>();
+        async move {
+            if !query.is_prepared() {
+                return Err(anyhow!(
+                    "Cannot execute a batch of a raw query, prepare it first"
+                ));
+            }
+            self.do_execute_batch(query, params).await
+        }
+    }
+
+    /// Internal hook for implementing batch execution, the query is always prepared.
+    ///
+    /// Binds and executes the params sets one after the other by default.
+    fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<Self::Driver>,
+        params: Vec<Params>,
+    ) -> impl Future<Output = Result<Vec<RowsAffected>>> + Send {
+        async move {
+            let mut result = Vec::with_capacity(params.len());
+            for params in params {
+                query.bind_params(params)?;
+                result.push(self.execute(&mut *query).await?);
+            }
+            Ok(result)
+        }
+    }
+
     /// Efficiently inserts a collection of entities bypassing regular SQL execution when supported by the driver.
     fn append<It>(&mut self, entities: It) -> impl Future<Output = Result<RowsAffected>> + Send
     where
@@ -102,6 +167,35 @@ pub trait Executor: Send {
             .write_insert(&mut query, entities, false);
         self.execute(query)
     }
+
+    /// Reads the definition of the columns of a table from the database.
+    ///
+    /// Returns an empty list when the table does not exist.
+    fn describe_table(
+        &mut self,
+        table: &TableRef,
+    ) -> impl Future<Output = Result<Vec<ColumnInfo>>> + Send {
+        let mut query = DynQuery::default();
+        self.driver()
+            .sql_writer()
+            .write_describe_table(&mut query, table);
+        async move {
+            let rows = self.fetch(query).try_collect::<Vec<_>>().await?;
+            ColumnInfo::from_rows(rows, table, self.driver().sql_writer().as_dyn())
+        }
+    }
+
+    /// Lists the tables of a schema, or of the default one when `schema` is empty.
+    fn list_tables(&mut self, schema: &str) -> impl Future<Output = Result<Vec<TableRef>>> + Send {
+        let mut query = DynQuery::default();
+        self.driver()
+            .sql_writer()
+            .write_list_tables(&mut query, schema);
+        async move {
+            let rows = self.fetch(query).try_collect::<Vec<_>>().await?;
+            table_refs_from_rows(rows)
+        }
+    }
 }
 
 impl<S: Executor + ?Sized> Executor for &mut S {
@@ -150,6 +244,26 @@ impl<S: Executor + ?Sized> Executor for &mut S {
         (**self).execute(query)
     }
 
+    fn execute_batch<'s, It>(
+        &'s mut self,
+        query: &'s mut Query<Self::Driver>,
+        params: It,
+    ) -> impl Future<Output = Result<Vec<RowsAffected>>> + Send
+    where
+        It: IntoIterator,
+        It::Item: IntoParams,
+    {
+        (**self).execute_batch(query, params)
+    }
+
+    fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<Self::Driver>,
+        params: Vec<Params>,
+    ) -> impl Future<Output = Result<Vec<RowsAffected>>> + Send {
+        (**self).do_execute_batch(query, params)
+    }
+
     fn append<It>(&mut self, entities: It) -> impl Future<Output = Result<RowsAffected>> + Send
     where
         It: IntoIterator + Send,
@@ -158,4 +272,15 @@ impl<S: Executor + ?Sized> Executor for &mut S {
     {
         (**self).append(entities)
     }
+
+    fn describe_table(
+        &mut self,
+        table: &TableRef,
+    ) -> impl Future<Output = Result<Vec<ColumnInfo>>> + Send {
+        (**self).describe_table(table)
+    }
+
+    fn list_tables(&mut self, schema: &str) -> impl Future<Output = Result<Vec<TableRef>>> + Send {
+        (**self).list_tables(schema)
+    }
 }
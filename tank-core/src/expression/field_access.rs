@@ -0,0 +1,48 @@
+use crate::{
+    DynQuery, Expression, ExpressionVisitor, OpPrecedence,
+    writer::{Context, SqlWriter},
+};
+
+/// Field of a composite column, like `User::address.city` in `expr!`.
+#[derive(Debug)]
+pub struct FieldAccess<'a, E: Expression> {
+    /// Expression evaluating to the composite value, usually a column.
+    pub expression: E,
+    /// Field names, from the outermost to the innermost.
+    pub fields: &'a [&'a str],
+}
+
+impl<E: Expression> OpPrecedence for FieldAccess<'_, E> {
+    fn precedence(&self, _writer: &dyn SqlWriter) -> i32 {
+        1_000_000
+    }
+}
+
+impl<E: Expression> Expression for FieldAccess<'_, E> {
+    fn write_query(&self, writer: &dyn SqlWriter, context: &mut Context, out: &mut DynQuery) {
+        if self.fields.is_empty() {
+            // A flattened column, like `Order::price.amount`
+            return self.expression.write_query(writer, context, out);
+        }
+        writer.write_field_access(
+            context,
+            out,
+            &FieldAccess {
+                expression: &self.expression,
+                fields: self.fields,
+            },
+        );
+    }
+    fn accept_visitor(
+        &self,
+        matcher: &mut dyn ExpressionVisitor,
+        writer: &dyn SqlWriter,
+        context: &mut Context,
+        out: &mut DynQuery,
+    ) -> bool {
+        self.fields.is_empty()
+            && self
+                .expression
+                .accept_visitor(matcher, writer, context, out)
+    }
+}
@@ -1,6 +1,7 @@
 mod binary_op;
 mod default_value_type;
 mod expression;
+mod field_access;
 mod op_precedence;
 mod operand;
 mod ordered;
@@ -10,6 +11,7 @@ mod visitor;
 pub use binary_op::*;
 pub use default_value_type::*;
 pub use expression::*;
+pub use field_access::*;
 pub use op_precedence::*;
 pub use operand::*;
 pub use ordered::*;
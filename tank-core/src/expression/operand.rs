@@ -20,6 +20,7 @@ pub enum Operand<'a> {
     Call(&'static str, &'a [&'a dyn Expression]),
     Asterisk,
     QuestionMark,
+    NamedParameter(&'a str),
     CurrentTimestampMs,
 }
 
@@ -41,6 +42,7 @@ impl fmt::Debug for Operand<'_> {
             Self::Call(name, _) => f.debug_tuple("Call").field(name).field(&"..").finish(),
             Self::Asterisk => f.write_str("Asterisk"),
             Self::QuestionMark => f.write_str("QuestionMark"),
+            Self::NamedParameter(v) => f.debug_tuple("NamedParameter").field(v).finish(),
             Self::CurrentTimestampMs => f.write_str("CurrentTimestampMs"),
         }
     }
@@ -83,6 +85,7 @@ impl PartialEq for Operand<'_> {
             (Self::Variable(l), Self::Variable(r)) => l == r,
             (Self::Asterisk, Self::Asterisk) => true,
             (Self::QuestionMark, Self::QuestionMark) => true,
+            (Self::NamedParameter(l), Self::NamedParameter(r)) => l == r,
             (Self::CurrentTimestampMs, Self::CurrentTimestampMs) => true,
             _ => false,
         }
@@ -0,0 +1,23 @@
+use crate::{ColumnDef, Expression, Order};
+
+/// Secondary index, declared with `#[tank(index(...))]` on the entity.
+#[derive(Default, Debug)]
+pub struct IndexDef {
+    /// Index name.
+    pub name: &'static str,
+    /// Indexed columns with their ordering.
+    pub columns: Box<[(&'static ColumnDef, Order)]>,
+    /// Rejects duplicate values.
+    pub unique: bool,
+    /// Partial index condition, built on demand.
+    pub condition: Option<fn() -> Box<dyn Expression>>,
+    /// Index method (for example `gin` on Postgres).
+    pub using: &'static str,
+}
+
+impl IndexDef {
+    /// Index name.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+}
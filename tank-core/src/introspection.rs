@@ -0,0 +1,188 @@
+use crate::{Action, ColumnRef, PrimaryKeyType, Result, Row, SqlWriter, TableRef, Value};
+use anyhow::anyhow;
+use std::borrow::Cow;
+
+/// Column definition read back from the database, see [`Executor::describe_table`](crate::Executor::describe_table).
+///
+/// Mirrors [`ColumnDef`](crate::ColumnDef) but owns its data.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ColumnInfo {
+    /// Column identity.
+    pub column_ref: ColumnRef,
+    /// SQL type as reported by the database.
+    pub column_type: String,
+    /// Type descriptor parsed from `column_type`.
+    pub value: Value,
+    /// Is nullable.
+    pub nullable: bool,
+    /// Default expression as reported by the database.
+    pub default: Option<String>,
+    /// Primary key role.
+    pub primary_key: PrimaryKeyType,
+    /// Clustering key (relevant for ScyllaDB/Cassandra).
+    pub clustering_key: bool,
+    /// Single-column unique constraint.
+    pub unique: bool,
+    /// Foreign key target.
+    pub references: Option<ColumnRef>,
+    /// On delete action.
+    pub on_delete: Option<Action>,
+    /// On update action.
+    pub on_update: Option<Action>,
+    /// Comment.
+    pub comment: String,
+}
+
+impl ColumnInfo {
+    /// Column name.
+    pub fn name(&self) -> &str {
+        &self.column_ref.name
+    }
+
+    /// Decodes the rows produced by [`SqlWriter::write_describe_table`].
+    ///
+    /// Consecutive rows describing the same column (for example one per constraint) are merged.
+    pub fn from_rows(
+        rows: impl IntoIterator<Item = Row>,
+        table: &TableRef,
+        writer: &dyn SqlWriter,
+    ) -> Result<Vec<ColumnInfo>> {
+        let mut result: Vec<ColumnInfo> = Vec::new();
+        for row in rows {
+            let get = |label: &str| {
+                row.labels
+                    .iter()
+                    .position(|v| v.eq_ignore_ascii_case(label))
+                    .map(|i| &row.values[i])
+                    .unwrap_or(&Value::Null)
+            };
+            let name = text(get("column_name")).ok_or_else(|| {
+                anyhow!("Column `column_name` is missing from the table description")
+            })?;
+            let column_type = text(get("data_type")).unwrap_or_default();
+            let primary_key = match integer(get("primary_key")) {
+                0 => PrimaryKeyType::None,
+                1 => PrimaryKeyType::PrimaryKey,
+                _ => PrimaryKeyType::PartOfPrimaryKey,
+            };
+            let references = text(get("referenced_table")).map(|referenced_table| ColumnRef {
+                name: text(get("referenced_column")).unwrap_or_default().into(),
+                table: referenced_table.into(),
+                schema: text(get("referenced_schema")).unwrap_or_default().into(),
+            });
+            let column = ColumnInfo {
+                column_ref: ColumnRef {
+                    name: name.into(),
+                    table: table.name.clone(),
+                    schema: table.schema.clone(),
+                },
+                value: writer.parse_column_type(&column_type),
+                column_type,
+                nullable: flag(get("is_nullable")),
+                default: text(get("column_default")),
+                primary_key,
+                clustering_key: flag(get("clustering_key")),
+                unique: flag(get("is_unique")),
+                on_delete: references
+                    .as_ref()
+                    .and_then(|_| text(get("on_delete")))
+                    .and_then(|v| parse_action(&v)),
+                on_update: references
+                    .as_ref()
+                    .and_then(|_| text(get("on_update")))
+                    .and_then(|v| parse_action(&v)),
+                references,
+                comment: text(get("comment")).unwrap_or_default(),
+            };
+            match result.last_mut() {
+                Some(last) if last.column_ref == column.column_ref => last.merge(column),
+                _ => result.push(column),
+            }
+        }
+        Ok(result)
+    }
+
+    fn merge(&mut self, other: ColumnInfo) {
+        self.unique |= other.unique;
+        self.clustering_key |= other.clustering_key;
+        if self.references.is_none() {
+            self.references = other.references;
+            self.on_delete = other.on_delete;
+            self.on_update = other.on_update;
+        }
+    }
+}
+
+/// Decodes the rows produced by [`SqlWriter::write_list_tables`].
+pub fn table_refs_from_rows(rows: impl IntoIterator<Item = Row>) -> Result<Vec<TableRef>> {
+    rows.into_iter()
+        .map(|row| {
+            let get = |label: &str| {
+                row.labels
+                    .iter()
+                    .position(|v| v.eq_ignore_ascii_case(label))
+                    .map(|i| &row.values[i])
+                    .unwrap_or(&Value::Null)
+            };
+            let name = text(get("table_name"))
+                .ok_or_else(|| anyhow!("Column `table_name` is missing from the tables list"))?;
+            Ok(TableRef {
+                name: name.into(),
+                schema: Cow::Owned(text(get("table_schema")).unwrap_or_default()),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Parses a referential action like `SET NULL` or `cascade`.
+pub fn parse_action(value: &str) -> Option<Action> {
+    Some(
+        match value.trim().to_ascii_uppercase().replace('_', " ").as_str() {
+            "NO ACTION" => Action::NoAction,
+            "RESTRICT" => Action::Restrict,
+            "CASCADE" => Action::Cascade,
+            "SET NULL" => Action::SetNull,
+            "SET DEFAULT" => Action::SetDefault,
+            _ => return None,
+        },
+    )
+}
+
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::Varchar(Some(v)) => Some(v.to_string()),
+        Value::Char(Some(v)) => Some(v.to_string()),
+        Value::Blob(Some(v)) => Some(String::from_utf8_lossy(v).into_owned()),
+        Value::Unknown(Some(v)) => Some(v.clone()),
+        v if v.is_null() => None,
+        v => Some(v.to_string()),
+    }
+}
+
+fn integer(value: &Value) -> i64 {
+    match value {
+        Value::Boolean(Some(v)) => *v as i64,
+        Value::Int8(Some(v)) => *v as i64,
+        Value::Int16(Some(v)) => *v as i64,
+        Value::Int32(Some(v)) => *v as i64,
+        Value::Int64(Some(v)) => *v,
+        Value::Int128(Some(v)) => *v as i64,
+        Value::UInt8(Some(v)) => *v as i64,
+        Value::UInt16(Some(v)) => *v as i64,
+        Value::UInt32(Some(v)) => *v as i64,
+        Value::UInt64(Some(v)) => *v as i64,
+        Value::UInt128(Some(v)) => *v as i64,
+        Value::Decimal(Some(v), ..) => v.trunc().try_into().unwrap_or_default(),
+        v => text(v)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or_default(),
+    }
+}
+
+fn flag(value: &Value) -> bool {
+    match text(value) {
+        Some(v) if matches!(v.to_ascii_uppercase().as_str(), "YES" | "Y" | "TRUE" | "T") => true,
+        _ => integer(value) != 0,
+    }
+}
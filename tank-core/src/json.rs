@@ -0,0 +1,69 @@
+use crate::{AsValue, Result, TypeKind, Value, truncate_long, value_to_json};
+use anyhow::{Context, anyhow};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value as JsonValue;
+use std::any;
+
+/// Wrapper storing any serde type as a JSON value.
+///
+/// This is what `#[tank(json)]` fields are converted through, it can also be used directly as a field type.
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Json<T>(pub T);
+
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> AsValue for Json<T> {
+    const KIND: TypeKind = TypeKind::Json;
+    fn as_empty_value() -> Value {
+        Value::Json(None)
+    }
+    fn as_value(self) -> Value {
+        match serde_json::to_value(&self.0) {
+            Ok(JsonValue::Null) => Value::Json(None),
+            Ok(v) => Value::Json(Some(v)),
+            Err(e) => {
+                log::error!(
+                    "Could not serialize {} into json: {e:?}",
+                    any::type_name::<T>()
+                );
+                Value::Json(None)
+            }
+        }
+    }
+    fn try_from_value(value: Value) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        // Text columns hold the serialized json, documents store plain strings as they are
+        let text =
+            |v: &str| serde_json::from_str(v).unwrap_or_else(|_| JsonValue::String(v.into()));
+        let json = match value {
+            Value::Json(Some(v), ..) => v,
+            Value::Varchar(Some(v), ..) => text(&v),
+            Value::Unknown(Some(v), ..) => text(&v),
+            ref v if v.is_null() => JsonValue::Null,
+            ref v => value_to_json(v).ok_or_else(|| {
+                anyhow!(
+                    "Cannot convert {} to {}",
+                    truncate_long!(format!("{v:?}")),
+                    any::type_name::<Self>()
+                )
+            })?,
+        };
+        serde_json::from_value(json)
+            .map(Self)
+            .with_context(|| format!("Could not deserialize json into {}", any::type_name::<T>()))
+    }
+    fn parse(input: impl AsRef<str>) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        serde_json::from_str(input.as_ref())
+            .map(Self)
+            .with_context(|| format!("Could not deserialize json into {}", any::type_name::<T>()))
+    }
+}
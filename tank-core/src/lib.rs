@@ -1,47 +1,74 @@
 mod as_value;
 mod column;
+mod columns;
 mod connection;
 mod dataset;
 mod decode_type;
 mod driver;
 mod entity;
+mod enums;
 mod executor;
 mod expression;
+mod index;
 mod interval;
+mod introspection;
 mod join;
+#[cfg(feature = "serde")]
+mod json;
 mod pool;
 mod pool_config;
+mod pool_executor;
+mod pool_hooks;
+mod pool_status;
 mod query;
 mod relations;
+mod routing_pool;
 mod row;
+mod schema_diff;
+#[cfg(feature = "serde")]
+mod serialization;
 mod table_ref;
 mod transaction;
+mod type_check;
 mod util;
 mod value;
+mod view;
 mod writer;
 
 pub use ::anyhow::Context as ErrorContext;
 pub use ::anyhow::anyhow;
 pub use as_value::*;
 pub use column::*;
+pub use columns::*;
 pub use connection::*;
 pub use dataset::*;
 pub use decode_type::*;
 pub use driver::*;
 pub use entity::*;
+pub use enums::*;
 pub use executor::*;
 pub use expression::*;
+pub use index::*;
 pub use interval::*;
+pub use introspection::*;
 pub use join::*;
+#[cfg(feature = "serde")]
+pub use json::*;
 pub use pool::*;
 pub use pool_config::*;
+pub use pool_hooks::*;
+pub use pool_status::*;
 pub use query::*;
 pub use relations::*;
+pub use routing_pool::*;
 pub use row::*;
+pub use schema_diff::*;
 pub use table_ref::*;
 pub use transaction::*;
+pub use type_check::*;
 pub use util::*;
 pub use value::*;
+pub use view::*;
 pub use writer::*;
 pub mod stream {
     pub use ::futures::stream::*;
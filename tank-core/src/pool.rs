@@ -1,17 +1,26 @@
 use crate::{
-    AsEntity, AsQuery, Connection, Driver, Error, Executor, Query, QueryResult, Result, Row,
-    RowsAffected,
+    AsEntity, AsQuery, ColumnInfo, Connection, Driver, Error, Executor, PoolConfig, PoolHooks,
+    PoolStats, PoolStatus, Query, QueryResult, QueryTimer, Result, Row, RowsAffected, TableRef,
 };
-use anyhow::anyhow;
-use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleResult, Timeouts};
-use futures::{FutureExt, Stream, future::BoxFuture};
+use anyhow::{Context, anyhow};
+use deadpool::managed::{
+    Manager, Metrics, Object, Pool, PoolError, RecycleError, RecycleResult, Timeouts, WeakPool,
+};
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture};
 use std::{
     borrow::Cow,
     fmt::Debug,
     future,
+    mem::ManuallyDrop,
     ops::{Deref, DerefMut},
+    ptr,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
+};
+use tokio::{
+    runtime::Handle,
+    sync::mpsc::{UnboundedSender, unbounded_channel},
+    time::MissedTickBehavior,
 };
 
 /// The [`Manager`] that backs every Tank connection pool.
@@ -20,15 +29,58 @@ use std::{
 /// new connections on demand and how to validate recycled ones.  You do not
 /// need to construct or interact with this type directly, it is created
 /// internally by [`Driver::connect_pool`].
+///
+/// A connection is discarded instead of being handed out again when it
+/// exceeds [`PoolConfig::max_lifetime`] or [`PoolConfig::idle_timeout`], or
+/// when [`PoolConfig::test_on_checkout`] is set and [`Connection::ping`] fails.
+///
+/// The `after_release` hook runs as soon as a connection is returned to the
+/// pool. Right before it is handed out again the session is reset (if
+/// [`PoolConfig::reset_on_release`]), then the `before_acquire` hook runs.
 #[derive(Debug)]
 pub struct DBConnectionManager<D: Driver> {
     driver: D,
     url: Cow<'static, str>,
+    config: PoolConfig,
+    hooks: PoolHooks<D>,
+    stats: Arc<PoolStats>,
+    /// Receives the connections returned to the pool, see [`spawn_releaser`].
+    released: Option<UnboundedSender<Object<DBConnectionManager<D>>>>,
 }
 
 impl<D: Driver> DBConnectionManager<D> {
     pub fn new(driver: D, url: Cow<'static, str>) -> Self {
-        Self { driver, url }
+        let stats = PoolStats::new(driver.name()).into();
+        Self {
+            driver,
+            url,
+            config: Default::default(),
+            hooks: Default::default(),
+            stats,
+            released: None,
+        }
+    }
+    /// Replaces the pool configuration used when recycling connections.
+    pub fn with_config(mut self, config: PoolConfig) -> Self {
+        self.config = config;
+        self
+    }
+    /// Replaces the connection lifecycle hooks.
+    pub fn with_hooks(mut self, hooks: PoolHooks<D>) -> Self {
+        self.hooks = hooks;
+        self
+    }
+    /// Returns the pool configuration.
+    pub fn config(&self) -> &PoolConfig {
+        &self.config
+    }
+    /// Returns the connection lifecycle hooks.
+    pub fn hooks(&self) -> &PoolHooks<D> {
+        &self.hooks
+    }
+    /// True if the connections returned to the pool need to be processed in the background.
+    fn needs_release(&self) -> bool {
+        self.hooks.after_release.is_some()
     }
 }
 
@@ -36,17 +88,180 @@ impl<D: Driver> Manager for DBConnectionManager<D> {
     type Type = D::Connection;
     type Error = Error;
     async fn create(&self) -> Result<Self::Type> {
-        Ok(D::Connection::connect(&self.driver, self.url.clone()).await?)
+        let mut connection = D::Connection::connect(&self.driver, self.url.clone()).await?;
+        if let Some(hook) = &self.hooks.after_connect {
+            hook(&mut connection)
+                .await
+                .context("While running the after_connect hook")?;
+        }
+        Ok(connection)
     }
-    fn recycle(
+    async fn recycle(
         &self,
-        _: &mut Self::Type,
-        _: &Metrics,
-    ) -> impl Future<Output = RecycleResult<Self::Error>> + Send {
-        future::ready(RecycleResult::Ok(()))
+        connection: &mut Self::Type,
+        metrics: &Metrics,
+    ) -> RecycleResult<Self::Error> {
+        if self.config.is_expired(metrics.age()) {
+            return Err(RecycleError::message(
+                "The connection exceeded the maximum lifetime",
+            ));
+        }
+        if self.config.is_idle_expired(metrics.last_used()) {
+            return Err(RecycleError::message(
+                "The connection exceeded the idle timeout",
+            ));
+        }
+        let mut reset = false;
+        if self.config.reset_on_release {
+            reset = connection.reset().await.map_err(|e| {
+                log::warn!("Discarding a pooled connection that could not be reset: {e:#}");
+                RecycleError::Backend(e)
+            })?;
+            if reset && let Some(hook) = &self.hooks.after_connect {
+                hook(connection).await.map_err(|e| {
+                    log::warn!(
+                        "Discarding a pooled connection, the after_connect hook failed: {e:#}"
+                    );
+                    RecycleError::Backend(e)
+                })?;
+            }
+        }
+        // A reset already reached the database
+        if self.config.test_on_checkout && !reset {
+            connection.ping().await.map_err(|e| {
+                log::warn!("Discarding a pooled connection that failed the health check: {e:#}");
+                RecycleError::Backend(e)
+            })?;
+        }
+        if let Some(hook) = &self.hooks.before_acquire {
+            hook(connection).await.map_err(|e| {
+                log::warn!("Discarding a pooled connection, the before_acquire hook failed: {e:#}");
+                RecycleError::Backend(e)
+            })?;
+        }
+        Ok(())
     }
 }
 
+/// Runs the `after_release` hook of a connection returned to the pool.
+async fn release<D: Driver>(hooks: &PoolHooks<D>, connection: &mut D::Connection) -> Result<()> {
+    if let Some(hook) = &hooks.after_release {
+        hook(connection)
+            .await
+            .context("While running the after_release hook")?;
+    }
+    Ok(())
+}
+
+/// Spawns the background task processing the connections returned to the pool.
+///
+/// A connection goes back to the idle ones only after [`release`] succeeds, it is
+/// closed otherwise. Nothing is spawned when there is nothing to run on release,
+/// outside of a Tokio runtime the connections needing it are closed instead.
+pub(crate) fn spawn_releaser<D: Driver + 'static>(
+    mut manager: DBConnectionManager<D>,
+) -> DBConnectionManager<D> {
+    if !manager.needs_release() {
+        return manager;
+    }
+    let Ok(handle) = Handle::try_current() else {
+        log::warn!(
+            "No Tokio runtime available, the connections returned to the pool will be closed"
+        );
+        return manager;
+    };
+    let (sender, mut receiver) = unbounded_channel::<Object<DBConnectionManager<D>>>();
+    let hooks = manager.hooks.clone();
+    handle.spawn(async move {
+        while let Some(mut object) = receiver.recv().await {
+            let hooks = hooks.clone();
+            tokio::spawn(async move {
+                if let Err(e) = release(&hooks, &mut object).await {
+                    log::warn!("Discarding a pooled connection that could not be released: {e:#}");
+                    drop(Object::take(object));
+                }
+            });
+        }
+    });
+    manager.released = Some(sender);
+    manager
+}
+
+/// Spawns the background task enforcing `max_lifetime`, `idle_timeout` and
+/// `min_idle` on idle connections.
+///
+/// The task holds a weak reference and stops once the pool is closed or dropped.
+/// Nothing is spawned when the configuration doesn't need it or when called
+/// outside of a Tokio runtime.
+pub(crate) fn spawn_reaper<D: Driver + 'static>(pool: &Pool<DBConnectionManager<D>>) {
+    let config = *pool.manager().config();
+    if !config.needs_reaper() {
+        return;
+    }
+    let Ok(handle) = Handle::try_current() else {
+        log::warn!("No Tokio runtime available, the connection pool reaper will not run");
+        return;
+    };
+    let weak = pool.weak();
+    handle.spawn(async move {
+        let mut interval = tokio::time::interval(config.reaper_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if !reap(&weak).await {
+                break;
+            }
+        }
+    });
+}
+
+/// Runs one reaper cycle, returns false when the pool is gone.
+async fn reap<D: Driver>(weak: &WeakPool<DBConnectionManager<D>>) -> bool {
+    let Some(pool) = weak.upgrade() else {
+        return false;
+    };
+    if pool.is_closed() {
+        return false;
+    }
+    let config = *pool.manager().config();
+    let mut surplus = pool.status().available.saturating_sub(config.min_idle);
+    let removed = pool
+        .retain(|_, metrics| {
+            if config.is_expired(metrics.age()) {
+                return false;
+            }
+            if surplus > 0 && config.is_idle_expired(metrics.last_used()) {
+                surplus -= 1;
+                return false;
+            }
+            true
+        })
+        .removed;
+    if !removed.is_empty() {
+        log::debug!(
+            "Connection pool reaper closed {} connections",
+            removed.len()
+        );
+    }
+    drop(removed);
+    if config.min_idle > 0 {
+        // Borrowing the connections at the same time forces the pool to open the missing ones,
+        // the idle ones are health checked in the process
+        let timeouts = Timeouts {
+            wait: Some(Duration::ZERO),
+            ..pool.timeouts()
+        };
+        let mut borrowed = Vec::with_capacity(config.min_idle);
+        while borrowed.len() < config.min_idle {
+            match pool.timeout_get(&timeouts).await {
+                Ok(v) => borrowed.push(v),
+                Err(..) => break,
+            }
+        }
+    }
+    true
+}
+
 /// A database connection borrowed from a [`ConnectionPool`].
 ///
 /// Implements both [`Executor`] and [`Connection`], so it can be used anywhere
@@ -59,7 +274,8 @@ impl<D: Driver> Manager for DBConnectionManager<D> {
 /// outside pool management, call [`ConnectionPool::detach`] instead.
 #[derive(Debug)]
 pub struct PooledConnection<D: Driver> {
-    pub(crate) object: Object<DBConnectionManager<D>>,
+    pub(crate) object: ManuallyDrop<Object<DBConnectionManager<D>>>,
+    pub(crate) stats: Arc<PoolStats>,
 }
 
 /// A managed pool of reusable database connections.
@@ -83,6 +299,14 @@ pub trait ConnectionPool<D: Driver>: Sync + Send + Debug {
     /// stricter or looser deadlines than the pool-level default.
     fn timeout_get<'s>(&'s self, timeout: Duration) -> BoxFuture<'s, Result<PooledConnection<D>>>;
 
+    /// Acquires a connection meant for read-only work.
+    ///
+    /// Pools with replicas (see [`RoutingPool`](crate::RoutingPool)) hand out a
+    /// replica connection, the others behave like [`get`](ConnectionPool::get).
+    fn get_read<'s>(&'s self) -> BoxFuture<'s, Result<PooledConnection<D>>> {
+        self.get()
+    }
+
     /// Acquires a connection and removes it from pool management.
     ///
     /// The returned `D::Connection` is a plain, pool-unaware connection.  It
@@ -109,7 +333,43 @@ pub trait ConnectionPool<D: Driver>: Sync + Send + Debug {
 
     /// Closes the pool, marking it as unavailable and dropping all managed
     /// connections.
-    fn close(self) -> BoxFuture<'static, Result<()>>;
+    ///
+    /// Every handle to the pool (clones, boxes, arcs) sees it closed.
+    fn close(&self) -> BoxFuture<'static, Result<()>>;
+
+    /// Returns a snapshot of the pool size, occupancy and usage counters.
+    ///
+    /// With the `metrics` feature, the occupancy gauges are also published
+    /// every time this is called.
+    fn status(&self) -> PoolStatus;
+}
+
+/// Acquires a connection from the deadpool pool, recording the acquire statistics.
+async fn acquire<D: Driver>(
+    pool: &Pool<DBConnectionManager<D>>,
+    timeouts: Option<&Timeouts>,
+) -> Result<PooledConnection<D>> {
+    let stats = &pool.manager().stats;
+    let start = Instant::now();
+    let result = match timeouts {
+        Some(timeouts) => pool.timeout_get(timeouts).await,
+        None => pool.get().await,
+    };
+    match result {
+        Ok(object) => {
+            stats.record_acquire(start.elapsed());
+            Ok(PooledConnection {
+                object: ManuallyDrop::new(object),
+                stats: stats.clone(),
+            })
+        }
+        Err(e) => {
+            if matches!(e, PoolError::Timeout(..)) {
+                stats.record_timeout();
+            }
+            Err(anyhow!("{e:#?}"))
+        }
+    }
 }
 
 impl<D: Driver> ConnectionPool<D> for Pool<DBConnectionManager<D>>
@@ -117,24 +377,16 @@ where
     <D as Driver>::Connection: Debug,
 {
     fn get<'s>(&'s self) -> BoxFuture<'s, Result<PooledConnection<D>>> {
-        async move {
-            let object = Pool::<DBConnectionManager<D>>::get(self)
-                .await
-                .map_err(|e| anyhow!("{e:#?}"))?;
-            Ok(PooledConnection { object })
-        }
-        .boxed()
+        acquire(self, None).boxed()
     }
 
     fn timeout_get<'s>(&'s self, timeout: Duration) -> BoxFuture<'s, Result<PooledConnection<D>>> {
         async move {
-            let object = Pool::<DBConnectionManager<D>>::timeout_get(
+            acquire(
                 self,
-                &Timeouts::wait_millis(timeout.as_millis() as u64),
+                Some(&Timeouts::wait_millis(timeout.as_millis() as u64)),
             )
             .await
-            .map_err(|e| anyhow!("{e:#?}"))?;
-            Ok(PooledConnection::<D> { object })
         }
         .boxed()
     }
@@ -172,10 +424,26 @@ where
         Arc::new(self)
     }
 
-    fn close(self) -> BoxFuture<'static, Result<()>> {
-        Self::close(&self);
+    fn close(&self) -> BoxFuture<'static, Result<()>> {
+        Pool::close(self);
         future::ready(Ok(())).boxed()
     }
+
+    fn status(&self) -> PoolStatus {
+        self.manager().stats.status(Pool::status(self))
+    }
+}
+
+impl<D: Driver> PooledConnection<D> {
+    /// Closes the connection instead of returning it to the pool.
+    pub(crate) fn discard(self) {
+        let mut this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, each field is moved out exactly once
+        let (object, stats) =
+            unsafe { (ManuallyDrop::take(&mut this.object), ptr::read(&this.stats)) };
+        drop(stats);
+        drop(Object::take(object));
+    }
 }
 
 impl<D: Driver> Executor for PooledConnection<D> {
@@ -204,21 +472,33 @@ impl<D: Driver> Executor for PooledConnection<D> {
         &'s mut self,
         query: impl AsQuery<D> + 's,
     ) -> impl Stream<Item = Result<QueryResult>> + Send {
-        self.object.run(query)
+        let timer = QueryTimer::new(&self.stats);
+        self.object.run(query).map(move |v| {
+            let _ = &timer;
+            v
+        })
     }
 
     fn fetch<'s>(
         &'s mut self,
         query: impl AsQuery<D> + 's,
     ) -> impl Stream<Item = Result<Row>> + Send {
-        self.object.fetch(query)
+        let timer = QueryTimer::new(&self.stats);
+        self.object.fetch(query).map(move |v| {
+            let _ = &timer;
+            v
+        })
     }
 
     fn execute<'s>(
         &'s mut self,
         query: impl AsQuery<D> + 's,
     ) -> impl Future<Output = Result<RowsAffected>> + Send {
-        self.object.execute(query)
+        let timer = QueryTimer::new(&self.stats);
+        self.object.execute(query).map(move |v| {
+            drop(timer);
+            v
+        })
     }
 
     fn append<It>(&mut self, entities: It) -> impl Future<Output = Result<RowsAffected>> + Send
@@ -229,6 +509,17 @@ impl<D: Driver> Executor for PooledConnection<D> {
     {
         self.object.append(entities)
     }
+
+    fn describe_table(
+        &mut self,
+        table: &TableRef,
+    ) -> impl Future<Output = Result<Vec<ColumnInfo>>> + Send {
+        self.object.describe_table(table)
+    }
+
+    fn list_tables(&mut self, schema: &str) -> impl Future<Output = Result<Vec<TableRef>>> + Send {
+        self.object.list_tables(schema)
+    }
 }
 
 impl<D: Driver> Connection for PooledConnection<D> {
@@ -247,6 +538,32 @@ impl<D: Driver> Connection for PooledConnection<D> {
     fn begin(&mut self) -> impl Future<Output = Result<<D as Driver>::Transaction<'_>>> + Send {
         self.object.begin()
     }
+
+    fn ping(&mut self) -> impl Future<Output = Result<()>> + Send {
+        self.object.ping()
+    }
+}
+
+impl<D: Driver> Drop for PooledConnection<D> {
+    fn drop(&mut self) {
+        // SAFETY: the object is taken only here and never accessed again
+        let object = unsafe { ManuallyDrop::take(&mut self.object) };
+        let Some(pool) = Object::pool(&object) else {
+            return;
+        };
+        let manager = pool.manager();
+        if !manager.needs_release() {
+            return;
+        }
+        match &manager.released {
+            Some(sender) => {
+                if let Err(e) = sender.send(object) {
+                    drop(Object::take(e.0));
+                }
+            }
+            None => drop(Object::take(object)),
+        }
+    }
 }
 
 impl<D: Driver> Deref for PooledConnection<D> {
@@ -13,12 +13,40 @@ pub struct PoolConfig {
     pub create_timeout: Option<Duration>,
     pub recycle_timeout: Option<Duration>,
     pub queue_mode: QueueMode,
+    /// Connections older than this are closed instead of being handed out again.
+    pub max_lifetime: Option<Duration>,
+    /// Idle connections unused for longer than this are closed (down to `min_idle`).
+    pub idle_timeout: Option<Duration>,
+    /// Number of idle connections the background reaper keeps ready.
+    pub min_idle: usize,
+    /// Ping the connection (see [`Connection::ping`](crate::Connection::ping)) before handing it out, on by default.
+    pub test_on_checkout: bool,
+    /// How often the background reaper wakes up.
+    pub reaper_interval: Duration,
+    /// Reset the session state (see [`Connection::reset`](crate::Connection::reset)) of a
+    /// connection returned to the pool before handing it out again, on by default.
+    pub reset_on_release: bool,
 }
 
 impl PoolConfig {
     pub fn new() -> Self {
         deadpool::managed::PoolConfig::default().into()
     }
+
+    /// True if the connection must be discarded because of `max_lifetime`.
+    pub fn is_expired(&self, age: Duration) -> bool {
+        self.max_lifetime.is_some_and(|v| age >= v)
+    }
+
+    /// True if the connection has been idle for longer than `idle_timeout`.
+    pub fn is_idle_expired(&self, idle: Duration) -> bool {
+        self.idle_timeout.is_some_and(|v| idle >= v)
+    }
+
+    /// True if the settings require the background reaper task.
+    pub fn needs_reaper(&self) -> bool {
+        self.max_lifetime.is_some() || self.idle_timeout.is_some() || self.min_idle > 0
+    }
 }
 
 impl From<PoolConfig> for deadpool::managed::PoolConfig {
@@ -49,6 +77,12 @@ impl From<deadpool::managed::PoolConfig> for PoolConfig {
                 deadpool::managed::QueueMode::Fifo => QueueMode::Fifo,
                 deadpool::managed::QueueMode::Lifo => QueueMode::Lifo,
             },
+            max_lifetime: None,
+            idle_timeout: None,
+            min_idle: 0,
+            test_on_checkout: true,
+            reaper_interval: Duration::from_secs(30),
+            reset_on_release: true,
         }
     }
 }
@@ -0,0 +1,104 @@
+use crate::{
+    AsQuery, ColumnInfo, ConnectionPool, DBConnectionManager, Driver, Executor, Query, QueryResult,
+    Result, RoutingPool, Row, TableRef,
+    stream::{Stream, StreamExt},
+};
+use anyhow::anyhow;
+use async_stream::try_stream;
+use deadpool::managed::Pool;
+use std::{
+    future::{self, Future},
+    pin::pin,
+    sync::Arc,
+};
+
+/// Runs the query on a connection checked out for the lifetime of the stream.
+fn pool_run<'s, D: Driver, P: ConnectionPool<D> + ?Sized>(
+    pool: &'s P,
+    query: impl AsQuery<D> + 's,
+) -> impl Stream<Item = Result<QueryResult>> + Send {
+    let mut query = query.as_query();
+    try_stream! {
+        let mut connection = pool.get().await?;
+        let mut stream = pin!(connection.run(query.as_mut()));
+        while let Some(value) = stream.next().await {
+            yield value?;
+        }
+    }
+}
+
+/// Fetches the rows using a read connection (see [`ConnectionPool::get_read`]).
+fn pool_fetch<'s, D: Driver, P: ConnectionPool<D> + ?Sized>(
+    pool: &'s P,
+    query: impl AsQuery<D> + 's,
+) -> impl Stream<Item = Result<Row>> + Send {
+    let mut query = query.as_query();
+    try_stream! {
+        let mut connection = pool.get_read().await?;
+        let mut stream = pin!(connection.fetch(query.as_mut()));
+        while let Some(value) = stream.next().await {
+            yield value?;
+        }
+    }
+}
+
+/// Implements [`Executor`] for connection pool types.
+///
+/// Every call checks out a connection, kept until the returned future or stream completes.
+/// `fetch` uses [`ConnectionPool::get_read`], everything else [`ConnectionPool::get`].
+/// Prepared statements belong to a single connection and are not supported.
+///
+/// Shared references to the pools are executors as well, they can be used by many tasks at once.
+macro_rules! impl_pool_executor {
+    ($($([$lt:lifetime])? $pool:ident: $ty:ty => $deref:expr),+ $(,)?) => {$(
+        impl<$($lt,)? D: Driver> Executor for $ty {
+            type Driver = D;
+
+            fn accepts_multiple_statements(&self) -> bool {
+                // Not all the drivers do, stay on the safe side
+                false
+            }
+
+            fn do_prepare(&mut self, _sql: String) -> impl Future<Output = Result<Query<D>>> + Send {
+                future::ready(Err(anyhow!(
+                    "Cannot prepare a query on a connection pool, acquire a connection with `get` first"
+                )))
+            }
+
+            fn run<'s>(
+                &'s mut self,
+                query: impl AsQuery<D> + 's,
+            ) -> impl Stream<Item = Result<QueryResult>> + Send {
+                let $pool = &*self;
+                pool_run($deref, query)
+            }
+
+            fn fetch<'s>(
+                &'s mut self,
+                query: impl AsQuery<D> + 's,
+            ) -> impl Stream<Item = Result<Row>> + Send {
+                let $pool = &*self;
+                pool_fetch($deref, query)
+            }
+
+            async fn describe_table(&mut self, table: &TableRef) -> Result<Vec<ColumnInfo>> {
+                let $pool = &*self;
+                $deref.get_read().await?.describe_table(table).await
+            }
+
+            async fn list_tables(&mut self, schema: &str) -> Result<Vec<TableRef>> {
+                let $pool = &*self;
+                $deref.get_read().await?.list_tables(schema).await
+            }
+        }
+    )+};
+}
+
+impl_pool_executor!(
+    pool: Pool<DBConnectionManager<D>> => pool,
+    pool: RoutingPool<D> => pool,
+    pool: Box<dyn ConnectionPool<D> + Send + Sync> => pool.as_ref(),
+    pool: Arc<dyn ConnectionPool<D> + Send + Sync> => pool.as_ref(),
+    ['p] pool: &'p Pool<DBConnectionManager<D>> => *pool,
+    ['p] pool: &'p RoutingPool<D> => *pool,
+);
@@ -0,0 +1,99 @@
+use crate::{Driver, Result};
+use futures::future::BoxFuture;
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// Async callback receiving a connection managed by the pool.
+pub type PoolHook<D> = Arc<
+    dyn for<'c> Fn(&'c mut <D as Driver>::Connection) -> BoxFuture<'c, Result<()>> + Send + Sync,
+>;
+
+/// Callbacks invoked by the connection pool during the lifecycle of its connections.
+///
+/// Every hook returns a boxed future, an error discards the connection (or fails the
+/// connection attempt for `after_connect`).
+///
+/// ```ignore
+/// let hooks = PoolHooks::new()
+///     .after_connect(|c| {
+///         async move {
+///             c.execute("SET TIME ZONE 'UTC'").await?;
+///             Ok(())
+///         }
+///         .boxed()
+///     });
+/// let pool = driver.connect_pool_with_hooks(url, PoolConfig::new(), hooks).await?;
+/// ```
+pub struct PoolHooks<D: Driver> {
+    /// Runs once on every new connection, and again after the session is reset.
+    pub after_connect: Option<PoolHook<D>>,
+    /// Runs on an idle connection right before it is handed out.
+    pub before_acquire: Option<PoolHook<D>>,
+    /// Runs on a connection as soon as it is returned to the pool, before it is reset.
+    pub after_release: Option<PoolHook<D>>,
+}
+
+impl<D: Driver> PoolHooks<D> {
+    pub fn new() -> Self {
+        Self {
+            after_connect: None,
+            before_acquire: None,
+            after_release: None,
+        }
+    }
+
+    /// Sets the hook run on new connections, typically session settings.
+    pub fn after_connect<F>(mut self, hook: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut D::Connection) -> BoxFuture<'c, Result<()>> + Send + Sync + 'static,
+    {
+        self.after_connect = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets the hook run before an idle connection is handed out.
+    pub fn before_acquire<F>(mut self, hook: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut D::Connection) -> BoxFuture<'c, Result<()>> + Send + Sync + 'static,
+    {
+        self.before_acquire = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets the hook run on connections returned to the pool.
+    pub fn after_release<F>(mut self, hook: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut D::Connection) -> BoxFuture<'c, Result<()>> + Send + Sync + 'static,
+    {
+        self.after_release = Some(Arc::new(hook));
+        self
+    }
+}
+
+impl<D: Driver> Default for PoolHooks<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Driver> Clone for PoolHooks<D> {
+    fn clone(&self) -> Self {
+        Self {
+            after_connect: self.after_connect.clone(),
+            before_acquire: self.before_acquire.clone(),
+            after_release: self.after_release.clone(),
+        }
+    }
+}
+
+impl<D: Driver> Debug for PoolHooks<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolHooks")
+            .field("after_connect", &self.after_connect.is_some())
+            .field("before_acquire", &self.before_acquire.is_some())
+            .field("after_release", &self.after_release.is_some())
+            .finish()
+    }
+}
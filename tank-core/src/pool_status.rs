@@ -0,0 +1,150 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Snapshot of the state of a connection pool, see [`ConnectionPool::status`](crate::ConnectionPool::status).
+///
+/// The counters are cumulative since the pool was created.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct PoolStatus {
+    /// Maximum number of connections.
+    pub max_size: usize,
+    /// Open connections, idle or in use.
+    pub size: usize,
+    /// Idle connections ready to be handed out.
+    pub idle: usize,
+    /// Connections currently borrowed.
+    pub in_use: usize,
+    /// Callers waiting for a connection.
+    pub waiting: usize,
+    /// Connections successfully acquired.
+    pub acquired: u64,
+    /// Acquire attempts that timed out.
+    pub timeouts: u64,
+    /// Total time spent acquiring connections.
+    pub acquire_time: Duration,
+    /// Queries run through pooled connections.
+    pub queries: u64,
+    /// Total time spent running queries through pooled connections.
+    pub query_time: Duration,
+}
+
+impl PoolStatus {
+    /// Average time needed to acquire a connection.
+    pub fn mean_acquire_time(&self) -> Option<Duration> {
+        (self.acquired > 0).then(|| self.acquire_time.div_f64(self.acquired as f64))
+    }
+
+    /// Average query duration.
+    pub fn mean_query_time(&self) -> Option<Duration> {
+        (self.queries > 0).then(|| self.query_time.div_f64(self.queries as f64))
+    }
+
+    /// Fraction of the connections in use, from 0 to 1.
+    pub fn saturation(&self) -> f64 {
+        if self.max_size == 0 {
+            return 0.0;
+        }
+        self.in_use as f64 / self.max_size as f64
+    }
+}
+
+/// Counters shared by a pool and its connections.
+///
+/// With the `metrics` feature every event is also published through the `metrics` facade,
+/// labeled with the driver name.
+#[derive(Debug)]
+pub(crate) struct PoolStats {
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    driver: &'static str,
+    acquired: AtomicU64,
+    timeouts: AtomicU64,
+    acquire_nanos: AtomicU64,
+    queries: AtomicU64,
+    query_nanos: AtomicU64,
+}
+
+impl PoolStats {
+    pub(crate) fn new(driver: &'static str) -> Self {
+        Self {
+            driver,
+            acquired: Default::default(),
+            timeouts: Default::default(),
+            acquire_nanos: Default::default(),
+            queries: Default::default(),
+            query_nanos: Default::default(),
+        }
+    }
+
+    pub(crate) fn record_acquire(&self, elapsed: Duration) {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        self.acquire_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("tank_pool_acquire_seconds", "driver" => self.driver)
+            .record(elapsed.as_secs_f64());
+    }
+
+    pub(crate) fn record_timeout(&self) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::counter!("tank_pool_acquire_timeouts_total", "driver" => self.driver).increment(1);
+    }
+
+    pub(crate) fn record_query(&self, elapsed: Duration) {
+        self.queries.fetch_add(1, Ordering::Relaxed);
+        self.query_nanos
+            .fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("tank_pool_query_seconds", "driver" => self.driver)
+            .record(elapsed.as_secs_f64());
+    }
+
+    /// Combines the counters with the pool occupancy and publishes the gauges.
+    pub(crate) fn status(&self, status: deadpool::Status) -> PoolStatus {
+        let result = PoolStatus {
+            max_size: status.max_size,
+            size: status.size,
+            idle: status.available,
+            in_use: status.size.saturating_sub(status.available),
+            waiting: status.waiting,
+            acquired: self.acquired.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            acquire_time: Duration::from_nanos(self.acquire_nanos.load(Ordering::Relaxed)),
+            queries: self.queries.load(Ordering::Relaxed),
+            query_time: Duration::from_nanos(self.query_nanos.load(Ordering::Relaxed)),
+        };
+        #[cfg(feature = "metrics")]
+        {
+            let driver = self.driver;
+            metrics::gauge!("tank_pool_max_size", "driver" => driver).set(result.max_size as f64);
+            metrics::gauge!("tank_pool_connections", "driver" => driver).set(result.size as f64);
+            metrics::gauge!("tank_pool_idle_connections", "driver" => driver)
+                .set(result.idle as f64);
+            metrics::gauge!("tank_pool_waiting", "driver" => driver).set(result.waiting as f64);
+        }
+        result
+    }
+}
+
+/// Records the duration of a query when dropped.
+pub(crate) struct QueryTimer<'a> {
+    stats: &'a PoolStats,
+    start: Instant,
+}
+
+impl<'a> QueryTimer<'a> {
+    pub(crate) fn new(stats: &'a PoolStats) -> Self {
+        Self {
+            stats,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer<'_> {
+    fn drop(&mut self) {
+        self.stats.record_query(self.start.elapsed());
+    }
+}
@@ -1,11 +1,15 @@
 mod as_query;
 mod builder;
 mod dyn_query;
+mod parameters;
 mod prepared;
 mod query;
+mod sql;
 
 pub use as_query::*;
 pub use builder::*;
 pub use dyn_query::*;
+pub use parameters::*;
 pub use prepared::*;
 pub use query::*;
+pub use sql::*;
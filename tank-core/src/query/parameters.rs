@@ -0,0 +1,277 @@
+use crate::{
+    AsValue, DynQuery, Prepared, Result, Value,
+    writer::{Context, Fragment, SqlWriter},
+};
+use anyhow::anyhow;
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem,
+};
+
+/// Names of the placeholders of a prepared query, used by `Prepared::bind_named`.
+///
+/// A name used multiple times in the query maps to all of its placeholders.
+#[derive(Default, Clone, PartialEq, Eq, Debug)]
+pub struct ParameterNames {
+    names: Vec<(u64, String)>,
+}
+
+impl ParameterNames {
+    /// Empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Name the placeholder at index.
+    pub fn push(&mut self, index: u64, name: impl Into<String>) {
+        self.names.push((index, name.into()));
+    }
+    /// True if there are no named placeholders.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+    /// Number of named placeholders.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+    /// Indexes of the placeholders with the name.
+    pub fn indexes<'s>(&'s self, name: &'s str) -> impl Iterator<Item = u64> + 's {
+        self.names
+            .iter()
+            .filter(move |(_, v)| v == name)
+            .map(|(i, _)| *i)
+    }
+    /// Pairs of index and name, in placeholder order.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &str)> {
+        self.names.iter().map(|(i, v)| (*i, v.as_str()))
+    }
+    /// Same names with the indexes shifted, for drivers whose indexes do not start from 0.
+    pub fn offset(mut self, offset: u64) -> Self {
+        for (index, _) in &mut self.names {
+            *index += offset;
+        }
+        self
+    }
+}
+
+/// Values for one execution of a prepared query, see `Executor::execute_batch`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Params {
+    /// Bound in placeholder order.
+    Positional(Vec<Value>),
+    /// Bound to every placeholder with the name.
+    Named(Vec<(String, Value)>),
+}
+
+impl Params {
+    /// Replace the bindings of the prepared query with these values.
+    pub fn bind<P: Prepared>(self, prepared: &mut P) -> Result<()> {
+        prepared.clear_bindings()?;
+        match self {
+            Params::Positional(values) => {
+                for value in values {
+                    prepared.bind(value)?;
+                }
+            }
+            Params::Named(values) => {
+                for (name, value) in values {
+                    prepared.bind_named(&name, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Conversion into the values of one execution of a prepared query.
+///
+/// Implemented for tuples, arrays and vectors (positional) and for maps from name to value (named).
+pub trait IntoParams {
+    fn into_params(self) -> Params;
+}
+
+impl IntoParams for Params {
+    fn into_params(self) -> Params {
+        self
+    }
+}
+
+impl IntoParams for () {
+    fn into_params(self) -> Params {
+        Params::Positional(Vec::new())
+    }
+}
+
+impl<T: AsValue> IntoParams for Vec<T> {
+    fn into_params(self) -> Params {
+        Params::Positional(self.into_iter().map(AsValue::as_value).collect())
+    }
+}
+
+impl<T: AsValue, const N: usize> IntoParams for [T; N] {
+    fn into_params(self) -> Params {
+        Params::Positional(self.into_iter().map(AsValue::as_value).collect())
+    }
+}
+
+impl<K: Into<String>, V: AsValue, S> IntoParams for HashMap<K, V, S> {
+    fn into_params(self) -> Params {
+        Params::Named(
+            self.into_iter()
+                .map(|(k, v)| (k.into(), v.as_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<K: Into<String>, V: AsValue> IntoParams for BTreeMap<K, V> {
+    fn into_params(self) -> Params {
+        Params::Named(
+            self.into_iter()
+                .map(|(k, v)| (k.into(), v.as_value()))
+                .collect(),
+        )
+    }
+}
+
+macro_rules! impl_into_params {
+    ($($t:ident),+) => {
+        impl<$($t: AsValue),+> IntoParams for ($($t,)+) {
+            #[allow(non_snake_case)]
+            fn into_params(self) -> Params {
+                let ($($t,)+) = self;
+                Params::Positional(vec![$($t.as_value()),+])
+            }
+        }
+    };
+}
+
+impl_into_params!(A);
+impl_into_params!(A, B);
+impl_into_params!(A, B, C);
+impl_into_params!(A, B, C, D);
+impl_into_params!(A, B, C, D, E);
+impl_into_params!(A, B, C, D, E, F);
+impl_into_params!(A, B, C, D, E, F, G);
+impl_into_params!(A, B, C, D, E, F, G, H);
+impl_into_params!(A, B, C, D, E, F, G, H, I);
+impl_into_params!(A, B, C, D, E, F, G, H, I, J);
+impl_into_params!(A, B, C, D, E, F, G, H, I, J, K);
+impl_into_params!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Position right after the first `pattern` found from `start`, or the end of the sql.
+fn skip_past(sql: &str, start: usize, pattern: &str) -> usize {
+    sql[start..]
+        .find(pattern)
+        .map_or(sql.len(), |v| start + v + pattern.len())
+}
+
+/// Position right after the closing `quote` of the literal starting at `start`.
+fn skip_quoted(sql: &str, start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let bytes = sql.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    sql.len()
+}
+
+/// Rewrite the named placeholders of the sql into the native ones of the writer (`?`, `$1`, ...),
+/// returning the names of the placeholders.
+///
+/// The accepted prefixes depend on the writer (`SqlWriter::named_parameter_prefixes`), MySQL for
+/// example does not accept `@name` because it is a user variable. String literals, quoted
+/// identifiers, comments and dollar quoted strings are left untouched, as well as `::` casts,
+/// `@@` variables, map literals and positional placeholders like `$1`. Mixing named and positional
+/// placeholders in the same query is an error. If there are no named placeholders, the sql is
+/// returned unchanged.
+pub fn rewrite_named_parameters(
+    sql: &str,
+    writer: &dyn SqlWriter,
+) -> Result<(String, ParameterNames)> {
+    let prefixes = writer.named_parameter_prefixes();
+    let backslash_escapes = writer.string_backslash_escapes();
+    // `?` is an operator (json for example) when the native placeholder is not `?`
+    let question_mark = {
+        let mut native = DynQuery::default();
+        writer.write_question_mark(
+            &mut Context::new(Fragment::ParameterBinding, false),
+            &mut native,
+        );
+        native.as_str() == "?"
+    };
+    let mut names = ParameterNames::new();
+    let mut positional = false;
+    let mut context = Context::new(Fragment::ParameterBinding, false);
+    let mut out = DynQuery::with_capacity(sql.len());
+    let bytes = sql.as_bytes();
+    // Start of the text not yet copied to the output
+    let mut copied = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let next = bytes.get(i + 1).copied();
+        i = match c {
+            b'\'' | b'"' => skip_quoted(sql, i + 1, c, backslash_escapes),
+            b'`' => skip_quoted(sql, i + 1, c, false),
+            b'-' if next == Some(b'-') => skip_past(sql, i + 2, "\n"),
+            b'/' if next == Some(b'*') => skip_past(sql, i + 2, "*/"),
+            b'?' => {
+                positional |= question_mark;
+                i + 1
+            }
+            b':' | b'@' | b'$' => {
+                let start = i + 1;
+                let name_len = sql[start..]
+                    .find(|v| !is_name_char(v))
+                    .unwrap_or(sql.len() - start);
+                let name = &sql[start..start + name_len];
+                let end = start + name_len;
+                if sql[..i].chars().next_back().is_some_and(|v| {
+                    is_name_char(v)
+                        || v == c as char
+                        || matches!(v, '\'' | '"' | '`' | ')' | ']' | '}' | '.')
+                }) {
+                    // Part of an identifier, `::` cast, `@@` variable or `'key':value` pair
+                    end
+                } else if c == b'$' && bytes.get(end) == Some(&b'$') {
+                    // Dollar quoted string `$$ ... $$` or `$tag$ ... $tag$`
+                    skip_past(sql, end + 1, &sql[i..end + 1])
+                } else if name.starts_with(is_name_start) && prefixes.contains(&(c as char)) {
+                    out.push_str(&sql[copied..i]);
+                    names.push(names.len() as u64, name);
+                    writer.write_question_mark(&mut context, &mut out);
+                    copied = end;
+                    end
+                } else {
+                    // Positional placeholder `$1`
+                    positional |=
+                        c == b'$' && !name.is_empty() && name.bytes().all(|v| v.is_ascii_digit());
+                    end
+                }
+            }
+            _ => i + 1,
+        };
+    }
+    if names.is_empty() {
+        return Ok((sql.to_string(), names));
+    }
+    if positional {
+        return Err(anyhow!(
+            "Cannot mix named and positional placeholders in the same query"
+        ));
+    }
+    out.push_str(&sql[copied..]);
+    Ok((mem::take(out.buffer()), names))
+}
@@ -1,4 +1,5 @@
-use crate::{AsValue, Result};
+use crate::{AsValue, ParameterNames, Result};
+use anyhow::anyhow;
 use std::{
     any::Any,
     fmt::{Debug, Display},
@@ -11,6 +12,7 @@ use std::{
 /// # Semantics
 /// * `bind`: Append value.
 /// * `bind_index`: Set value at 0-based index.
+/// * `bind_named`: Set value at every placeholder with the name (`:name`, `@name` or `$name`).
 pub trait Prepared: Any + Send + Sync + Display + Debug {
     fn as_any(self: Box<Self>) -> Box<dyn Any>;
     /// Clear all bindings.
@@ -25,6 +27,36 @@ pub trait Prepared: Any + Send + Sync + Display + Debug {
     fn bind_index(&mut self, value: impl AsValue, index: u64) -> Result<&mut Self>
     where
         Self: Sized;
+    /// Names of the placeholders, if the query has named ones.
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        None
+    }
+    /// Store the names of the placeholders, called when preparing a query with named ones.
+    fn set_parameter_names(&mut self, _names: ParameterNames) -> Result<()> {
+        Err(anyhow!(
+            "This prepared query does not support named parameters"
+        ))
+    }
+    /// Bind value to every placeholder with the name.
+    fn bind_named(&mut self, name: &str, value: impl AsValue) -> Result<&mut Self>
+    where
+        Self: Sized,
+    {
+        let indexes = self
+            .parameter_names()
+            .map(|v| v.indexes(name).collect::<Vec<_>>())
+            .unwrap_or_default();
+        if indexes.is_empty() {
+            return Err(anyhow!(
+                "The query does not have a parameter named `{name}`"
+            ));
+        }
+        let value = value.as_value();
+        for index in indexes {
+            self.bind_index(value.clone(), index)?;
+        }
+        Ok(self)
+    }
     /// True if the query has no meaningful value (the meaning depends on the driver).
     fn is_empty(&self) -> bool {
         false
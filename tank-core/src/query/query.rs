@@ -1,4 +1,6 @@
-use crate::{AsValue, Driver, DynQuery, Prepared, Result, Row, RowsAffected, truncate_long};
+use crate::{
+    AsValue, Driver, DynQuery, IntoParams, Prepared, Result, Row, RowsAffected, truncate_long,
+};
 use anyhow::anyhow;
 use std::fmt::{self, Display};
 
@@ -60,6 +62,26 @@ impl<D: Driver> Query<D> {
         prepared.bind_index(value, index)?;
         Ok(self)
     }
+    /// Bind value to every placeholder with the name.
+    ///
+    /// Error if not prepared.
+    pub fn bind_named(&mut self, name: &str, value: impl AsValue) -> Result<&mut Self> {
+        let Self::Prepared(prepared) = self else {
+            return Err(anyhow!("Cannot bind a named parameter of a raw query"));
+        };
+        prepared.bind_named(name, value)?;
+        Ok(self)
+    }
+    /// Replace the bound values with the params.
+    ///
+    /// Error if not prepared.
+    pub fn bind_params(&mut self, params: impl IntoParams) -> Result<&mut Self> {
+        let Self::Prepared(prepared) = self else {
+            return Err(anyhow!("Cannot bind the parameters of a raw query"));
+        };
+        params.into_params().bind(prepared)?;
+        Ok(self)
+    }
     pub fn into_dyn(self) -> DynQuery {
         self.into()
     }
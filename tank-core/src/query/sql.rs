@@ -0,0 +1,195 @@
+use crate::{
+    ColumnRef, Driver, DynQuery, Executor, Query, Result, Row, RowsAffected, TableRef, Value,
+    stream::{Stream, StreamExt},
+    writer::{Context, Fragment, SqlWriter},
+};
+use anyhow::anyhow;
+use async_stream::try_stream;
+use std::{borrow::Cow, pin::pin};
+
+/// Piece of a `sql!` query.
+#[derive(Clone, Debug)]
+pub enum SqlPart {
+    /// SQL text, written as is.
+    Text(Cow<'static, str>),
+    /// Value bound to a placeholder.
+    Value(Value),
+    /// Values bound to a parenthesized list of placeholders, like `($1, $2, $3)`.
+    List(Vec<Value>),
+    /// Column name, quoted by the driver.
+    Column(ColumnRef),
+    /// Table name, quoted and qualified with its schema by the driver.
+    Table(TableRef),
+}
+
+/// Raw SQL with interpolated values and identifiers, built by `sql!`.
+///
+/// The values are bound to the native placeholders of the driver (`?`, `$1`, ...) by
+/// [`Sql::prepare`], [`Sql::fetch`] and [`Sql::execute`]. [`Sql::inline`] writes them as
+/// escaped literals instead, for the statements that cannot be prepared. Connection pools cannot
+/// prepare, use a connection checked out of the pool.
+#[derive(Default, Clone, Debug)]
+pub struct Sql {
+    /// Pieces of the query, in order.
+    pub parts: Vec<SqlPart>,
+}
+
+impl Sql {
+    /// New query from its pieces.
+    pub fn new(parts: Vec<SqlPart>) -> Self {
+        Self { parts }
+    }
+
+    /// Write the SQL with placeholders for the values, returning the values in binding order.
+    ///
+    /// Fails on an empty `{#list}`: `IN ()` is not valid SQL.
+    pub fn write_prepared(&self, writer: &dyn SqlWriter, out: &mut DynQuery) -> Result<Vec<Value>> {
+        let mut context = Context::new(Fragment::ParameterBinding, false);
+        let mut values = Vec::new();
+        for part in &self.parts {
+            match part {
+                SqlPart::Value(value) => {
+                    writer.write_question_mark(&mut context, out);
+                    values.push(value.clone());
+                }
+                SqlPart::List(list) => {
+                    Self::write_list(list, out, |out, _| {
+                        writer.write_question_mark(&mut context, out)
+                    })?;
+                    values.extend(list.iter().cloned());
+                }
+                _ => Self::write_part(part, writer, &mut context, out),
+            }
+        }
+        Ok(values)
+    }
+
+    /// Write the SQL with the values written as literals.
+    ///
+    /// Fails on an empty `{#list}`: `IN ()` is not valid SQL.
+    pub fn write_inline(&self, writer: &dyn SqlWriter, out: &mut DynQuery) -> Result<()> {
+        // Same quoting as the literals in a condition
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        for part in &self.parts {
+            match part {
+                SqlPart::Value(value) => writer.write_value(&mut context, out, value),
+                SqlPart::List(list) => Self::write_list(list, out, |out, value| {
+                    writer.write_value(&mut context, out, value)
+                })?,
+                _ => Self::write_part(part, writer, &mut context, out),
+            }
+        }
+        Ok(())
+    }
+
+    /// Prepare the query on the executor and bind the values.
+    pub async fn prepare<E: Executor>(self, executor: &mut E) -> Result<Query<E::Driver>> {
+        let mut sql = DynQuery::default();
+        let values = self.write_prepared(&executor.driver().sql_writer(), &mut sql)?;
+        let mut query = executor.prepare(sql).await?;
+        for value in values {
+            query.bind(value)?;
+        }
+        Ok(query)
+    }
+
+    /// Prepare the query, bind the values and stream the resulting rows.
+    pub fn fetch<'e, E: Executor>(
+        self,
+        executor: &'e mut E,
+    ) -> impl Stream<Item = Result<Row>> + Send + 'e {
+        try_stream! {
+            let query = self.prepare(&mut *executor).await?;
+            let mut stream = pin!(executor.fetch(query));
+            while let Some(value) = stream.next().await {
+                yield value?;
+            }
+        }
+    }
+
+    /// Prepare the query, bind the values and return the number of affected rows.
+    pub async fn execute<E: Executor>(self, executor: &mut E) -> Result<RowsAffected> {
+        let query = self.prepare(executor).await?;
+        executor.execute(query).await
+    }
+
+    /// Query with the values written as escaped literals by the driver.
+    ///
+    /// Use it only for the statements that cannot be prepared, like most DDL.
+    pub fn inline<D: Driver>(&self, driver: &D) -> Result<Query<D>> {
+        let mut query = DynQuery::default();
+        self.write_inline(&driver.sql_writer(), &mut query)?;
+        Ok(query.into())
+    }
+
+    fn write_list(
+        list: &[Value],
+        out: &mut DynQuery,
+        mut write: impl FnMut(&mut DynQuery, &Value),
+    ) -> Result<()> {
+        if list.is_empty() {
+            return Err(anyhow!(
+                "`sql!` list interpolated with `{{#..}}` is empty, `IN ()` is not valid SQL"
+            ));
+        }
+        out.push('(');
+        for (i, value) in list.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write(out, value);
+        }
+        out.push(')');
+        Ok(())
+    }
+
+    fn write_part(
+        part: &SqlPart,
+        writer: &dyn SqlWriter,
+        context: &mut Context,
+        out: &mut DynQuery,
+    ) {
+        match part {
+            SqlPart::Text(text) => out.push_str(text),
+            SqlPart::Column(column) => writer.write_column_ref(context, out, column),
+            SqlPart::Table(table) => writer.write_table_ref(context, out, table),
+            SqlPart::Value(..) | SqlPart::List(..) => {}
+        }
+    }
+}
+
+/// Identifier interpolated by `sql!` with `{ident:..}`.
+pub trait IntoIdentifier {
+    /// Piece of the query writing the identifier.
+    fn into_identifier(self) -> SqlPart;
+}
+
+impl IntoIdentifier for ColumnRef {
+    fn into_identifier(self) -> SqlPart {
+        SqlPart::Column(self)
+    }
+}
+
+impl IntoIdentifier for TableRef {
+    fn into_identifier(self) -> SqlPart {
+        SqlPart::Table(self)
+    }
+}
+
+impl IntoIdentifier for &TableRef {
+    fn into_identifier(self) -> SqlPart {
+        SqlPart::Table(self.clone())
+    }
+}
+
+impl IntoIdentifier for &str {
+    fn into_identifier(self) -> SqlPart {
+        SqlPart::Column(ColumnRef::new(self.to_string().into()))
+    }
+}
+
+impl IntoIdentifier for String {
+    fn into_identifier(self) -> SqlPart {
+        SqlPart::Column(ColumnRef::new(self.into()))
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{Connection, ConnectionPool, Driver, Error, PoolStatus, PooledConnection, Result};
+use anyhow::anyhow;
+use futures::{
+    FutureExt,
+    future::{BoxFuture, try_join_all},
+};
+use std::{
+    iter,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Strategy used by [`RoutingPool`] to pick a replica.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum ReplicaSelection {
+    /// Cycle through the replicas.
+    #[default]
+    RoundRobin,
+    /// Pick the replica with the fewest connections in use or waited for.
+    LeastBusy,
+}
+
+#[derive(Debug)]
+struct Replica<D: Driver> {
+    pool: Box<dyn ConnectionPool<D> + Send + Sync>,
+    ejected_until: Mutex<Option<Instant>>,
+}
+
+impl<D: Driver> Replica<D> {
+    fn is_available(&self, now: Instant) -> bool {
+        self.ejected_until
+            .lock()
+            .map(|v| v.is_none_or(|until| until <= now))
+            .unwrap_or(true)
+    }
+
+    fn eject(&self, until: Instant) {
+        if let Ok(mut v) = self.ejected_until.lock() {
+            *v = Some(until);
+        }
+    }
+}
+
+/// A pool splitting reads and writes between a primary and its replicas.
+///
+/// [`get`](ConnectionPool::get), [`timeout_get`](ConnectionPool::timeout_get) and
+/// [`detach`](ConnectionPool::detach) always use the primary: writes and transactions must go
+/// through them. [`get_read`](ConnectionPool::get_read) hands out a replica connection, falling
+/// back to the primary when no replica is available.
+///
+/// Every replica connection is pinged before it is handed out, regardless of
+/// [`PoolConfig::test_on_checkout`](crate::PoolConfig::test_on_checkout), and the checkout is
+/// bounded by [`checkout_timeout`](RoutingPool::checkout_timeout). A connection failing the ping
+/// is closed instead of going back to its pool. A replica that fails to provide a healthy
+/// connection in time is ejected for [`ejection_time`](RoutingPool::ejection_time) and the next
+/// one is tried.
+///
+/// After a primary connection is acquired, reads can be pinned to the primary for a while to
+/// observe the writes that replication did not propagate yet, see
+/// [`read_your_writes`](RoutingPool::read_your_writes). Use [`RoutingPool::primary`] to target
+/// the primary explicitly.
+///
+/// ```ignore
+/// let pool = RoutingPool::new(driver.connect_pool(primary_url, PoolConfig::new()).await?)
+///     .replica(driver.connect_pool(replica_url, PoolConfig::new()).await?)
+///     .selection(ReplicaSelection::LeastBusy)
+///     .read_your_writes(Duration::from_secs(1));
+/// ```
+#[derive(Debug)]
+pub struct RoutingPool<D: Driver> {
+    primary: Box<dyn ConnectionPool<D> + Send + Sync>,
+    replicas: Vec<Replica<D>>,
+    selection: ReplicaSelection,
+    ejection_time: Duration,
+    checkout_timeout: Duration,
+    read_your_writes: Option<Duration>,
+    next: AtomicUsize,
+    primary_until: Mutex<Option<Instant>>,
+}
+
+impl<D: Driver + 'static> RoutingPool<D> {
+    /// Creates a routing pool without replicas, every connection comes from `primary`.
+    pub fn new(primary: impl ConnectionPool<D>) -> Self {
+        Self {
+            primary: primary.into_box(),
+            replicas: Vec::new(),
+            selection: Default::default(),
+            ejection_time: Duration::from_secs(30),
+            checkout_timeout: Duration::from_secs(5),
+            read_your_writes: None,
+            next: AtomicUsize::new(0),
+            primary_until: Mutex::new(None),
+        }
+    }
+
+    /// Adds a replica pool used for reads.
+    pub fn replica(mut self, pool: impl ConnectionPool<D>) -> Self {
+        self.replicas.push(Replica {
+            pool: pool.into_box(),
+            ejected_until: Mutex::new(None),
+        });
+        self
+    }
+
+    /// Sets the replica selection strategy.
+    pub fn selection(mut self, selection: ReplicaSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Sets how long a failing replica is excluded from the reads.
+    pub fn ejection_time(mut self, duration: Duration) -> Self {
+        self.ejection_time = duration;
+        self
+    }
+
+    /// Sets how long a replica can take to provide a connection and answer the ping before it is
+    /// ejected.
+    pub fn checkout_timeout(mut self, duration: Duration) -> Self {
+        self.checkout_timeout = duration;
+        self
+    }
+
+    /// Routes reads to the primary for `duration` after a primary connection is acquired.
+    pub fn read_your_writes(mut self, duration: Duration) -> Self {
+        self.read_your_writes = Some(duration);
+        self
+    }
+}
+
+impl<D: Driver> RoutingPool<D> {
+    /// Returns the primary pool.
+    pub fn primary(&self) -> &(dyn ConnectionPool<D> + Send + Sync) {
+        self.primary.as_ref()
+    }
+
+    /// Returns the number of replicas that are not currently ejected.
+    pub fn available_replicas(&self) -> usize {
+        let now = Instant::now();
+        self.replicas.iter().filter(|v| v.is_available(now)).count()
+    }
+
+    fn written(&self) {
+        if let Some(duration) = self.read_your_writes
+            && let Ok(mut v) = self.primary_until.lock()
+        {
+            *v = Some(Instant::now() + duration);
+        }
+    }
+
+    fn reads_from_primary(&self, now: Instant) -> bool {
+        self.primary_until
+            .lock()
+            .map(|v| v.is_some_and(|until| now < until))
+            .unwrap_or(false)
+    }
+
+    /// Available replicas, in the order they should be tried.
+    fn candidates(&self, now: Instant) -> Vec<&Replica<D>> {
+        let len = self.replicas.len();
+        if len == 0 {
+            return Vec::new();
+        }
+        let mut result: Vec<_> = match self.selection {
+            ReplicaSelection::RoundRobin => {
+                let start = self.next.fetch_add(1, Ordering::Relaxed);
+                (0..len)
+                    .map(|i| &self.replicas[(start + i) % len])
+                    .filter(|v| v.is_available(now))
+                    .collect()
+            }
+            ReplicaSelection::LeastBusy => self
+                .replicas
+                .iter()
+                .filter(|v| v.is_available(now))
+                .collect(),
+        };
+        if self.selection == ReplicaSelection::LeastBusy {
+            result.sort_by_cached_key(|v| {
+                let status = v.pool.status();
+                status.in_use + status.waiting
+            });
+        }
+        result
+    }
+}
+
+impl<D: Driver> ConnectionPool<D> for RoutingPool<D> {
+    fn get<'s>(&'s self) -> BoxFuture<'s, Result<PooledConnection<D>>> {
+        async move {
+            let connection = self.primary.get().await?;
+            self.written();
+            Ok(connection)
+        }
+        .boxed()
+    }
+
+    fn timeout_get<'s>(&'s self, timeout: Duration) -> BoxFuture<'s, Result<PooledConnection<D>>> {
+        async move {
+            let connection = self.primary.timeout_get(timeout).await?;
+            self.written();
+            Ok(connection)
+        }
+        .boxed()
+    }
+
+    fn get_read<'s>(&'s self) -> BoxFuture<'s, Result<PooledConnection<D>>> {
+        async move {
+            let now = Instant::now();
+            if !self.reads_from_primary(now) {
+                for replica in self.candidates(now) {
+                    let checkout = async {
+                        let mut connection = replica.pool.get().await?;
+                        if let Err(e) = connection.ping().await {
+                            connection.discard();
+                            return Err(e);
+                        }
+                        Ok::<_, Error>(connection)
+                    };
+                    let result = tokio::time::timeout(self.checkout_timeout, checkout)
+                        .await
+                        .unwrap_or_else(|_| {
+                            Err(anyhow!(
+                                "Timed out after {:?} waiting for a replica connection",
+                                self.checkout_timeout
+                            ))
+                        });
+                    match result {
+                        Ok(connection) => return Ok(connection),
+                        Err(e) => {
+                            log::warn!(
+                                "Ejecting a replica for {:?} after it failed to provide a connection: {e:#}",
+                                self.ejection_time
+                            );
+                            replica.eject(Instant::now() + self.ejection_time);
+                        }
+                    }
+                }
+            }
+            self.primary.get().await
+        }
+        .boxed()
+    }
+
+    fn detach<'s>(&'s self) -> BoxFuture<'s, Result<D::Connection>> {
+        self.primary.detach()
+    }
+
+    fn resize(&self, max_size: usize) -> Result<()> {
+        self.primary.resize(max_size)?;
+        for replica in &self.replicas {
+            replica.pool.resize(max_size)?;
+        }
+        Ok(())
+    }
+
+    fn into_box(self) -> Box<dyn ConnectionPool<D> + Send + Sync>
+    where
+        Self: Sized,
+        D: 'static,
+    {
+        Box::new(self)
+    }
+
+    fn into_arc(self) -> Arc<dyn ConnectionPool<D> + Send + Sync>
+    where
+        Self: Sized,
+        D: 'static,
+    {
+        Arc::new(self)
+    }
+
+    fn close(&self) -> BoxFuture<'static, Result<()>> {
+        let closing = iter::once(&self.primary)
+            .chain(self.replicas.iter().map(|v| &v.pool))
+            .map(|v| v.close())
+            .collect::<Vec<_>>();
+        async move {
+            try_join_all(closing).await?;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn status(&self) -> PoolStatus {
+        self.replicas
+            .iter()
+            .map(|v| v.pool.status())
+            .fold(self.primary.status(), |acc, v| PoolStatus {
+                max_size: acc.max_size + v.max_size,
+                size: acc.size + v.size,
+                idle: acc.idle + v.idle,
+                in_use: acc.in_use + v.in_use,
+                waiting: acc.waiting + v.waiting,
+                acquired: acc.acquired + v.acquired,
+                timeouts: acc.timeouts + v.timeouts,
+                acquire_time: acc.acquire_time + v.acquire_time,
+                queries: acc.queries + v.queries,
+                query_time: acc.query_time + v.query_time,
+            })
+    }
+}
@@ -0,0 +1,302 @@
+use crate::{
+    ColumnDef, ColumnInfo, ColumnRef, Context, DynQuery, Entity, Executor, Fragment,
+    PrimaryKeyType, Result, SqlWriter, TableRef, Value, split_sql_statements,
+};
+use anyhow::anyhow;
+use std::fmt::{self, Display, Formatter};
+
+/// Column properties to change, see [`SqlWriter::write_alter_table_alter_column`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnChanges {
+    /// Change the type.
+    pub column_type: bool,
+    /// Change the nullability.
+    pub nullable: bool,
+    /// Set the default.
+    pub default: bool,
+}
+
+impl ColumnChanges {
+    /// Nothing to change.
+    pub fn is_empty(&self) -> bool {
+        *self == Default::default()
+    }
+}
+
+/// Single step of a schema migration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationStep {
+    /// Create the missing table.
+    CreateTable(TableRef),
+    /// Add a column declared by the entity.
+    AddColumn(ColumnRef),
+    /// Drop a column not declared by the entity.
+    DropColumn(ColumnRef),
+    /// Alter a column whose definition differs.
+    AlterColumn(ColumnRef, ColumnChanges),
+    /// Add a unique constraint.
+    AddUnique(Vec<ColumnRef>),
+    /// Add a foreign key constraint.
+    AddForeignKey(ColumnRef),
+}
+
+/// Migration step together with the SQL implementing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChange {
+    /// What changes.
+    pub step: MigrationStep,
+    /// Can lose data or fail on existing rows.
+    pub destructive: bool,
+    /// Statements to execute, empty if the driver does not support this change.
+    pub sql: String,
+}
+
+/// Differences between an entity and its table in the database, see [`Entity::diff_table`].
+///
+/// The changes are ordered: new columns first, then altered columns and constraints, dropped
+/// columns last. `Display` prints the SQL, useful for dry runs.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct SchemaDiff {
+    /// Table compared.
+    pub table: TableRef,
+    /// Changes needed to match the entity.
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Compares the definition of `E` with the `current` columns of its table (as returned by
+    /// [`Executor::describe_table`]), an empty list means the table does not exist.
+    ///
+    /// Primary key changes are not supported, composite unique constraints are only added
+    /// together with one of their columns because they are not introspected.
+    pub fn new<E: Entity, W: SqlWriter>(writer: &W, current: &[ColumnInfo]) -> Self {
+        let table = E::table();
+        let mut result = SchemaDiff {
+            table: table.clone(),
+            changes: Vec::new(),
+        };
+        if current.is_empty() {
+            let mut query = DynQuery::default();
+            writer.write_create_table::<E>(&mut query, false);
+            result.push(MigrationStep::CreateTable(table.clone()), false, query);
+            return result;
+        }
+        let find = |name: &str| current.iter().find(|v| v.name() == name);
+        let mut primary_key = E::primary_key_def()
+            .iter()
+            .map(|v| v.name())
+            .collect::<Vec<_>>();
+        let mut current_primary_key = current
+            .iter()
+            .filter(|v| v.primary_key != PrimaryKeyType::None)
+            .map(|v| v.name())
+            .collect::<Vec<_>>();
+        primary_key.sort();
+        current_primary_key.sort();
+        if primary_key != current_primary_key {
+            log::warn!(
+                "The primary key of {} differs from the entity, changing it is not supported",
+                table.full_name(".")
+            );
+        }
+        let added = E::columns()
+            .iter()
+            .filter(|v| find(v.name()).is_none())
+            .collect::<Vec<_>>();
+        for column in &added {
+            let mut query = DynQuery::default();
+            writer.write_alter_table_add_column(&mut query, table, column);
+            // NOT NULL without a default fails when the table has rows
+            let destructive = !nullable(column) && !column.default.is_set();
+            result.push(
+                MigrationStep::AddColumn(column.column_ref.clone()),
+                destructive,
+                query,
+            );
+        }
+        let added = added.into_iter().map(|v| v.name()).collect::<Vec<_>>();
+        for column in E::columns() {
+            let Some(info) = find(column.name()) else {
+                continue;
+            };
+            let changes = ColumnChanges {
+                column_type: !same_column_type(writer, column, info),
+                nullable: nullable(column) != info.nullable,
+                default: column.default.is_set() && info.default.is_none(),
+            };
+            if changes.is_empty() {
+                continue;
+            }
+            let mut query = DynQuery::default();
+            if writer.can_alter_columns() {
+                writer.write_alter_table_alter_column(&mut query, table, column, &changes);
+                if query.is_empty() && !changes.column_type {
+                    // The dialect has no such property, like NOT NULL in CQL
+                    continue;
+                }
+            }
+            // Converting the values or forbidding NULL can fail on existing rows
+            let destructive = changes.column_type || (changes.nullable && info.nullable);
+            result.push(
+                MigrationStep::AlterColumn(column.column_ref.clone(), changes),
+                destructive,
+                query,
+            );
+        }
+        let mut uniques = E::columns()
+            .iter()
+            .filter(|v| v.unique && v.primary_key != PrimaryKeyType::PrimaryKey)
+            .filter(|v| find(v.name()).is_some_and(|v| !v.unique))
+            .map(|v| vec![v])
+            .collect::<Vec<_>>();
+        uniques.extend(
+            E::unique_defs()
+                .filter(|v| v.len() > 1)
+                .map(|v| v.collect::<Vec<_>>())
+                .filter(|v| v.iter().any(|v| added.contains(&v.name()))),
+        );
+        for unique in uniques {
+            let mut query = DynQuery::default();
+            writer.write_alter_table_add_unique(&mut query, table, &unique);
+            // Fails if there are duplicates
+            result.push(
+                MigrationStep::AddUnique(unique.iter().map(|v| v.column_ref.clone()).collect()),
+                true,
+                query,
+            );
+        }
+        for column in E::columns().iter().filter(|v| {
+            v.references.is_some() && find(v.name()).is_none_or(|v| v.references.is_none())
+        }) {
+            let mut query = DynQuery::default();
+            writer.write_alter_table_add_foreign_key(&mut query, table, column);
+            // Fails if there are dangling references
+            result.push(
+                MigrationStep::AddForeignKey(column.column_ref.clone()),
+                !added.contains(&column.name()),
+                query,
+            );
+        }
+        for info in current
+            .iter()
+            .filter(|v| !E::columns().iter().any(|c| c.name() == v.name()))
+        {
+            let mut query = DynQuery::default();
+            writer.write_alter_table_drop_column(&mut query, table, info.name());
+            result.push(
+                MigrationStep::DropColumn(info.column_ref.clone()),
+                true,
+                query,
+            );
+        }
+        result
+    }
+
+    fn push(&mut self, step: MigrationStep, destructive: bool, query: DynQuery) {
+        self.changes.push(SchemaChange {
+            step,
+            destructive,
+            sql: query.into(),
+        });
+    }
+
+    /// The table already matches the entity.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Contains no destructive change.
+    pub fn is_safe(&self) -> bool {
+        self.changes.iter().all(|v| !v.destructive)
+    }
+
+    /// Changes the driver cannot express, they have no SQL.
+    pub fn unsupported(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(|v| v.sql.is_empty())
+    }
+
+    /// Removes the changes the driver cannot express, to apply the others anyway.
+    pub fn skip_unsupported(mut self) -> Self {
+        self.changes.retain(|v| !v.sql.is_empty());
+        self
+    }
+
+    /// Executes the changes, in order.
+    ///
+    /// Fails without executing anything if there are destructive changes and
+    /// `allow_destructive` is false, or if there are changes the driver cannot express (see
+    /// [`skip_unsupported`](SchemaDiff::skip_unsupported)). Pass a transaction to apply them
+    /// atomically, where the database supports DDL in transactions.
+    pub async fn apply(&self, executor: &mut impl Executor, allow_destructive: bool) -> Result<()> {
+        if !allow_destructive && !self.is_safe() {
+            let error = anyhow!(
+                "The migration of {} contains destructive changes",
+                self.table.full_name(".")
+            );
+            log::error!("{error:#}");
+            return Err(error);
+        }
+        let unsupported = self
+            .unsupported()
+            .map(|v| format!("{:?}", v.step))
+            .collect::<Vec<_>>();
+        if !unsupported.is_empty() {
+            let error = anyhow!(
+                "The migration of {} contains changes not supported by the driver: {}",
+                self.table.full_name("."),
+                unsupported.join(", ")
+            );
+            log::error!("{error:#}");
+            return Err(error);
+        }
+        for change in &self.changes {
+            if executor.accepts_multiple_statements() {
+                executor.execute(change.sql.as_str()).await?;
+            } else {
+                // Some changes need more than one statement, like a column and its index
+                for statement in split_sql_statements(&change.sql) {
+                    executor.execute(statement).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+        for change in &self.changes {
+            f.write_str(separator)?;
+            if change.destructive {
+                f.write_str("-- Destructive\n")?;
+            }
+            if change.sql.is_empty() {
+                write!(f, "-- Not supported: {:?}", change.step)?;
+            } else {
+                f.write_str(&change.sql)?;
+            }
+            separator = "\n";
+        }
+        Ok(())
+    }
+}
+
+fn nullable(column: &ColumnDef) -> bool {
+    column.nullable && column.primary_key == PrimaryKeyType::None
+}
+
+/// Compares the types by writing the declared one and parsing it back, unknown types are
+/// considered equal.
+fn same_column_type(writer: &impl SqlWriter, column: &ColumnDef, info: &ColumnInfo) -> bool {
+    let mut context = Context::new(Fragment::SqlAlterTable, false);
+    let mut query = DynQuery::default();
+    writer.write_column_overridden_type(&mut context, &mut query, column, &column.column_type);
+    if query.is_empty() {
+        writer.write_column_type(&mut context, &mut query, &column.value);
+    }
+    let declared = writer.parse_column_type(&query.as_str());
+    matches!(declared, Value::Unknown(..))
+        || matches!(info.value, Value::Unknown(..))
+        || declared.same_type(&info.value)
+}
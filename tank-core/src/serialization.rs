@@ -0,0 +1,388 @@
+use crate::{
+    DynQuery, GenericSqlWriter, Row, Value,
+    writer::{Context, Fragment, SqlWriter},
+};
+use anyhow::Context as _;
+use rust_decimal::prelude::ToPrimitive;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        self, DeserializeOwned, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+        value::{Error as DeError, MapDeserializer, SeqDeserializer},
+    },
+    forward_to_deserialize_any,
+    ser::SerializeMap,
+};
+use std::{any, collections::HashMap, fmt, mem, sync::Arc};
+
+/// Text of the value as written by the generic writer, without quotes.
+fn value_text(value: &Value) -> String {
+    let mut out = DynQuery::default();
+    GenericSqlWriter::new().write_value(&mut Context::new(Fragment::None, false), &mut out, value);
+    mem::take(out.buffer())
+}
+
+/// Serializes to the natural serde representation: numbers, strings, sequences and maps.
+///
+/// Dates, times, intervals and uuids become strings, decimals become floats (like `value_to_json`).
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            v if v.is_null() => serializer.serialize_none(),
+            Value::Boolean(Some(v), ..) => serializer.serialize_bool(*v),
+            Value::Int8(Some(v), ..) => serializer.serialize_i8(*v),
+            Value::Int16(Some(v), ..) => serializer.serialize_i16(*v),
+            Value::Int32(Some(v), ..) => serializer.serialize_i32(*v),
+            Value::Int64(Some(v), ..) => serializer.serialize_i64(*v),
+            Value::Int128(Some(v), ..) => serializer.serialize_i128(*v),
+            Value::UInt8(Some(v), ..) => serializer.serialize_u8(*v),
+            Value::UInt16(Some(v), ..) => serializer.serialize_u16(*v),
+            Value::UInt32(Some(v), ..) => serializer.serialize_u32(*v),
+            Value::UInt64(Some(v), ..) => serializer.serialize_u64(*v),
+            Value::UInt128(Some(v), ..) => serializer.serialize_u128(*v),
+            Value::Float32(Some(v), ..) => serializer.serialize_f32(*v),
+            Value::Float64(Some(v), ..) => serializer.serialize_f64(*v),
+            Value::Decimal(Some(v), ..) => match v.to_f64() {
+                Some(v) => serializer.serialize_f64(v),
+                None => serializer.collect_str(v),
+            },
+            Value::Char(Some(v), ..) => serializer.serialize_char(*v),
+            Value::Varchar(Some(v), ..) => serializer.serialize_str(v),
+            Value::Blob(Some(v), ..) => serializer.serialize_bytes(v),
+            Value::Array(Some(v), ..) => serializer.collect_seq(v.iter()),
+            Value::List(Some(v), ..) => serializer.collect_seq(v.iter()),
+            Value::Map(Some(v), ..) => serializer.collect_map(v.iter()),
+            Value::Json(Some(v), ..) => v.serialize(serializer),
+            Value::Struct(Some(v), ..) => {
+                let mut map = serializer.serialize_map(Some(v.len()))?;
+                for (k, v) in v {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
+            Value::Unknown(Some(v), ..) => serializer.serialize_str(v),
+            v => serializer.serialize_str(&value_text(v)),
+        }
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Boolean(Some(v)))
+    }
+    fn visit_i8<E: de::Error>(self, v: i8) -> Result<Value, E> {
+        Ok(Value::Int8(Some(v)))
+    }
+    fn visit_i16<E: de::Error>(self, v: i16) -> Result<Value, E> {
+        Ok(Value::Int16(Some(v)))
+    }
+    fn visit_i32<E: de::Error>(self, v: i32) -> Result<Value, E> {
+        Ok(Value::Int32(Some(v)))
+    }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Int64(Some(v)))
+    }
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Value, E> {
+        Ok(Value::Int128(Some(v)))
+    }
+    fn visit_u8<E: de::Error>(self, v: u8) -> Result<Value, E> {
+        Ok(Value::UInt8(Some(v)))
+    }
+    fn visit_u16<E: de::Error>(self, v: u16) -> Result<Value, E> {
+        Ok(Value::UInt16(Some(v)))
+    }
+    fn visit_u32<E: de::Error>(self, v: u32) -> Result<Value, E> {
+        Ok(Value::UInt32(Some(v)))
+    }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::UInt64(Some(v)))
+    }
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Value, E> {
+        Ok(Value::UInt128(Some(v)))
+    }
+    fn visit_f32<E: de::Error>(self, v: f32) -> Result<Value, E> {
+        Ok(Value::Float32(Some(v)))
+    }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Float64(Some(v)))
+    }
+    fn visit_char<E: de::Error>(self, v: char) -> Result<Value, E> {
+        Ok(Value::Char(Some(v)))
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::Varchar(Some(v.to_string().into())))
+    }
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::Varchar(Some(v.into())))
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::Blob(Some(v.into())))
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(value) = seq.next_element::<Value>()? {
+            values.push(value);
+        }
+        let element = values.first().map(Value::as_null).unwrap_or_default();
+        Ok(Value::List(Some(values), element.into()))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut entries = HashMap::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some((k, v)) = map.next_entry::<Value, Value>()? {
+            entries.insert(k, v);
+        }
+        let (key, value) = entries
+            .iter()
+            .next()
+            .map(|(k, v)| (k.as_null(), v.as_null()))
+            .unwrap_or_default();
+        Ok(Value::Map(Some(entries), key.into(), value.into()))
+    }
+}
+
+/// Deserializes from the natural serde representation, numbers keep the width given by the format.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Deserializer reading a borrowed value.
+struct ValueDeserializer<'a>(&'a Value);
+
+impl<'de> IntoDeserializer<'de, DeError> for ValueDeserializer<'de> {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        match self.0 {
+            v if v.is_null() => visitor.visit_unit(),
+            Value::Boolean(Some(v), ..) => visitor.visit_bool(*v),
+            Value::Int8(Some(v), ..) => visitor.visit_i8(*v),
+            Value::Int16(Some(v), ..) => visitor.visit_i16(*v),
+            Value::Int32(Some(v), ..) => visitor.visit_i32(*v),
+            Value::Int64(Some(v), ..) => visitor.visit_i64(*v),
+            Value::Int128(Some(v), ..) => visitor.visit_i128(*v),
+            Value::UInt8(Some(v), ..) => visitor.visit_u8(*v),
+            Value::UInt16(Some(v), ..) => visitor.visit_u16(*v),
+            Value::UInt32(Some(v), ..) => visitor.visit_u32(*v),
+            Value::UInt64(Some(v), ..) => visitor.visit_u64(*v),
+            Value::UInt128(Some(v), ..) => visitor.visit_u128(*v),
+            Value::Float32(Some(v), ..) => visitor.visit_f32(*v),
+            Value::Float64(Some(v), ..) => visitor.visit_f64(*v),
+            Value::Decimal(Some(v), ..) => match v.to_f64() {
+                Some(v) => visitor.visit_f64(v),
+                None => visitor.visit_string(v.to_string()),
+            },
+            Value::Char(Some(v), ..) => visitor.visit_char(*v),
+            Value::Varchar(Some(v), ..) => visitor.visit_str(v.as_ref()),
+            Value::Unknown(Some(v), ..) => visitor.visit_str(v),
+            Value::Blob(Some(v), ..) => visit_seq(v.iter().copied(), visitor),
+            Value::Array(Some(v), ..) => visit_seq(v.iter().map(ValueDeserializer), visitor),
+            Value::List(Some(v), ..) => visit_seq(v.iter().map(ValueDeserializer), visitor),
+            Value::Map(Some(v), ..) => visit_map(
+                v.iter()
+                    .map(|(k, v)| (ValueDeserializer(k), ValueDeserializer(v))),
+                visitor,
+            ),
+            Value::Struct(Some(v), ..) => visit_map(
+                v.iter().map(|(k, v)| (k.as_str(), ValueDeserializer(v))),
+                visitor,
+            ),
+            Value::Json(Some(v), ..) => v.deserialize_any(visitor).map_err(de::Error::custom),
+            v => visitor.visit_string(value_text(v)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.0.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        match self.0 {
+            Value::Varchar(Some(v), ..) => {
+                visitor.visit_enum(IntoDeserializer::<DeError>::into_deserializer(v.as_ref()))
+            }
+            Value::Json(Some(v), ..) => v
+                .deserialize_enum(name, variants, visitor)
+                .map_err(de::Error::custom),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn visit_seq<'de, I, V>(iter: I, visitor: V) -> Result<V::Value, DeError>
+where
+    I: Iterator,
+    I::Item: IntoDeserializer<'de, DeError>,
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(iter);
+    let result = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(result)
+}
+
+fn visit_map<'de, I, K, T, V>(iter: I, visitor: V) -> Result<V::Value, DeError>
+where
+    I: Iterator<Item = (K, T)>,
+    K: IntoDeserializer<'de, DeError>,
+    T: IntoDeserializer<'de, DeError>,
+    V: Visitor<'de>,
+{
+    let mut map = MapDeserializer::new(iter);
+    let result = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(result)
+}
+
+/// Deserializer reading a borrowed row: by label for structs and maps, by position for tuples.
+struct RowDeserializer<'a>(&'a Row);
+
+impl<'de> Deserializer<'de> for RowDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visit_map(
+            self.0
+                .into_iter()
+                .map(|(k, v)| (k.as_str(), ValueDeserializer(v))),
+            visitor,
+        )
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visit_seq(self.0.values.iter().map(ValueDeserializer), visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Serializes as a map from column label to value.
+impl Serialize for Row {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (k, v) in self {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+struct RowVisitor;
+
+impl<'de> Visitor<'de> for RowVisitor {
+    type Value = Row;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map from column label to value")
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Row, A::Error> {
+        let mut labels = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        let mut values = Vec::with_capacity(labels.capacity());
+        while let Some((k, v)) = map.next_entry::<String, Value>()? {
+            labels.push(k);
+            values.push(v);
+        }
+        Ok(Row::new(Arc::from(labels), values.into()))
+    }
+}
+
+/// Deserializes from a map from column label to value.
+impl<'de> Deserialize<'de> for Row {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(RowVisitor)
+    }
+}
+
+impl Row {
+    /// Deserializes the row into any serde type.
+    ///
+    /// Structs and maps are filled by column label, tuples and sequences by position.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> crate::Result<T> {
+        T::deserialize(RowDeserializer(self)).with_context(|| {
+            format!(
+                "Could not deserialize the row into {}",
+                any::type_name::<T>()
+            )
+        })
+    }
+}
@@ -0,0 +1,271 @@
+use crate::Value;
+
+/// Family of a type, used by `expr!` to reject operands that cannot be compared.
+#[derive(Clone, Copy, Debug)]
+pub enum TypeKind {
+    /// Not known, compatible with anything.
+    Unknown,
+    /// Boolean.
+    Boolean,
+    /// Integer, floating point and decimal numbers.
+    Numeric,
+    /// Characters and strings, also accepted by the other non numeric kinds.
+    Text,
+    /// Binary data.
+    Binary,
+    /// Date and timestamp.
+    Date,
+    /// Time of day.
+    Time,
+    /// Time interval.
+    Interval,
+    /// UUID.
+    Uuid,
+    /// Array or list of the kind.
+    List(&'static TypeKind),
+    /// Map.
+    Map,
+    /// JSON.
+    Json,
+    /// Struct.
+    Struct,
+}
+
+impl TypeKind {
+    /// Kind of the values of the same type as `value`.
+    pub const fn of(value: &Value) -> Self {
+        match value {
+            Value::Boolean(..) => Self::Boolean,
+            Value::Int8(..)
+            | Value::Int16(..)
+            | Value::Int32(..)
+            | Value::Int64(..)
+            | Value::Int128(..)
+            | Value::UInt8(..)
+            | Value::UInt16(..)
+            | Value::UInt32(..)
+            | Value::UInt64(..)
+            | Value::UInt128(..)
+            | Value::Float32(..)
+            | Value::Float64(..)
+            | Value::Decimal(..) => Self::Numeric,
+            Value::Char(..) | Value::Varchar(..) => Self::Text,
+            Value::Blob(..) => Self::Binary,
+            Value::Date(..) | Value::Timestamp(..) | Value::TimestampWithTimezone(..) => Self::Date,
+            Value::Time(..) => Self::Time,
+            Value::Interval(..) => Self::Interval,
+            Value::Uuid(..) => Self::Uuid,
+            Value::Array(..) | Value::List(..) => Self::List(&Self::Unknown),
+            Value::Map(..) => Self::Map,
+            Value::Json(..) => Self::Json,
+            Value::Struct(..) => Self::Struct,
+            Value::Null | Value::Unknown(..) => Self::Unknown,
+        }
+    }
+
+    /// Whether values of the two kinds can be compared.
+    pub const fn is_comparable(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Unknown, _) | (_, Self::Unknown) => true,
+            (Self::Numeric, Self::Numeric) | (Self::Boolean, Self::Boolean) => true,
+            (Self::Numeric | Self::Boolean, _) | (_, Self::Numeric | Self::Boolean) => false,
+            // Dates, uuids, intervals and the like can be written as strings
+            (Self::Text, _) | (_, Self::Text) => true,
+            (Self::List(l), Self::List(r)) => l.is_comparable(*r),
+            (Self::Binary, Self::Binary)
+            | (Self::Date, Self::Date)
+            | (Self::Time, Self::Time)
+            | (Self::Interval, Self::Interval)
+            | (Self::Uuid, Self::Uuid)
+            | (Self::Map, Self::Map)
+            | (Self::Json, Self::Json)
+            | (Self::Struct, Self::Struct) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the kind can be matched with `LIKE`, `REGEXP` and `GLOB`.
+    pub const fn is_text(self) -> bool {
+        matches!(self, Self::Text | Self::Unknown)
+    }
+
+    /// Whether a value of this kind can be the right side of `IN` for `element`.
+    pub const fn contains(self, element: Self) -> bool {
+        match self {
+            Self::List(v) => element.is_comparable(*v),
+            Self::Unknown => true,
+            _ => false,
+        }
+    }
+}
+
+/// Operand of `expr!` whose kind is known at compile time.
+pub trait Typed {
+    /// Kind of the operand.
+    const KIND: TypeKind;
+}
+
+/// Rust type of a field of an entity, implemented by `#[derive(Entity)]` for every column.
+///
+/// `FIELD` is the [`field_hash`] of the field name, `expr!` uses it to find the type of the
+/// columns it compares.
+#[doc(hidden)]
+pub trait FieldType<const FIELD: u64> {
+    /// Type of the field.
+    type Type;
+}
+
+/// Hash (FNV-1a) of a field name, identifying the field in [`FieldType`].
+#[doc(hidden)]
+pub const fn field_hash(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash = 0xcbf29ce484222325_u64;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    hash
+}
+
+/// Numeric literal in `expr!`.
+#[doc(hidden)]
+pub struct LitNumeric;
+impl Typed for LitNumeric {
+    const KIND: TypeKind = TypeKind::Numeric;
+}
+
+/// String literal in `expr!`.
+#[doc(hidden)]
+pub struct LitText;
+impl Typed for LitText {
+    const KIND: TypeKind = TypeKind::Text;
+}
+
+/// Boolean literal in `expr!`.
+#[doc(hidden)]
+pub struct LitBoolean;
+impl Typed for LitBoolean {
+    const KIND: TypeKind = TypeKind::Boolean;
+}
+
+/// Checks in the code generated by `expr!`, they fail the build when the operands don't match.
+#[doc(hidden)]
+pub mod check {
+    use super::{FieldType, TypeKind, Typed};
+    use crate::AsValue;
+    use std::marker::PhantomData;
+
+    /// Field `FIELD` (see [`field_hash`](super::field_hash)) of the entity `E` used as an operand.
+    ///
+    /// Its type is known only when `E` implements [`FieldType`], manual entities and constants
+    /// that are not columns are left unchecked.
+    pub struct Field<E, const FIELD: u64>(PhantomData<fn() -> E>);
+
+    impl<E, const FIELD: u64> Field<E, FIELD> {
+        pub const fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<E, const FIELD: u64> Default for Field<E, FIELD> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    /// Operand of the Rust type `T`.
+    pub struct Known<T>(PhantomData<fn() -> T>);
+
+    impl<T: AsValue> Typed for Known<T> {
+        const KIND: TypeKind = T::KIND;
+    }
+
+    /// Operand of unknown type, compatible with anything.
+    pub struct Unknown;
+
+    impl Typed for Unknown {
+        const KIND: TypeKind = TypeKind::Unknown;
+    }
+
+    /// Field whose type is known, called as `(&&field).typed()` it takes precedence over [`UnknownField`].
+    pub trait KnownField {
+        type Typed;
+        fn typed(&self) -> Self::Typed;
+    }
+
+    impl<E: FieldType<FIELD>, const FIELD: u64> KnownField for &Field<E, FIELD>
+    where
+        E::Type: AsValue,
+    {
+        type Typed = Known<E::Type>;
+        fn typed(&self) -> Self::Typed {
+            Known(PhantomData)
+        }
+    }
+
+    /// Field whose type is not known.
+    pub trait UnknownField {
+        fn typed(&self) -> Unknown;
+    }
+
+    impl<E, const FIELD: u64> UnknownField for Field<E, FIELD> {
+        fn typed(&self) -> Unknown {
+            Unknown
+        }
+    }
+
+    /// Comparison between two typed operands.
+    pub const fn comparable<L: Typed, R: Typed>(_: &L, _: &R) {
+        const {
+            assert!(
+                L::KIND.is_comparable(R::KIND),
+                "expr!: the operands of the comparison have incompatible types"
+            )
+        }
+    }
+
+    /// `LIKE`, `REGEXP` or `GLOB` on a typed operand.
+    pub const fn text<T: Typed>(_: &T) {
+        const {
+            assert!(
+                T::KIND.is_text(),
+                "expr!: LIKE, REGEXP and GLOB can only match strings"
+            )
+        }
+    }
+
+    /// Variable compared to a typed operand.
+    pub fn bind_comparable<T: Typed, V: AsValue>(_: &T, value: V) -> V {
+        const {
+            assert!(
+                T::KIND.is_comparable(V::KIND),
+                "expr!: the variable type does not match the type of the other operand"
+            )
+        }
+        value
+    }
+
+    /// Variable matched with `LIKE`, `REGEXP` or `GLOB`.
+    pub fn bind_text<V: AsValue>(value: V) -> V {
+        const {
+            assert!(
+                V::KIND.is_text(),
+                "expr!: LIKE, REGEXP and GLOB patterns must be strings"
+            )
+        }
+        value
+    }
+
+    /// Variable on the right side of `IN`.
+    pub fn bind_contains<T: Typed, V: AsValue>(_: &T, value: V) -> V {
+        const {
+            assert!(
+                V::KIND.contains(T::KIND),
+                "expr!: the variable after IN must be a collection of the type of the left operand"
+            )
+        }
+        value
+    }
+}
@@ -1,4 +1,5 @@
-use crate::{AsValue, ColumnDef, DynQuery, TableRef, Value};
+use crate::{AsValue, ColumnDef, DynQuery, ErrorContext, Result, TableRef, Value};
+use anyhow::anyhow;
 use proc_macro2::TokenStream;
 use quote::{ToTokens, TokenStreamExt, quote};
 use rust_decimal::prelude::ToPrimitive;
@@ -6,7 +7,7 @@ use serde_json::{Map, Number, Value as JsonValue};
 use std::{
     borrow::Cow,
     cmp::min,
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     ffi::{CStr, CString},
     ptr,
 };
@@ -78,13 +79,14 @@ pub fn value_to_json(v: &Value) -> Option<JsonValue> {
         Value::Map(Some(v), ..) => {
             let mut map = Map::new();
             for (k, v) in v.iter() {
-                let Ok(k) = String::try_from_value(k.clone()) else {
+                // JSON object keys must be strings
+                let Value::Varchar(Some(k), ..) = k else {
                     return None;
                 };
                 let Some(v) = value_to_json(v) else {
                     return None;
                 };
-                map.insert(k, v)?;
+                map.insert(k.to_string(), v);
             }
             JsonValue::Object(map)
         }
@@ -95,7 +97,7 @@ pub fn value_to_json(v: &Value) -> Option<JsonValue> {
                 let Some(v) = value_to_json(v) else {
                     return None;
                 };
-                map.insert(k.clone(), v)?;
+                map.insert(k.clone(), v);
             }
             JsonValue::Object(map)
         }
@@ -106,6 +108,29 @@ pub fn value_to_json(v: &Value) -> Option<JsonValue> {
     })
 }
 
+/// Fields of a composite value by name, implementing `AsValue` for structs.
+///
+/// Accepts a struct, a map with string keys or a JSON object (also encoded as text), depending on how the driver stores it.
+pub fn struct_fields(value: Value) -> Result<HashMap<String, Value>> {
+    Ok(match value {
+        Value::Struct(Some(v), ..) => v.into_iter().collect(),
+        Value::Map(Some(v), ..) => v
+            .into_iter()
+            .map(|(k, v)| Ok((String::try_from_value(k)?, v)))
+            .collect::<Result<_>>()?,
+        Value::Json(Some(JsonValue::Object(v)), ..) => {
+            v.into_iter().map(|(k, v)| (k, v.as_value())).collect()
+        }
+        Value::Varchar(Some(ref v), ..) if v.trim_start().starts_with('{') => {
+            let json = serde_json::from_str::<JsonValue>(v)
+                .with_context(|| format!("While decoding the struct from `{v}`"))?;
+            return struct_fields(Value::Json(Some(json)));
+        }
+        Value::Unknown(Some(v), ..) => return struct_fields(Value::Varchar(Some(v.into()))),
+        _ => return Err(anyhow!("Cannot convert {value:?} to a struct")),
+    })
+}
+
 /// Quote a `BTreeMap<K, V>` into tokens.
 pub fn quote_btree_map<K: ToTokens, V: ToTokens>(value: &BTreeMap<K, V>) -> TokenStream {
     let mut tokens = TokenStream::new();
@@ -221,6 +246,66 @@ pub fn consume_while<'s>(input: &mut &'s str, predicate: impl FnMut(&char) -> bo
     result
 }
 
+/// Split a SQL script into its statements, for executors not accepting multiple statements.
+///
+/// Semicolons inside quotes, dollar quotes and comments are ignored, every statement keeps its terminating
+/// semicolon and statements containing only comments are skipped.
+pub fn split_sql_statements(sql: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut content = false;
+    let mut chars = sql.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                // Doubled quotes are handled as two consecutive quoted sections
+                chars.by_ref().find(|(_, v)| *v == c);
+            }
+            '$' => {
+                // Dollar quoted body ($$ .. $$ or $tag$ .. $tag$), not a $1 placeholder
+                let rest = &sql[i + 1..];
+                if let Some(len) = rest.find('$')
+                    && !rest.starts_with(|v: char| v.is_ascii_digit())
+                    && rest[..len].chars().all(|v| v.is_alphanumeric() || v == '_')
+                {
+                    let tag = &sql[i..=i + len + 1];
+                    let body = i + tag.len();
+                    let end = sql[body..]
+                        .find(tag)
+                        .map_or(sql.len(), |v| body + v + tag.len());
+                    while chars.next_if(|(j, _)| *j < end).is_some() {}
+                }
+            }
+            '-' if chars.next_if(|(_, v)| *v == '-').is_some() => {
+                chars.by_ref().find(|(_, v)| *v == '\n');
+                continue;
+            }
+            '/' if chars.next_if(|(_, v)| *v == '*').is_some() => {
+                while let Some((_, v)) = chars.next() {
+                    if v == '*' && chars.next_if(|(_, v)| *v == '/').is_some() {
+                        break;
+                    }
+                }
+                continue;
+            }
+            ';' => {
+                if content {
+                    result.push(sql[start..=i].trim());
+                }
+                start = i + 1;
+                content = false;
+                continue;
+            }
+            _ => {}
+        }
+        content |= !c.is_whitespace();
+    }
+    if content {
+        result.push(sql[start..].trim());
+    }
+    result
+}
+
 pub fn extract_number<'s, const SIGNED: bool>(input: &mut &'s str) -> &'s str {
     let mut end = 0;
     let mut chars = input.chars().peekable();
@@ -420,7 +505,7 @@ macro_rules! take_until {
 ///
 /// This reduces boilerplate across driver implementations. The macro expands
 /// into an `impl Executor for $transaction<'c>` with forwarding methods for
-/// `prepare`, `run`, `fetch`, `execute`, and `append`.
+/// `prepare`, `run`, `fetch`, `execute`, `append`, `describe_table` and `list_tables`.
 ///
 /// Parameters:
 /// * `$driver`: concrete driver type.
@@ -488,6 +573,15 @@ macro_rules! impl_executor_transaction {
                 self.$connection.execute(query)
             }
 
+            fn do_execute_batch<'s>(
+                &'s mut self,
+                query: &'s mut ::tank_core::Query<Self::Driver>,
+                params: Vec<::tank_core::Params>,
+            ) -> impl Future<Output = ::tank_core::Result<Vec<::tank_core::RowsAffected>>> + Send
+            {
+                self.$connection.do_execute_batch(query, params)
+            }
+
             fn append<It>(
                 &mut self,
                 entities: It,
@@ -499,6 +593,20 @@ macro_rules! impl_executor_transaction {
             {
                 self.$connection.append(entities)
             }
+
+            fn describe_table(
+                &mut self,
+                table: &::tank_core::TableRef,
+            ) -> impl Future<Output = ::tank_core::Result<Vec<::tank_core::ColumnInfo>>> + Send {
+                self.$connection.describe_table(table)
+            }
+
+            fn list_tables(
+                &mut self,
+                schema: &str,
+            ) -> impl Future<Output = ::tank_core::Result<Vec<::tank_core::TableRef>>> + Send {
+                self.$connection.list_tables(schema)
+            }
         }
     }
 }
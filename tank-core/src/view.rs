@@ -0,0 +1,33 @@
+use crate::{Dataset, DynQuery, SelectQuery, SelectQueryBuilder, SqlWriter};
+
+/// View mapped by an entity, declared with `#[tank(view = ..)]`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewDef {
+    /// The rows are stored and updated by [`Entity::refresh`](crate::Entity::refresh).
+    pub materialized: bool,
+}
+
+/// Query defining a view: a select query builder or raw SQL.
+pub trait ViewQuery {
+    /// Writes the query, as the body of `CREATE VIEW`.
+    fn write_view_query(&self, writer: &impl SqlWriter, out: &mut DynQuery);
+}
+
+impl ViewQuery for &str {
+    fn write_view_query(&self, _writer: &impl SqlWriter, out: &mut DynQuery) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(self.trim());
+    }
+}
+
+impl<S, F, W, G, H, O, L> ViewQuery for SelectQueryBuilder<S, F, W, G, H, O, L>
+where
+    Self: SelectQuery<F>,
+    F: Dataset,
+{
+    fn write_view_query(&self, writer: &impl SqlWriter, out: &mut DynQuery) {
+        writer.write_select(out, self);
+    }
+}
@@ -11,15 +11,22 @@ pub enum Fragment {
     Json,
     JsonKey,
     ParameterBinding,
+    SqlAlterTable,
     SqlCommentOnColumn,
+    SqlCreateIndex,
     SqlCreateSchema,
     SqlCreateTable,
+    SqlCreateTableCheck,
+    SqlCreateTableGenerated,
     SqlCreateTablePrimaryKey,
     SqlCreateTableUnique,
+    SqlCreateType,
+    SqlCreateView,
     SqlDeleteFrom,
     SqlDeleteFromWhere,
     SqlDropSchema,
     SqlDropTable,
+    SqlDropView,
     SqlInsertInto,
     SqlInsertIntoOnConflict,
     SqlInsertIntoValues,
@@ -1,14 +1,15 @@
 use crate::{
-    Action, AsEntity, BinaryOp, BinaryOpType, ColumnDef, ColumnRef, Dataset, DynQuery, Entity,
-    Error, Expression, Fragment, Interval, IsTrue, Join, JoinType, Operand, Order, Ordered,
-    PrimaryKeyType, SelectQuery, TableRef, UnaryOp, UnaryOpType, Value, possibly_parenthesized,
-    separated_by, write_escaped, writer::Context,
+    Action, AsEntity, BinaryOp, BinaryOpType, ColumnChanges, ColumnDef, ColumnRef, Dataset,
+    DynQuery, Entity, EnumDef, Error, Expression, FieldAccess, Fragment, IndexDef, Interval,
+    IsTrue, Join, JoinType, Operand, Order, Ordered, PrimaryKeyType, SelectQuery, TableRef,
+    UnaryOp, UnaryOpType, Value, possibly_parenthesized, separated_by, write_escaped,
+    writer::Context,
 };
 use core::f64;
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Write,
-    mem,
+    iter, mem,
 };
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 use uuid::Uuid;
@@ -79,6 +80,16 @@ pub trait SqlWriter: Send {
         "."
     }
 
+    /// Prefixes of the named placeholders (`:name`, `@name`, `$name`) rewritten when preparing raw sql.
+    fn named_parameter_prefixes(&self) -> &'static [char] {
+        &[':', '@', '$']
+    }
+
+    /// True if a backslash escapes the next character inside string literals.
+    fn string_backslash_escapes(&self) -> bool {
+        false
+    }
+
     /// Determines if the current SQL context supports alias declarations.
     fn is_alias_declaration(&self, context: &mut Context) -> bool {
         match context.fragment {
@@ -214,6 +225,20 @@ pub trait SqlWriter: Send {
                 out.push(')');
             }
             Value::Json(..) => out.push_str("JSON"),
+            Value::Struct(.., fields, _) => {
+                out.push_str("STRUCT(");
+                separated_by(
+                    out,
+                    fields,
+                    |out, (name, value)| {
+                        self.write_identifier(context, out, name, true);
+                        out.push(' ');
+                        self.write_column_type(context, out, value);
+                    },
+                    ", ",
+                );
+                out.push(')');
+            }
             _ => log::error!("Unexpected tank::Value, variant {value:?} is not supported"),
         };
     }
@@ -729,6 +754,7 @@ pub trait SqlWriter: Send {
             Operand::Call(f, args) => self.write_function(context, out, f, args),
             Operand::Asterisk => drop(out.push('*')),
             Operand::QuestionMark => self.write_question_mark(context, out),
+            Operand::NamedParameter(v) => self.write_named_parameter(context, out, v),
             Operand::CurrentTimestampMs => self.write_current_timestamp_ms(context, out),
         };
     }
@@ -738,6 +764,12 @@ pub trait SqlWriter: Send {
         out.push('?');
     }
 
+    /// Write the placeholder `:name`, rewritten into the native one when the query is prepared.
+    fn write_named_parameter(&self, _context: &mut Context, out: &mut DynQuery, name: &str) {
+        out.push(':');
+        out.push_str(name);
+    }
+
     fn write_current_timestamp_ms(&self, _context: &mut Context, out: &mut DynQuery) {
         out.push_str("NOW()");
     }
@@ -829,6 +861,22 @@ pub trait SqlWriter: Send {
         }
     }
 
+    /// Render the access to a field of a composite value.
+    fn write_field_access(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &FieldAccess<&dyn Expression>,
+    ) {
+        out.push('(');
+        value.expression.write_query(self.as_dyn(), context, out);
+        out.push(')');
+        for field in value.fields {
+            out.push('.');
+            self.write_identifier(context, out, field, true);
+        }
+    }
+
     /// Render join keyword(s) for the given join type.
     fn write_join_type(&self, _context: &mut Context, out: &mut DynQuery, join_type: &JoinType) {
         out.push_str(match &join_type {
@@ -879,6 +927,13 @@ pub trait SqlWriter: Send {
         out.push_str("ROLLBACK;");
     }
 
+    /// Emit the statement acquiring the session level advisory lock `name`, waiting until it is
+    /// released by other sessions. Writes nothing if the database has no advisory locks.
+    fn write_advisory_lock(&self, _out: &mut DynQuery, _name: &str) {}
+
+    /// Emit the statement releasing the session level advisory lock `name`.
+    fn write_advisory_unlock(&self, _out: &mut DynQuery, _name: &str) {}
+
     /// Emit CREATE SCHEMA.
     fn write_create_schema<E>(&self, out: &mut DynQuery, if_not_exists: bool)
     where
@@ -929,6 +984,23 @@ pub trait SqlWriter: Send {
         let table = E::table();
         let estimated = 128 + E::columns().len() * 64 + E::primary_key_def().len() * 24;
         out.buffer().reserve(estimated);
+        let mut enums = Vec::<&EnumDef>::new();
+        for enum_def in E::columns().iter().filter_map(|c| c.enum_def) {
+            if self.is_native_enum(enum_def) && !enums.iter().any(|v| v.name == enum_def.name) {
+                self.write_create_enum_type(out, enum_def);
+                enums.push(enum_def);
+            }
+        }
+        let mut structs = Vec::<&Value>::new();
+        for column in E::columns() {
+            collect_struct_types(&column.value, &mut structs);
+        }
+        for value in structs {
+            self.write_create_struct_type(out, value);
+        }
+        for column in E::columns().iter().filter(|c| c.auto_increment) {
+            self.write_create_sequence(out, column, if_not_exists);
+        }
         if !out.is_empty() {
             out.push('\n');
         }
@@ -976,27 +1048,50 @@ pub trait SqlWriter: Send {
             out,
             foreign_keys,
             |out, column| {
-                let references = column.references.as_ref().unwrap();
-                out.push_str(",\nFOREIGN KEY (");
-                self.write_identifier(&mut context, out, &column.name(), true);
-                out.push_str(") REFERENCES ");
-                self.write_table_ref(&mut context, out, &references.table());
-                out.push('(');
-                self.write_column_ref(&mut context, out, references);
-                out.push(')');
-                if let Some(on_delete) = &column.on_delete {
-                    out.push_str(" ON DELETE ");
-                    self.write_create_table_references_action(&mut context, out, on_delete);
-                }
-                if let Some(on_update) = &column.on_update {
-                    out.push_str(" ON UPDATE ");
-                    self.write_create_table_references_action(&mut context, out, on_update);
-                }
+                out.push_str(",\n");
+                self.write_create_table_foreign_key_fragment(&mut context, out, column);
             },
             "",
         );
+        let checks = E::columns()
+            .iter()
+            .filter_map(|c| c.check)
+            .chain(E::check_defs().iter().copied());
+        for check in checks {
+            self.write_create_table_check_fragment(&mut context, out, check().as_ref());
+        }
+        for column in E::columns() {
+            if let Some(enum_def) = column.enum_def
+                && enum_def.check
+                && !self.is_native_enum(enum_def)
+            {
+                let values = enum_def
+                    .values
+                    .iter()
+                    .map(Operand::Value)
+                    .collect::<Vec<_>>();
+                let check = BinaryOp {
+                    op: BinaryOpType::In,
+                    lhs: column,
+                    rhs: Operand::LitTuple(&values),
+                };
+                self.write_create_table_check_fragment(&mut context, out, &check);
+            }
+        }
+        if let Some(function) = self.json_validation_function() {
+            for column in E::columns()
+                .iter()
+                .filter(|c| matches!(c.value, Value::Json(..)))
+            {
+                let check = Operand::Call(function, &[column]);
+                self.write_create_table_check_fragment(&mut context, out, &check);
+            }
+        }
         out.push_str(");");
         self.write_column_comments_statements::<E>(&mut context, out);
+        for index in E::indexes() {
+            self.write_create_index::<E>(out, index, if_not_exists);
+        }
     }
 
     /// Emit single column definition fragment.
@@ -1014,19 +1109,33 @@ pub trait SqlWriter: Send {
         self.write_column_overridden_type(context, out, column, &column.column_type);
         let didnt_write_type = out.len() == len;
         if didnt_write_type {
-            SqlWriter::write_column_type(self, context, out, &column.value);
-        }
-        if !column.nullable && column.primary_key == PrimaryKeyType::None {
-            out.push_str(" NOT NULL");
+            match column.enum_def {
+                Some(enum_def) if self.is_native_enum(enum_def) => {
+                    self.write_column_enum_type(context, out, enum_def);
+                }
+                _ => SqlWriter::write_column_type(self, context, out, &column.value),
+            }
         }
-        if column.default.is_set() {
-            out.push_str(" DEFAULT ");
-            column.default.write_query(self.as_dyn(), context, out);
+        let generated = column.generated.map(|v| v());
+        if let Some(generated) = &generated {
+            // Nullability and default follow from the expression
+            self.write_create_table_generated_fragment(context, out, column, generated.as_ref());
+        } else {
+            if !column.nullable && column.primary_key == PrimaryKeyType::None {
+                out.push_str(" NOT NULL");
+            }
+            if column.default.is_set() {
+                out.push_str(" DEFAULT ");
+                column.default.write_query(self.as_dyn(), context, out);
+            }
         }
         if column.primary_key == PrimaryKeyType::PrimaryKey {
             // Composite primary key will be printed elsewhere
             out.push_str(" PRIMARY KEY");
         }
+        if column.auto_increment {
+            self.write_create_table_auto_increment_fragment(context, out, column);
+        }
         if column.unique && column.primary_key != PrimaryKeyType::PrimaryKey {
             out.push_str(" UNIQUE");
         }
@@ -1035,6 +1144,39 @@ pub trait SqlWriter: Send {
         }
     }
 
+    /// Write the clause computing the value of a generated column.
+    fn write_create_table_generated_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        column: &ColumnDef,
+        generated: &dyn Expression,
+    ) {
+        out.push_str(" GENERATED ALWAYS AS (");
+        generated.write_query(
+            self.as_dyn(),
+            &mut context
+                .switch_fragment(Fragment::SqlCreateTableGenerated)
+                .current,
+            out,
+        );
+        out.push_str(if column.stored {
+            ") STORED"
+        } else {
+            ") VIRTUAL"
+        });
+    }
+
+    /// Write the clause making the database generate the value of a column.
+    fn write_create_table_auto_increment_fragment(
+        &self,
+        _context: &mut Context,
+        out: &mut DynQuery,
+        _column: &ColumnDef,
+    ) {
+        out.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+    }
+
     /// Write PRIMARY KEY constraint.
     fn write_create_table_primary_key_fragment<'a, It>(
         &self,
@@ -1065,6 +1207,51 @@ pub trait SqlWriter: Send {
         out.push(')');
     }
 
+    /// Write CHECK constraint.
+    fn write_create_table_check_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        check: &dyn Expression,
+    ) {
+        out.push_str(",\nCHECK (");
+        check.write_query(
+            self.as_dyn(),
+            &mut context
+                .switch_fragment(Fragment::SqlCreateTableCheck)
+                .current,
+            out,
+        );
+        out.push(')');
+    }
+
+    /// Write FOREIGN KEY constraint of a column.
+    fn write_create_table_foreign_key_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        column: &ColumnDef,
+    ) {
+        let Some(references) = &column.references else {
+            return;
+        };
+        out.push_str("FOREIGN KEY (");
+        self.write_identifier(context, out, column.name(), true);
+        out.push_str(") REFERENCES ");
+        self.write_table_ref(context, out, &references.table());
+        out.push('(');
+        self.write_column_ref(context, out, references);
+        out.push(')');
+        if let Some(on_delete) = &column.on_delete {
+            out.push_str(" ON DELETE ");
+            self.write_create_table_references_action(context, out, on_delete);
+        }
+        if let Some(on_update) = &column.on_update {
+            out.push_str(" ON UPDATE ");
+            self.write_create_table_references_action(context, out, on_update);
+        }
+    }
+
     /// Write referential action.
     fn write_create_table_references_action(
         &self,
@@ -1108,6 +1295,51 @@ pub trait SqlWriter: Send {
         }
     }
 
+    /// Write CREATE INDEX statement of a secondary index of the entity.
+    fn write_create_index<E>(&self, out: &mut DynQuery, index: &IndexDef, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut context = Context::new(Fragment::SqlCreateIndex, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE ");
+        if index.unique {
+            out.push_str("UNIQUE ");
+        }
+        out.push_str("INDEX ");
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_identifier(&mut context, out, index.name(), true);
+        out.push_str(" ON ");
+        self.write_table_ref(&mut context, out, E::table());
+        if !index.using.is_empty() {
+            out.push_str(" USING ");
+            out.push_str(index.using);
+        }
+        out.push_str(" (");
+        separated_by(
+            out,
+            index.columns.iter(),
+            |out, (column, order)| {
+                self.write_identifier(&mut context, out, column.name(), true);
+                if *order == Order::DESC {
+                    out.push_str(" DESC");
+                }
+            },
+            ", ",
+        );
+        out.push(')');
+        if let Some(condition) = &index.condition {
+            out.push_str(" WHERE ");
+            condition().write_query(self.as_dyn(), &mut context, out);
+        }
+        out.push(';');
+    }
+
     /// Write DROP TABLE statement.
     fn write_drop_table<E>(&self, out: &mut DynQuery, if_exists: bool)
     where
@@ -1127,6 +1359,448 @@ pub trait SqlWriter: Send {
         }
         self.write_table_ref(&mut context, out, table);
         out.push(';');
+        for column in E::columns().iter().filter(|c| c.auto_increment) {
+            self.write_drop_sequence(out, column, if_exists);
+        }
+    }
+
+    /// Write CREATE SEQUENCE backing an auto increment column, only for drivers without identity columns.
+    fn write_create_sequence(
+        &self,
+        _out: &mut DynQuery,
+        _column: &ColumnDef,
+        _if_not_exists: bool,
+    ) {
+    }
+
+    /// Write DROP SEQUENCE of an auto increment column, counterpart of [`SqlWriter::write_create_sequence`].
+    fn write_drop_sequence(&self, _out: &mut DynQuery, _column: &ColumnDef, _if_exists: bool) {}
+
+    /// Whether the enum is stored with a native enum type, otherwise the column has the type of its values.
+    fn is_native_enum(&self, _enum_def: &EnumDef) -> bool {
+        false
+    }
+
+    /// Function validating json text, used to CHECK the json columns when the driver stores them as text.
+    fn json_validation_function(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Write the native type of a column storing an enum.
+    fn write_column_enum_type(
+        &self,
+        _context: &mut Context,
+        _out: &mut DynQuery,
+        _enum_def: &EnumDef,
+    ) {
+    }
+
+    /// Write the statement creating a named native enum type, if missing, before the table using it.
+    fn write_create_enum_type(&self, _out: &mut DynQuery, _enum_def: &EnumDef) {}
+
+    /// Write the statement creating a named composite type, if missing, before the table using it.
+    ///
+    /// Called with each `Value::Struct` type having a name, the nested ones first.
+    fn write_create_struct_type(&self, _out: &mut DynQuery, _value: &Value) {}
+
+    /// Write CREATE [MATERIALIZED] VIEW for an entity mapping a view.
+    fn write_create_view<E>(&self, out: &mut DynQuery, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let Some(view) = E::view() else {
+            log::error!("The entity {} is not a view", E::table().full_name("."));
+            return;
+        };
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(if view.materialized {
+            "CREATE MATERIALIZED VIEW "
+        } else {
+            "CREATE VIEW "
+        });
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS");
+        E::write_view_query(self, out);
+        if !out.as_str().ends_with(';') {
+            out.push(';');
+        }
+    }
+
+    /// Write DROP [MATERIALIZED] VIEW.
+    fn write_drop_view<E>(&self, out: &mut DynQuery, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(if E::view().is_some_and(|v| v.materialized) {
+            "DROP MATERIALIZED VIEW "
+        } else {
+            "DROP VIEW "
+        });
+        if if_exists {
+            out.push_str("IF EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlDropView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push(';');
+    }
+
+    /// Write the statement refreshing a materialized view, nothing if not supported.
+    fn write_refresh_view<E>(&self, out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !E::view().is_some_and(|v| v.materialized) {
+            log::error!(
+                "The entity {} is not a materialized view",
+                E::table().full_name(".")
+            );
+            return;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("REFRESH MATERIALIZED VIEW ");
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push(';');
+    }
+
+    /// Write ALTER TABLE adding a column, see [`SchemaDiff`](crate::SchemaDiff).
+    fn write_alter_table_add_column(&self, out: &mut DynQuery, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        out.buffer()
+            .reserve(64 + table.name.len() + column.name().len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" ADD COLUMN ");
+        self.write_create_table_column_fragment(&mut context, out, column);
+        out.push(';');
+    }
+
+    /// Write ALTER TABLE dropping a column.
+    fn write_alter_table_drop_column(&self, out: &mut DynQuery, table: &TableRef, name: &str) {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        out.buffer().reserve(32 + table.name.len() + name.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" DROP COLUMN ");
+        self.write_identifier(&mut context, out, name, true);
+        out.push(';');
+    }
+
+    /// True if existing columns can be altered, otherwise `SchemaDiff` reports their changes as not supported.
+    fn can_alter_columns(&self) -> bool {
+        true
+    }
+
+    /// Write ALTER TABLE statements bringing an existing column in line with its definition.
+    ///
+    /// Emits one statement for each change.
+    fn write_alter_table_alter_column(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+        changes: &ColumnChanges,
+    ) where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        let alter_column = |context: &mut Context, out: &mut DynQuery| {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("ALTER TABLE ");
+            self.write_table_ref(context, out, table);
+            out.push_str(" ALTER COLUMN ");
+            self.write_identifier(context, out, column.name(), true);
+        };
+        if changes.column_type {
+            alter_column(&mut context, out);
+            out.push_str(" TYPE ");
+            self.write_alter_table_column_type(&mut context, out, column);
+            out.push(';');
+        }
+        if changes.nullable {
+            alter_column(&mut context, out);
+            if column.nullable && column.primary_key == PrimaryKeyType::None {
+                out.push_str(" DROP NOT NULL;");
+            } else {
+                out.push_str(" SET NOT NULL;");
+            }
+        }
+        if changes.default && column.default.is_set() {
+            alter_column(&mut context, out);
+            out.push_str(" SET DEFAULT ");
+            column.default.write_query(self.as_dyn(), &mut context, out);
+            out.push(';');
+        }
+    }
+
+    /// Write the type of a column being altered.
+    fn write_alter_table_column_type(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        column: &ColumnDef,
+    ) {
+        let len = out.len();
+        self.write_column_overridden_type(context, out, column, &column.column_type);
+        if out.len() == len {
+            match column.enum_def {
+                Some(enum_def) if self.is_native_enum(enum_def) => {
+                    self.write_column_enum_type(context, out, enum_def);
+                }
+                _ => SqlWriter::write_column_type(self, context, out, &column.value),
+            }
+        }
+    }
+
+    /// Write ALTER TABLE adding a unique constraint.
+    fn write_alter_table_add_unique(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        columns: &[&ColumnDef],
+    ) {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" ADD UNIQUE (");
+        separated_by(
+            out,
+            columns,
+            |out, col| {
+                self.write_identifier(
+                    &mut context
+                        .switch_fragment(Fragment::SqlCreateTableUnique)
+                        .current,
+                    out,
+                    col.name(),
+                    true,
+                );
+            },
+            ", ",
+        );
+        out.push_str(");");
+    }
+
+    /// Write CREATE UNIQUE INDEX, for dialects that cannot add unique constraints.
+    ///
+    /// The index is named after the table and the columns.
+    fn write_create_unique_index(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        columns: &[&ColumnDef],
+    ) {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let name = iter::once(table.name.as_ref())
+            .chain(columns.iter().map(|v| v.name()))
+            .chain(iter::once("key"))
+            .collect::<Vec<_>>()
+            .join("_");
+        out.push_str("CREATE UNIQUE INDEX ");
+        self.write_identifier(&mut context, out, &name, true);
+        out.push_str(" ON ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" (");
+        separated_by(
+            out,
+            columns,
+            |out, col| self.write_identifier(&mut context, out, col.name(), true),
+            ", ",
+        );
+        out.push_str(");");
+    }
+
+    /// Write ALTER TABLE adding the foreign key of a column.
+    fn write_alter_table_add_foreign_key(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+    ) {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" ADD ");
+        self.write_create_table_foreign_key_fragment(&mut context, out, column);
+        out.push(';');
+    }
+
+    /// Write a query describing the columns of a table, see [`Executor::describe_table`](crate::Executor::describe_table).
+    ///
+    /// Every row describes one column with the labels `column_name`, `data_type`, `is_nullable`,
+    /// `column_default`, `primary_key` (number of columns in the primary key containing this one,
+    /// 0 if none), `is_unique`, `referenced_schema`, `referenced_table`, `referenced_column`,
+    /// `on_delete`, `on_update` and `comment`. Missing labels are treated as NULL.
+    fn write_describe_table(&self, out: &mut DynQuery, table: &TableRef) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.buffer().reserve(1536);
+        out.push_str(
+            "SELECT c.column_name, c.data_type, c.is_nullable, c.column_default,\n\
+            (SELECT COUNT(*) FROM information_schema.key_column_usage p\n\
+            JOIN information_schema.table_constraints t ON t.constraint_schema = p.constraint_schema AND t.constraint_name = p.constraint_name\n\
+            WHERE t.constraint_type = 'PRIMARY KEY' AND t.table_schema = c.table_schema AND t.table_name = c.table_name\n\
+            AND EXISTS (SELECT 1 FROM information_schema.key_column_usage k WHERE k.constraint_schema = t.constraint_schema AND k.constraint_name = t.constraint_name AND k.column_name = c.column_name)\n\
+            ) AS primary_key,\n\
+            EXISTS (SELECT 1 FROM information_schema.table_constraints t\n\
+            JOIN information_schema.key_column_usage k ON k.constraint_schema = t.constraint_schema AND k.constraint_name = t.constraint_name\n\
+            WHERE t.constraint_type = 'UNIQUE' AND t.table_schema = c.table_schema AND t.table_name = c.table_name AND k.column_name = c.column_name\n\
+            AND (SELECT COUNT(*) FROM information_schema.key_column_usage u WHERE u.constraint_schema = t.constraint_schema AND u.constraint_name = t.constraint_name) = 1\n\
+            ) AS is_unique,\n\
+            f.table_schema AS referenced_schema, f.table_name AS referenced_table, f.column_name AS referenced_column,\n\
+            r.delete_rule AS on_delete, r.update_rule AS on_update, NULL AS comment\n\
+            FROM information_schema.columns c\n\
+            LEFT JOIN information_schema.key_column_usage k ON k.table_schema = c.table_schema AND k.table_name = c.table_name AND k.column_name = c.column_name\n\
+            AND k.constraint_name IN (SELECT constraint_name FROM information_schema.referential_constraints)\n\
+            LEFT JOIN information_schema.referential_constraints r ON r.constraint_schema = k.constraint_schema AND r.constraint_name = k.constraint_name\n\
+            LEFT JOIN information_schema.key_column_usage f ON f.constraint_schema = r.unique_constraint_schema AND f.constraint_name = r.unique_constraint_name AND f.ordinal_position = k.position_in_unique_constraint\n\
+            WHERE c.table_name = ",
+        );
+        self.write_string(&mut context, out, &table.name);
+        if !table.schema.is_empty() {
+            out.push_str(" AND c.table_schema = ");
+            self.write_string(&mut context, out, &table.schema);
+        }
+        out.push_str("\nORDER BY c.ordinal_position;");
+    }
+
+    /// Write a query listing the tables of a schema, see [`Executor::list_tables`](crate::Executor::list_tables).
+    ///
+    /// Every row is a table with the labels `table_schema` and `table_name`. An empty schema means
+    /// the default one.
+    fn write_list_tables(&self, out: &mut DynQuery, schema: &str) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.push_str(
+            "SELECT table_schema, table_name FROM information_schema.tables WHERE table_type = 'BASE TABLE'",
+        );
+        if !schema.is_empty() {
+            out.push_str(" AND table_schema = ");
+            self.write_string(&mut context, out, schema);
+        }
+        out.push_str(" ORDER BY table_name;");
+    }
+
+    /// Parses a SQL type name reported by the database (for example `NUMERIC(10,2)` or
+    /// `INTEGER[]`) into the corresponding [`Value`] type, `Value::Unknown` when not recognized.
+    fn parse_column_type(&self, name: &str) -> Value {
+        let name = name.trim().to_ascii_uppercase();
+        if let Some(inner) = name.strip_suffix("[]") {
+            return Value::List(None, self.parse_column_type(inner).into());
+        }
+        if let Some(inner) = name.strip_suffix(']')
+            && let Some((inner, len)) = inner.rsplit_once('[')
+            && let Ok(len) = len.trim().parse::<u32>()
+        {
+            return Value::Array(None, self.parse_column_type(inner).into(), len);
+        }
+        let (mut base, args) = match (name.find('('), name.rfind(')')) {
+            (Some(open), Some(close)) if open < close => (
+                format!("{} {}", &name[..open], &name[close + 1..]),
+                &name[open + 1..close],
+            ),
+            _ => (name.clone(), ""),
+        };
+        if base.trim_start().starts_with("MAP") {
+            let mut depth = 0;
+            let split = args.char_indices().find(|(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    ',' if depth == 0 => return true,
+                    _ => {}
+                }
+                false
+            });
+            if let Some((i, _)) = split {
+                return Value::Map(
+                    None,
+                    self.parse_column_type(&args[..i]).into(),
+                    self.parse_column_type(&args[i + 1..]).into(),
+                );
+            }
+        }
+        let unsigned = base.contains(" UNSIGNED");
+        base = base
+            .replace(" UNSIGNED", "")
+            .replace(" ZEROFILL", "")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let arg = |i: usize| {
+            args.split(',')
+                .nth(i)
+                .and_then(|v| v.trim().parse::<u8>().ok())
+                .unwrap_or(0)
+        };
+        match base.as_str() {
+            "BOOLEAN" | "BOOL" | "LOGICAL" => Value::Boolean(None),
+            "TINYINT" | "INT1" if unsigned => Value::UInt8(None),
+            "TINYINT" | "INT1" => Value::Int8(None),
+            "SMALLINT" | "INT2" | "SHORT" if unsigned => Value::UInt16(None),
+            "SMALLINT" | "INT2" | "SHORT" => Value::Int16(None),
+            "INTEGER" | "INT" | "INT4" | "MEDIUMINT" | "SIGNED" if unsigned => Value::UInt32(None),
+            "INTEGER" | "INT" | "INT4" | "MEDIUMINT" | "SIGNED" => Value::Int32(None),
+            "BIGINT" | "INT8" | "LONG" if unsigned => Value::UInt64(None),
+            "BIGINT" | "INT8" | "LONG" => Value::Int64(None),
+            "HUGEINT" | "INT128" => Value::Int128(None),
+            "UTINYINT" => Value::UInt8(None),
+            "USMALLINT" => Value::UInt16(None),
+            "UINTEGER" => Value::UInt32(None),
+            "UBIGINT" => Value::UInt64(None),
+            "UHUGEINT" => Value::UInt128(None),
+            "REAL" | "FLOAT4" | "FLOAT" => Value::Float32(None),
+            "DOUBLE" | "DOUBLE PRECISION" | "FLOAT8" => Value::Float64(None),
+            "DECIMAL" | "NUMERIC" | "DEC" => Value::Decimal(None, arg(0), arg(1)),
+            "CHAR" | "CHARACTER" | "BPCHAR" if arg(0) == 1 => Value::Char(None),
+            "CHAR" | "CHARACTER" | "BPCHAR" | "VARCHAR" | "CHARACTER VARYING" | "NCHAR"
+            | "NVARCHAR" | "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "STRING" | "CLOB"
+            | "NAME" | "CITEXT" | "ENUM" | "SET" => Value::Varchar(None),
+            "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" | "BYTEA" | "BINARY" | "VARBINARY"
+            | "BYTES" => Value::Blob(None),
+            "DATE" => Value::Date(None),
+            "TIME" | "TIME WITHOUT TIME ZONE" | "TIMETZ" | "TIME WITH TIME ZONE" => {
+                Value::Time(None)
+            }
+            "TIMESTAMP" | "DATETIME" | "TIMESTAMP WITHOUT TIME ZONE" => Value::Timestamp(None),
+            "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE" => Value::TimestampWithTimezone(None),
+            "UUID" => Value::Uuid(None),
+            "JSON" | "JSONB" => Value::Json(None),
+            v if v.starts_with("INTERVAL") => Value::Interval(None),
+            _ => Value::Unknown(None),
+        }
     }
 
     /// Write SELECT statement.
@@ -1216,6 +1890,8 @@ pub trait SqlWriter: Send {
     }
 
     /// Write INSERT statement.
+    ///
+    /// Generated columns and auto increment columns unset in every entity are omitted, letting the database compute them.
     fn write_insert<It>(&self, out: &mut DynQuery, entities: It, update: bool)
     where
         Self: Sized,
@@ -1228,8 +1904,40 @@ pub trait SqlWriter: Send {
         if entities.peek().is_none() {
             return;
         };
-        let cols = E::<It>::columns().len();
-        out.buffer().reserve(128 + cols * 32);
+        let columns = E::<It>::columns();
+        let rows = columns
+            .iter()
+            .any(|c| c.auto_increment || c.generated.is_some())
+            .then(|| {
+                entities
+                    .by_ref()
+                    .map(|v| v.as_entity().row_values())
+                    .collect::<Vec<_>>()
+            });
+        // Auto increment columns are left to the database only when unset in every entity
+        let auto_increment = columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                c.auto_increment
+                    && rows
+                        .as_ref()
+                        .is_some_and(|rows| rows.iter().all(|v| v[i].is_null()))
+            })
+            .collect::<Vec<_>>();
+        let omitted = columns
+            .iter()
+            .zip(&auto_increment)
+            .map(|(c, auto_increment)| *auto_increment || c.generated.is_some())
+            .collect::<Vec<_>>();
+        let inserted = || {
+            columns
+                .iter()
+                .zip(&omitted)
+                .filter(|(_, omitted)| !**omitted)
+                .map(|(c, _)| c)
+        };
+        out.buffer().reserve(128 + columns.len() * 32);
         if !out.is_empty() {
             out.push('\n');
         }
@@ -1239,7 +1947,7 @@ pub trait SqlWriter: Send {
         out.push_str(" (");
         separated_by(
             out,
-            E::<It>::columns().iter(),
+            inserted(),
             |out, col| {
                 self.write_identifier(&mut context, out, col.name(), true);
             },
@@ -1247,28 +1955,82 @@ pub trait SqlWriter: Send {
         );
         out.push_str(") VALUES");
         let mut context = context.switch_fragment(Fragment::SqlInsertIntoValues);
-        separated_by(
-            out,
-            entities,
-            |out, entity| {
-                out.push_str("\n(");
-                entity
-                    .as_entity()
-                    .write_query(self.as_dyn(), &mut context.current, out);
-                out.push(')');
-            },
-            ",",
-        );
+        if let Some(rows) = &rows {
+            separated_by(
+                out,
+                rows,
+                |out, row| {
+                    out.push_str("\n(");
+                    separated_by(
+                        out,
+                        row.iter()
+                            .zip(columns)
+                            .zip(&omitted)
+                            .filter(|(_, omitted)| !**omitted)
+                            .map(|(v, _)| v),
+                        |out, (value, column)| {
+                            if column.auto_increment && value.is_null() {
+                                // Unset in this row but set in others
+                                self.write_insert_default_value(&mut context.current, out);
+                            } else {
+                                self.write_value(&mut context.current, out, value);
+                            }
+                        },
+                        ", ",
+                    );
+                    out.push(')');
+                },
+                ",",
+            );
+        } else {
+            separated_by(
+                out,
+                entities,
+                |out, entity| {
+                    out.push_str("\n(");
+                    entity
+                        .as_entity()
+                        .write_query(self.as_dyn(), &mut context.current, out);
+                    out.push(')');
+                },
+                ",",
+            );
+        }
         if update {
-            self.write_insert_update_fragment::<E<It>>(
+            self.write_insert_update_fragment::<E<It>>(&mut context.current, out, inserted());
+        }
+        if rows.as_ref().is_some_and(|v| v.len() == 1) && auto_increment.iter().any(|v| *v) {
+            self.write_insert_returning_fragment(
                 &mut context.current,
                 out,
-                E::<It>::columns().iter(),
+                columns
+                    .iter()
+                    .zip(&auto_increment)
+                    .filter(|(_, auto_increment)| **auto_increment)
+                    .map(|(c, _)| c),
             );
         }
         out.push(';');
     }
 
+    /// Write the value letting the database generate an auto increment column, in a row of a multi row insert.
+    fn write_insert_default_value(&self, _context: &mut Context, out: &mut DynQuery) {
+        out.push_str("DEFAULT");
+    }
+
+    /// Write the clause returning the values generated for the omitted auto increment columns of a single row.
+    ///
+    /// Only needed by drivers that do not report the last inserted id on their own.
+    fn write_insert_returning_fragment<'a>(
+        &self,
+        _context: &mut Context,
+        _out: &mut DynQuery,
+        _columns: impl Iterator<Item = &'a ColumnDef>,
+    ) where
+        Self: Sized,
+    {
+    }
+
     /// Write ON CONFLICT DO UPDATE fragment for upsert.
     fn write_insert_update_fragment<'a, E>(
         &self,
@@ -1354,3 +2116,27 @@ impl SqlWriter for GenericSqlWriter {
         self
     }
 }
+
+/// Named struct types used by a column type, the nested ones before the ones containing them.
+fn collect_struct_types<'a>(value: &'a Value, result: &mut Vec<&'a Value>) {
+    match value {
+        Value::Struct(.., fields, name) => {
+            for (_, field) in fields {
+                collect_struct_types(field, result);
+            }
+            if !name.name.is_empty()
+                && !result
+                    .iter()
+                    .any(|v| matches!(v, Value::Struct(.., v) if v == name))
+            {
+                result.push(value);
+            }
+        }
+        Value::Array(.., inner, _) | Value::List(.., inner) => collect_struct_types(inner, result),
+        Value::Map(.., key, value) => {
+            collect_struct_types(key, result);
+            collect_struct_types(value, result);
+        }
+        _ => {}
+    }
+}
@@ -21,9 +21,9 @@ use std::{
     },
 };
 use tank_core::{
-    AsEntity, AsQuery, Connection, Driver, Entity, Error, ErrorContext, Executor, Query,
-    QueryResult, RawQuery, Result, Row, RowsAffected, SqlWriter, Value, as_c_string,
-    error_message_from_ptr, send_value, stream::Stream, truncate_long,
+    AsEntity, AsQuery, Connection, Driver, DynQuery, Entity, Error, ErrorContext, Executor, Params,
+    Prepared, Query, QueryResult, RawQuery, Result, Row, RowsAffected, SqlWriter, Value,
+    as_c_string, error_message_from_ptr, send_value, stream::Stream, truncate_long,
 };
 use tokio::task::spawn_blocking;
 
@@ -31,6 +31,8 @@ use tokio::task::spawn_blocking;
 /// Provides helpers to execute queries and extract results into `tank_core` types.
 pub struct DuckDBConnection {
     pub(crate) connection: CBox<duckdb_connection>,
+    /// True while a transaction started by `begin` is open.
+    pub(crate) transaction: bool,
 }
 
 impl DuckDBConnection {
@@ -65,11 +67,17 @@ impl DuckDBConnection {
                 return;
             }
             let statement_type = duckdb_result_statement_type(*result);
+            // INSERT with a RETURNING clause produces rows
+            let returning = statement_type == duckdb_statement_type_DUCKDB_STATEMENT_TYPE_INSERT
+                && duckdb_result_return_type(*result)
+                    == duckdb_result_type_DUCKDB_RESULT_TYPE_QUERY_RESULT;
             #[allow(non_upper_case_globals)]
-            if !matches!(
-                statement_type,
-                duckdb_statement_type_DUCKDB_STATEMENT_TYPE_SELECT
-            ) {
+            if !returning
+                && !matches!(
+                    statement_type,
+                    duckdb_statement_type_DUCKDB_STATEMENT_TYPE_SELECT
+                )
+            {
                 let rows_affected = duckdb_rows_changed(&mut *result);
                 send_value!(
                     tx,
@@ -150,6 +158,59 @@ impl DuckDBConnection {
         );
     }
 
+    /// Runs a statement producing no rows on the raw connection.
+    pub(crate) fn do_run_statement(connection: duckdb_connection, sql: &CStr) -> Result<()> {
+        unsafe {
+            let result: duckdb_result = mem::zeroed();
+            let mut result = CBox::new(result, |mut r| duckdb_destroy_result(&mut r));
+            if duckdb_query(connection, sql.as_ptr(), &mut *result) != duckdb_state_DuckDBSuccess {
+                return Err(Error::msg(
+                    error_message_from_ptr(&duckdb_result_error(&mut *result)).to_string(),
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    /// True when a transaction is open on the connection, including the ones begun with raw SQL.
+    pub(crate) fn do_in_transaction(connection: duckdb_connection) -> Result<bool> {
+        let transaction_id = || unsafe {
+            let result: duckdb_result = mem::zeroed();
+            let mut result = CBox::new(result, |mut r| duckdb_destroy_result(&mut r));
+            if duckdb_query(connection, c"SELECT txid_current();".as_ptr(), &mut *result)
+                != duckdb_state_DuckDBSuccess
+            {
+                return Err(Error::msg(
+                    error_message_from_ptr(&duckdb_result_error(&mut *result)).to_string(),
+                ));
+            }
+            Ok(duckdb_value_int64(&mut *result, 0, 0))
+        };
+        // In autocommit mode every statement runs in a new transaction
+        Ok(transaction_id()? == transaction_id()?)
+    }
+
+    pub(crate) fn do_run_batch(
+        prepared: &mut DuckDBPrepared,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let mut result = Vec::with_capacity(params.len());
+        for params in params {
+            params.bind(prepared)?;
+            let (tx, rx) = flume::unbounded();
+            Self::do_run_prepared(prepared.statement(), tx);
+            let mut affected = RowsAffected::default();
+            for value in rx.drain() {
+                if let QueryResult::Affected(value) = value? {
+                    affected.extend([value]);
+                }
+            }
+            result.push(affected);
+        }
+        prepared.clear_bindings()?;
+        Ok(result)
+    }
+
     pub(crate) fn extract_result(result: *mut duckdb_result, tx: Sender<Result<QueryResult>>) {
         unsafe {
             let is_streaming = duckdb_result_is_streaming(*result);
@@ -220,6 +281,7 @@ impl Debug for DuckDBConnection {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("DuckDBConnection")
             .field("connection", &self.connection)
+            .field("transaction", &self.transaction)
             .finish()
     }
 }
@@ -294,6 +356,52 @@ impl Executor for DuckDBConnection {
         }
     }
 
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<DuckDBDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let context = format!("While executing a batch of the query:\n{query}");
+        let writer = self.driver().sql_writer();
+        let [begin, commit, rollback] = [
+            SqlWriter::write_transaction_begin,
+            SqlWriter::write_transaction_commit,
+            SqlWriter::write_transaction_rollback,
+        ]
+        .map(|write| {
+            let mut query = DynQuery::default();
+            write(&writer, &mut query);
+            as_c_string(String::from(query))
+        });
+        let Query::Prepared(mut prepared) = mem::take(query) else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        let connection = AtomicPtr::new(*self.connection);
+        let (prepared, result) = spawn_blocking(move || {
+            let connection = connection.load(Ordering::Relaxed);
+            let result = Self::do_in_transaction(connection).and_then(|open| {
+                if open {
+                    return Self::do_run_batch(&mut prepared, params);
+                }
+                Self::do_run_statement(connection, &begin)?;
+                let result = Self::do_run_batch(&mut prepared, params);
+                Self::do_run_statement(
+                    connection,
+                    if result.is_ok() { &commit } else { &rollback },
+                )?;
+                result
+            });
+            (prepared, result)
+        })
+        .await?;
+        *query = prepared.into();
+        result.map_err(|e| {
+            let error = e.context(context);
+            log::error!("{error:#}");
+            error
+        })
+    }
+
     async fn append<It>(&mut self, rows: It) -> Result<RowsAffected>
     where
         It: IntoIterator + Send,
@@ -301,6 +409,17 @@ impl Executor for DuckDBConnection {
         It::Item: AsEntity,
     {
         type E<It> = <<It as IntoIterator>::Item as AsEntity>::Entity;
+        if E::<It>::columns()
+            .iter()
+            .any(|c| c.auto_increment || c.generated.is_some())
+        {
+            // The appender cannot leave the generated values to the database
+            let mut query = DynQuery::default();
+            self.driver()
+                .sql_writer()
+                .write_insert(&mut query, rows, false);
+            return self.execute(query).await;
+        }
         let connection = AtomicPtr::new(*self.connection);
         let rows = rows
             .into_iter()
@@ -393,6 +512,9 @@ impl Executor for DuckDBConnection {
                             }
                             .as_ptr(),
                         ),
+                        Value::Json(Some(ref v), ..) => {
+                            duckdb_append_varchar(*appender, as_c_string(v.to_string()).as_ptr())
+                        }
                         Value::Blob(Some(v), ..) => duckdb_append_blob(
                             *appender,
                             v.as_ptr() as *const c_void,
@@ -631,7 +753,10 @@ impl Connection for DuckDBConnection {
                 return Err(error);
             };
         };
-        Ok(DuckDBConnection { connection })
+        Ok(DuckDBConnection {
+            connection,
+            transaction: false,
+        })
     }
 
     fn begin(&mut self) -> impl Future<Output = Result<DuckDBTransaction<'_>>> + Send {
@@ -22,4 +22,8 @@ impl Driver for DuckDBDriver {
     fn sql_writer(&self) -> Self::SqlWriter {
         DuckDBSqlWriter::default()
     }
+
+    fn supports_transactional_ddl(&self) -> bool {
+        true
+    }
 }
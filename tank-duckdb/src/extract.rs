@@ -219,7 +219,24 @@ pub(crate) fn extract_value(
             } else {
                 None
             }),
-            //  DUCKDB_TYPE_DUCKDB_TYPE_ENUM =>
+            DUCKDB_TYPE_DUCKDB_TYPE_ENUM => Value::Varchar(if is_valid {
+                let index = match duckdb_enum_internal_type(logical_type) {
+                    DUCKDB_TYPE_DUCKDB_TYPE_UTINYINT => *(data as *const u8).add(row) as idx_t,
+                    DUCKDB_TYPE_DUCKDB_TYPE_USMALLINT => *(data as *const u16).add(row) as idx_t,
+                    DUCKDB_TYPE_DUCKDB_TYPE_UINTEGER => *(data as *const u32).add(row) as idx_t,
+                    _ => {
+                        let error = anyhow!("Invalid internal enum storage type");
+                        log::error!("{error:#}");
+                        return Err(error);
+                    }
+                };
+                let label = CBox::new(duckdb_enum_dictionary_value(logical_type, index), |v| {
+                    duckdb_free(v as *mut c_void)
+                });
+                Some(CStr::from_ptr(*label).to_string_lossy().into_owned().into())
+            } else {
+                None
+            }),
             DUCKDB_TYPE_DUCKDB_TYPE_LIST | DUCKDB_TYPE_DUCKDB_TYPE_ARRAY => {
                 let is_array = type_id == DUCKDB_TYPE_DUCKDB_TYPE_ARRAY;
                 let (vector, child_logical_type) = if is_array {
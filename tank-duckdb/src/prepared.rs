@@ -9,7 +9,7 @@ use std::{
     ffi::c_void,
     fmt::{self, Display},
 };
-use tank_core::{error_message_from_ptr, AsValue, Error, Prepared, Result, Value};
+use tank_core::{AsValue, Error, ParameterNames, Prepared, Result, Value, error_message_from_ptr};
 
 /// Prepared statement wrapper for DuckDB.
 ///
@@ -18,12 +18,14 @@ use tank_core::{error_message_from_ptr, AsValue, Error, Prepared, Result, Value}
 pub struct DuckDBPrepared {
     pub(crate) statement: CBox<duckdb_prepared_statement>,
     pub(crate) index: u64,
+    pub(crate) names: ParameterNames,
 }
 impl DuckDBPrepared {
     pub(crate) fn new(statement: CBox<duckdb_prepared_statement>) -> Self {
         Self {
             statement: statement.into(),
             index: 1,
+            names: Default::default(),
         }
     }
     pub(crate) fn statement(&self) -> duckdb_prepared_statement {
@@ -106,6 +108,16 @@ impl Prepared for DuckDBPrepared {
                     );
                     status
                 }
+                Value::Json(Some(v), ..) => {
+                    let v = v.to_string();
+                    let status = duckdb_bind_varchar_length(
+                        prepared,
+                        index,
+                        v.as_ptr() as *const i8,
+                        v.len() as u64,
+                    );
+                    status
+                }
                 Value::Blob(Some(v), ..) => {
                     let status = duckdb_bind_blob(
                         prepared,
@@ -158,6 +170,14 @@ impl Prepared for DuckDBPrepared {
             Ok(self)
         }
     }
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        Some(&self.names)
+    }
+    fn set_parameter_names(&mut self, names: ParameterNames) -> Result<()> {
+        // Native indexes start from 1
+        self.names = names.offset(1);
+        Ok(())
+    }
 }
 
 impl From<CBox<duckdb_prepared_statement>> for DuckDBPrepared {
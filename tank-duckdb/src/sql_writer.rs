@@ -3,7 +3,8 @@ use std::{
     fmt::Write,
 };
 use tank_core::{
-    BinaryOpType, ColumnDef, Context, DynQuery, Interval, SqlWriter, Value, separated_by,
+    BinaryOpType, ColumnChanges, ColumnDef, Context, DynQuery, Entity, EnumDef, Expression,
+    Fragment, GenericSqlWriter, IndexDef, Interval, SqlWriter, TableRef, Value, separated_by,
 };
 
 /// SQL writer for the DuckDB dialect.
@@ -12,6 +13,17 @@ use tank_core::{
 #[derive(Default)]
 pub struct DuckDBSqlWriter {}
 
+impl DuckDBSqlWriter {
+    /// Sequence generating the values of an auto increment column.
+    pub fn sequence_ref(column: &ColumnDef) -> TableRef {
+        TableRef {
+            name: format!("{}_{}_seq", column.table(), column.name()).into(),
+            schema: column.schema().to_string().into(),
+            ..Default::default()
+        }
+    }
+}
+
 impl SqlWriter for DuckDBSqlWriter {
     fn as_dyn(&self) -> &dyn SqlWriter {
         self
@@ -85,6 +97,288 @@ impl SqlWriter for DuckDBSqlWriter {
         }
     }
 
+    fn write_describe_table(&self, out: &mut DynQuery, table: &TableRef) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.buffer().reserve(1280);
+        out.push_str(
+            "SELECT c.column_name, c.data_type, c.is_nullable, c.column_default,\n\
+            COALESCE((SELECT len(p.constraint_column_names) FROM duckdb_constraints() p WHERE p.constraint_type = 'PRIMARY KEY'\n\
+            AND p.database_oid = c.database_oid AND p.table_oid = c.table_oid AND list_contains(p.constraint_column_names, c.column_name)), 0) AS primary_key,\n\
+            EXISTS (SELECT 1 FROM duckdb_constraints() u WHERE u.constraint_type = 'UNIQUE'\n\
+            AND u.database_oid = c.database_oid AND u.table_oid = c.table_oid AND u.constraint_column_names = [c.column_name])\n\
+            OR EXISTS (SELECT 1 FROM duckdb_indexes() i WHERE i.is_unique AND i.database_oid = c.database_oid AND i.table_oid = c.table_oid\n\
+            AND i.expressions IN ('[' || c.column_name || ']', '[''\"' || replace(c.column_name, '\"', '\"\"') || '\"'']')) AS is_unique,\n\
+            CASE WHEN f.referenced_table IS NOT NULL THEN c.schema_name END AS referenced_schema, f.referenced_table,\n\
+            f.referenced_column_names[list_position(f.constraint_column_names, c.column_name)] AS referenced_column,\n\
+            c.comment\n\
+            FROM duckdb_columns() c\n\
+            LEFT JOIN duckdb_constraints() f ON f.constraint_type = 'FOREIGN KEY'\n\
+            AND f.database_oid = c.database_oid AND f.table_oid = c.table_oid AND list_contains(f.constraint_column_names, c.column_name)\n\
+            WHERE c.database_name = current_database() AND c.table_name = ",
+        );
+        self.write_string(&mut context, out, &table.name);
+        out.push_str(" AND c.schema_name = COALESCE(NULLIF(");
+        self.write_string(&mut context, out, &table.schema);
+        out.push_str(", ''), current_schema())\nORDER BY c.column_index;");
+    }
+
+    fn write_list_tables(&self, out: &mut DynQuery, schema: &str) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.push_str(
+            "SELECT schema_name AS table_schema, table_name FROM duckdb_tables() \
+            WHERE database_name = current_database() AND schema_name = COALESCE(NULLIF(",
+        );
+        self.write_string(&mut context, out, schema);
+        out.push_str(", ''), current_schema()) ORDER BY table_name;");
+    }
+
+    fn write_create_table_generated_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        column: &ColumnDef,
+        generated: &dyn Expression,
+    ) {
+        if column.stored {
+            log::warn!(
+                "DuckDB does not support stored generated columns, `{}` is computed when read",
+                column.name()
+            );
+        }
+        out.push_str(" GENERATED ALWAYS AS (");
+        generated.write_query(
+            self.as_dyn(),
+            &mut context
+                .switch_fragment(Fragment::SqlCreateTableGenerated)
+                .current,
+            out,
+        );
+        out.push_str(") VIRTUAL");
+    }
+
+    fn write_create_table_auto_increment_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        column: &ColumnDef,
+    ) {
+        out.push_str(" DEFAULT nextval(");
+        self.write_string(
+            context,
+            out,
+            &Self::sequence_ref(column).full_name(self.separator()),
+        );
+        out.push(')');
+    }
+
+    fn write_create_sequence(&self, out: &mut DynQuery, column: &ColumnDef, if_not_exists: bool) {
+        let mut context = Context::new(Fragment::SqlCreateTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE SEQUENCE ");
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_table_ref(&mut context, out, &Self::sequence_ref(column));
+        out.push(';');
+    }
+
+    fn write_drop_sequence(&self, out: &mut DynQuery, column: &ColumnDef, if_exists: bool) {
+        let mut context = Context::new(Fragment::SqlDropTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("DROP SEQUENCE ");
+        if if_exists {
+            out.push_str("IF EXISTS ");
+        }
+        self.write_table_ref(&mut context, out, &Self::sequence_ref(column));
+        out.push(';');
+    }
+
+    fn is_native_enum(&self, enum_def: &EnumDef) -> bool {
+        enum_def.is_textual()
+    }
+
+    fn write_column_enum_type(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        enum_def: &EnumDef,
+    ) {
+        out.push_str("ENUM(");
+        separated_by(
+            out,
+            enum_def.values,
+            |out, value| self.write_value(context, out, value),
+            ", ",
+        );
+        out.push(')');
+    }
+
+    fn write_create_view<E>(&self, out: &mut DynQuery, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // Materialized views are tables filled by the query
+        out.push_str(if E::view().is_some_and(|v| v.materialized) {
+            "CREATE TABLE "
+        } else {
+            "CREATE VIEW "
+        });
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS");
+        E::write_view_query(self, out);
+        if !out.as_str().ends_with(';') {
+            out.push(';');
+        }
+    }
+
+    fn write_drop_view<E>(&self, out: &mut DynQuery, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(if E::view().is_some_and(|v| v.materialized) {
+            "DROP TABLE "
+        } else {
+            "DROP VIEW "
+        });
+        if if_exists {
+            out.push_str("IF EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlDropView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push(';');
+    }
+
+    fn write_refresh_view<E>(&self, out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !E::view().is_some_and(|v| v.materialized) {
+            log::error!(
+                "The entity {} is not a materialized view",
+                E::table().full_name(self.separator())
+            );
+            return;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE OR REPLACE TABLE ");
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS");
+        E::write_view_query(self, out);
+        if !out.as_str().ends_with(';') {
+            out.push(';');
+        }
+    }
+
+    fn write_create_index<E>(&self, out: &mut DynQuery, index: &IndexDef, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if index.condition.is_some() {
+            log::error!(
+                "DuckDB does not support partial indexes, cannot create {} on {}",
+                index.name(),
+                E::table().full_name(self.separator()),
+            );
+            return;
+        }
+        GenericSqlWriter::new().write_create_index::<E>(out, index, if_not_exists);
+    }
+
+    fn write_alter_table_add_column(&self, out: &mut DynQuery, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        // Added columns cannot have constraints, they are set afterwards
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" ADD COLUMN ");
+        self.write_identifier(&mut context, out, column.name(), true);
+        out.push(' ');
+        self.write_alter_table_column_type(&mut context, out, column);
+        if column.default.is_set() {
+            out.push_str(" DEFAULT ");
+            column.default.write_query(self.as_dyn(), &mut context, out);
+        }
+        out.push(';');
+        if !column.nullable {
+            let changes = ColumnChanges {
+                nullable: true,
+                ..Default::default()
+            };
+            self.write_alter_table_alter_column(out, table, column, &changes);
+        }
+        if column.unique {
+            self.write_create_unique_index(out, table, &[column]);
+        }
+    }
+
+    fn write_alter_table_add_unique(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        columns: &[&ColumnDef],
+    ) {
+        // DuckDB cannot add constraints, a unique index is equivalent
+        self.write_create_unique_index(out, table, columns);
+    }
+
+    fn write_alter_table_add_foreign_key(
+        &self,
+        _out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+    ) {
+        log::error!(
+            "DuckDB does not support adding foreign keys, the table {} must be recreated to reference {}",
+            table.full_name(self.separator()),
+            column.name(),
+        );
+    }
+
+    fn write_insert_returning_fragment<'a>(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        columns: impl Iterator<Item = &'a ColumnDef>,
+    ) where
+        Self: Sized,
+    {
+        out.push_str("\nRETURNING ");
+        separated_by(
+            out,
+            columns,
+            |out, col| {
+                self.write_identifier(context, out, col.name(), true);
+            },
+            ", ",
+        );
+    }
+
     fn write_current_timestamp_ms(&self, _context: &mut Context, out: &mut DynQuery) {
         out.push_str("epoch_ms(current_timestamp)");
     }
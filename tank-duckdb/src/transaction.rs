@@ -22,6 +22,7 @@ impl<'c> DuckDBTransaction<'c> {
             .sql_writer()
             .write_transaction_begin(&mut query);
         result.connection.execute(query).await?;
+        result.connection.transaction = true;
         Ok(result)
     }
 }
@@ -29,6 +30,7 @@ impl<'c> DuckDBTransaction<'c> {
 impl_executor_transaction!(DuckDBDriver, DuckDBTransaction<'c>, connection);
 impl<'c> Transaction<'c> for DuckDBTransaction<'c> {
     fn commit(self) -> impl Future<Output = Result<()>> + Send {
+        self.connection.transaction = false;
         let mut query = DynQuery::default();
         self.driver()
             .sql_writer()
@@ -37,6 +39,7 @@ impl<'c> Transaction<'c> for DuckDBTransaction<'c> {
     }
 
     fn rollback(self) -> impl Future<Output = Result<()>> + Send {
+        self.connection.transaction = false;
         let mut query = DynQuery::default();
         self.driver()
             .sql_writer()
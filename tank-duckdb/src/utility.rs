@@ -52,7 +52,7 @@ pub(crate) fn tank_value_to_duckdb_logical_type(v: &Value) -> CBox<duckdb_logica
                 *result = duckdb_create_logical_type(DUCKDB_TYPE_DUCKDB_TYPE_DOUBLE)
             }
             Value::Decimal(.., w, s) => *result = duckdb_create_decimal_type(*w, *s),
-            Value::Char(..) | Value::Varchar(..) => {
+            Value::Char(..) | Value::Varchar(..) | Value::Json(..) => {
                 *result = duckdb_create_logical_type(DUCKDB_TYPE_DUCKDB_TYPE_VARCHAR)
             }
             Value::Blob(..) => *result = duckdb_create_logical_type(DUCKDB_TYPE_DUCKDB_TYPE_BLOB),
@@ -138,6 +138,10 @@ pub(crate) fn tank_value_to_duckdb_value(value: &Value) -> CBox<duckdb_value> {
                     as_c_string(v.to_string()).as_ptr(),
                     v.len() as u64,
                 ),
+                Value::Json(Some(v)) => {
+                    let v = v.to_string();
+                    duckdb_create_varchar_length(as_c_string(v.as_str()).as_ptr(), v.len() as u64)
+                }
                 Value::Blob(Some(v)) => duckdb_create_blob(v.as_ptr(), v.len() as u64),
                 Value::Date(Some(v)) => duckdb_create_date(date_to_duckdb_date(v)),
                 Value::Time(Some(v)) => duckdb_create_time(time_to_duckdb_time(v)),
@@ -103,4 +103,43 @@ mod tests {
         .expect("Could not extract string");
         assert_eq!(max_memory, "800.0 MiB");
     }
+
+    #[tokio::test]
+    async fn batch_in_raw_transaction() {
+        init_logs();
+        let mut connection =
+            DuckDBConnection::connect(&DuckDBDriver::new(), "duckdb://:memory:".into())
+                .await
+                .expect("Could not open the database");
+        connection
+            .execute("CREATE TABLE numbers (value INTEGER);")
+            .await
+            .expect("Could not create the table");
+        connection
+            .execute("BEGIN TRANSACTION;")
+            .await
+            .expect("Could not begin the transaction");
+        let mut query = connection
+            .prepare("INSERT INTO numbers VALUES (?);")
+            .await
+            .expect("Could not prepare the insert");
+        let result = connection
+            .execute_batch(&mut query, [[1], [2]])
+            .await
+            .expect("Could not execute the batch");
+        assert_eq!(result.len(), 2);
+        connection
+            .execute("ROLLBACK;")
+            .await
+            .expect("The transaction must still be open after the batch");
+        let rows = connection
+            .fetch("SELECT * FROM numbers;")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Could not fetch the rows");
+        assert!(
+            rows.is_empty(),
+            "The batch must be rolled back with the transaction"
+        );
+    }
 }
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank_core::{Connection, Executor, PrimaryKeyType, TableRef, Value};
+    use tank_duckdb::{DuckDBConnection, DuckDBDriver};
+    use tank_tests::init_logs;
+
+    #[tokio::test]
+    async fn describe_table() {
+        init_logs();
+        let mut connection =
+            DuckDBConnection::connect(&DuckDBDriver::new(), "duckdb://:memory:".into())
+                .await
+                .expect("Could not open the database");
+        connection
+            .execute(indoc! {r#"
+                CREATE TABLE author (id INTEGER PRIMARY KEY, name VARCHAR NOT NULL);
+                CREATE TABLE book (
+                    isbn VARCHAR,
+                    edition SMALLINT,
+                    title VARCHAR NOT NULL UNIQUE,
+                    price DECIMAL(6,2) DEFAULT 9.99,
+                    tags VARCHAR[],
+                    author INTEGER REFERENCES author(id),
+                    PRIMARY KEY (isbn, edition)
+                );
+                COMMENT ON COLUMN book.title IS 'Full title';
+            "#})
+            .await
+            .expect("Could not create the tables");
+
+        let tables = connection
+            .list_tables("")
+            .await
+            .expect("Could not list the tables");
+        let names: Vec<_> = tables.iter().map(|v| v.name.as_ref()).collect();
+        assert_eq!(names, ["author", "book"]);
+        assert_eq!(tables[0].schema, "main");
+
+        let columns = connection
+            .describe_table(&TableRef::from("book"))
+            .await
+            .expect("Could not describe the table");
+        let names: Vec<_> = columns.iter().map(|v| v.name()).collect();
+        assert_eq!(
+            names,
+            ["isbn", "edition", "title", "price", "tags", "author"]
+        );
+
+        assert_eq!(columns[0].primary_key, PrimaryKeyType::PartOfPrimaryKey);
+        assert_eq!(columns[1].primary_key, PrimaryKeyType::PartOfPrimaryKey);
+        assert_eq!(columns[1].value, Value::Int16(None));
+
+        let title = &columns[2];
+        assert_eq!(title.primary_key, PrimaryKeyType::None);
+        assert!(title.unique);
+        assert!(!title.nullable);
+        assert_eq!(title.comment, "Full title");
+
+        let price = &columns[3];
+        assert!(price.nullable);
+        assert!(price.default.is_some());
+        assert_eq!(price.value, Value::Decimal(None, 6, 2));
+
+        assert_eq!(
+            columns[4].value,
+            Value::List(None, Value::Varchar(None).into())
+        );
+
+        let author = &columns[5];
+        let references = author.references.as_ref().expect("Missing foreign key");
+        assert_eq!(references.table, "author");
+        assert_eq!(references.name, "id");
+    }
+}
@@ -27,15 +27,49 @@ pub(crate) fn column_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             const #name: ::tank::ColumnRef = #column_ref;
         }
     });
+    let field_types = table.columns.iter().map(|column| {
+        let name = column.ident.to_string();
+        let ty = column.conversion_type.as_ref().unwrap_or(&column.ty);
+        quote! {
+            #[allow(private_interfaces)]
+            impl ::tank::FieldType<{ ::tank::field_hash(#name) }> for #struct_name {
+                type Type = #ty;
+            }
+        }
+    });
+    let flattened_declarations = table.flattened.iter().map(|column| {
+        let name = &column.ident;
+        quote! {
+            #[allow(non_upper_case_globals)]
+            const #name: ::tank::ColumnsRef;
+        }
+    });
+    let flattened_definitions = table.flattened.iter().map(|column| {
+        let name = &column.ident;
+        let ty = &column.ty;
+        let prefix = &column.prefix;
+        let (table, schema) = (&table.name, &table.schema);
+        quote! {
+            const #name: ::tank::ColumnsRef = ::tank::ColumnsRef {
+                prefix: #prefix,
+                table: #table,
+                schema: #schema,
+                field_column: <#ty as ::tank::Columns>::field_column,
+            };
+        }
+    });
     (
         trait_name.clone(),
         quote! {
             pub trait #trait_name {
                 #(#columns_fields_declarations)*
+                #(#flattened_declarations)*
             }
             impl #trait_name for #struct_name {
                 #(#columns_fields_definitions)*
+                #(#flattened_definitions)*
             }
+            #(#field_types)*
         },
     )
 }
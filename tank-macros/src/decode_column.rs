@@ -5,7 +5,7 @@ use syn::{
     Expr, ExprCall, ExprLit, ExprMethodCall, Field, Ident, Lit, LitStr, Path, Result, Type,
     custom_keyword, parenthesized,
     parse::{Parse, ParseStream},
-    parse2,
+    parse_quote, parse2,
     token::{Comma, Eq},
 };
 use tank_core::{Action, PrimaryKeyType, TypeDecoded, Value, decode_type, future::Either};
@@ -17,15 +17,22 @@ pub(crate) struct ColumnMetadata {
     pub(crate) name: String,
     pub(crate) column_type: BTreeMap<String, String>,
     pub(crate) conversion_type: Option<Type>,
+    pub(crate) json: bool,
+    pub(crate) flatten: bool,
+    pub(crate) prefix: String,
     pub(crate) value: Value,
     pub(crate) nullable: bool,
     pub(crate) default: Option<TokenStream>,
     pub(crate) primary_key: PrimaryKeyType,
     pub(crate) clustering_key: bool,
+    pub(crate) auto_increment: bool,
     pub(crate) references: Option<Either<TokenStream, (String, String)>>,
     pub(crate) on_delete: Option<Action>,
     pub(crate) on_update: Option<Action>,
     pub(crate) unique: bool,
+    pub(crate) check: Option<TokenStream>,
+    pub(crate) generated: Option<TokenStream>,
+    pub(crate) stored: bool,
     pub(crate) comment: String,
 }
 
@@ -37,15 +44,22 @@ impl Debug for ColumnMetadata {
             .field("name", &self.name)
             .field("column_type", &self.column_type)
             .field("conversion_type", &self.conversion_type)
+            .field("json", &self.json)
+            .field("flatten", &self.flatten)
+            .field("prefix", &self.prefix)
             .field("value", &self.value)
             .field("nullable", &self.nullable)
             .field("default", &self.default)
             .field("primary_key", &self.primary_key)
             .field("clustering_key", &self.clustering_key)
+            .field("auto_increment", &self.auto_increment)
             .field("references", &self.references)
             .field("on_delete", &self.on_delete)
             .field("on_update", &self.on_update)
             .field("unique", &self.unique)
+            .field("check", &self.check)
+            .field("generated", &self.generated)
+            .field("stored", &self.stored)
             .field("comment", &self.comment)
             .finish()
     }
@@ -135,15 +149,22 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
         name,
         column_type: Default::default(),
         conversion_type: Default::default(),
+        json: false,
+        flatten: false,
+        prefix: String::new(),
         value: Value::Null,
         nullable: false,
         default: None,
         primary_key: PrimaryKeyType::None,
         clustering_key: false,
+        auto_increment: false,
         references: None,
         on_delete: None,
         on_update: None,
         unique: false,
+        check: None,
+        generated: None,
+        stored: false,
         comment: String::new(),
     };
     if metadata.name.starts_with('_') {
@@ -162,6 +183,12 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
                     metadata.ignored = true;
                 } else if name == "default" {
                     metadata.default = Some(value.to_token_stream().into());
+                } else if name == "check" {
+                    metadata.check = Some(value.to_token_stream());
+                } else if name == "generated" {
+                    metadata.generated = Some(value.to_token_stream());
+                } else if name == "stored" {
+                    metadata.stored = true;
                 } else if name == "name" {
                     let Ok(v) = parse2::<LitStr>(value.clone()) else {
                         panic!("Cannot parse `name`, example: `#[tank(name = \"my_column\")]`");
@@ -175,6 +202,17 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
                     metadata.conversion_type = parse2::<Type>(value.clone())
                         .expect("Cannot parse `conversion_type`, example: `#[tank(conversion_type = crate::TypeWrap)]`")
                         .into();
+                } else if name == "json" {
+                    metadata.json = true;
+                } else if name == "flatten" {
+                    metadata.flatten = true;
+                } else if name == "prefix" {
+                    let Ok(v) = parse2::<LitStr>(value.clone()) else {
+                        panic!(
+                            "Cannot parse `prefix`, example: `#[tank(flatten, prefix = \"price_\")]`"
+                        );
+                    };
+                    metadata.prefix = v.value();
                 } else if name == "primary_key" {
                     metadata.primary_key = PrimaryKeyType::PrimaryKey;
                     metadata.nullable = false;
@@ -235,6 +273,8 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
                     }
                 } else if name == "unique" {
                     metadata.unique = true;
+                } else if name == "auto_increment" || name == "identity" {
+                    metadata.auto_increment = true;
                 } else {
                     panic!("Unknown attribute `{}` inside tank macro", name);
                 }
@@ -253,20 +293,37 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
             metadata.comment.push_str(v.value().trim());
         }
     }
-    if !metadata.ignored {
-        let TypeDecoded { value, nullable } = if let Type::Path(..) = &field.ty {
-            decode_type(&field.ty)
-        } else if let Type::Array(..) = &field.ty {
-            decode_type(&field.ty)
-        } else if let Type::Reference(v) = &field.ty {
+    if !metadata.prefix.is_empty() && !metadata.flatten {
+        panic!(
+            "Attribute `prefix` requires `flatten`, use it like: `#[tank(flatten, prefix = \"{}\")]`",
+            metadata.prefix
+        );
+    }
+    if !metadata.ignored && !metadata.flatten {
+        let TypeDecoded { value, nullable } = if let Type::Reference(v) = &field.ty {
             decode_type(&v.elem)
         } else if let Type::Ptr(v) = &field.ty {
             decode_type(&v.elem)
         } else {
-            Default::default()
+            decode_type(&field.ty)
         };
         metadata.value = value;
         metadata.nullable = nullable;
+        if metadata.json {
+            let ty = &metadata.ty;
+            metadata.value = Value::Json(None);
+            metadata.conversion_type = Some(parse_quote!(::tank::Json<#ty>));
+            // Binary json on Postgres, the plain `serde_json::Value` fields keep `JSON`
+            if !metadata
+                .column_type
+                .keys()
+                .any(|k| k == "postgres" || k == "postgresql")
+            {
+                metadata
+                    .column_type
+                    .insert("postgres".into(), "JSONB".into());
+            }
+        }
     }
     metadata
 }
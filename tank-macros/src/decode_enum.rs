@@ -0,0 +1,157 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Fields, Ident, ItemEnum, LitStr, parse::ParseBuffer};
+
+pub(crate) struct EnumMetadata {
+    pub(crate) item: ItemEnum,
+    pub(crate) name: String,
+    pub(crate) schema: String,
+    /// Variant identifier and stored label.
+    pub(crate) variants: Vec<(Ident, String)>,
+    /// Value variant and primitive type storing the discriminant, `None` when the variants are stored as labels.
+    pub(crate) integer: Option<(TokenStream, TokenStream)>,
+    pub(crate) check: bool,
+}
+
+pub(crate) fn decode_rename_all(value: &str) -> Case<'static> {
+    match value {
+        "lowercase" => Case::Flat,
+        "UPPERCASE" => Case::UpperFlat,
+        "snake_case" => Case::Snake,
+        "SCREAMING_SNAKE_CASE" => Case::UpperSnake,
+        "kebab-case" => Case::Kebab,
+        "SCREAMING-KEBAB-CASE" => Case::UpperKebab,
+        "camelCase" => Case::Camel,
+        "PascalCase" => Case::Pascal,
+        _ => panic!(
+            "Unknown `rename_all` value `{value}`, expected one of: lowercase, UPPERCASE, snake_case, SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE, camelCase, PascalCase"
+        ),
+    }
+}
+
+fn decode_repr(item: &ItemEnum) -> (TokenStream, TokenStream) {
+    let mut result = (quote!(Int32), quote!(i32));
+    for attr in item.attrs.iter().filter(|v| v.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|arg| {
+            let Some(ident) = arg.path.get_ident() else {
+                return Ok(());
+            };
+            result = match ident.to_string().as_str() {
+                "i8" => (quote!(Int8), quote!(i8)),
+                "i16" => (quote!(Int16), quote!(i16)),
+                "i32" => (quote!(Int32), quote!(i32)),
+                "i64" | "isize" => (quote!(Int64), quote!(i64)),
+                "u8" => (quote!(UInt8), quote!(u8)),
+                "u16" => (quote!(UInt16), quote!(u16)),
+                "u32" => (quote!(UInt32), quote!(u32)),
+                "u64" | "usize" => (quote!(UInt64), quote!(u64)),
+                _ => return Ok(()),
+            };
+            Ok(())
+        });
+    }
+    result
+}
+
+pub fn decode_enum(item: ItemEnum) -> EnumMetadata {
+    let mut name = item.ident.to_string().to_case(Case::Snake);
+    let mut schema = String::new();
+    let mut rename_all = None;
+    let mut integer = false;
+    let mut check = false;
+    for attr in &item.attrs {
+        let meta = &attr.meta;
+        if meta.path().is_ident("tank") {
+            let Ok(list) = meta.require_list() else {
+                panic!("Error while parsing `tank`, use it like: `#[tank(attribute = value, ..)]`",);
+            };
+            let _ = list.parse_nested_meta(|arg| {
+                if arg.path.is_ident("name") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                        panic!(
+                            "Error while parsing `name`, use it like: `#[tank(name = \"my_enum\")]`"
+                        );
+                    };
+                    name = value.value();
+                } else if arg.path.is_ident("schema") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                        panic!(
+                            "Error while parsing `schema`, use it like: `#[tank(schema = \"my_schema\")]`"
+                        );
+                    };
+                    schema = value.value();
+                } else if arg.path.is_ident("rename_all") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                        panic!(
+                            "Error while parsing `rename_all`, use it like: `#[tank(rename_all = \"snake_case\")]`"
+                        );
+                    };
+                    rename_all = Some(decode_rename_all(&value.value()));
+                } else if arg.path.is_ident("integer") {
+                    integer = true;
+                } else if arg.path.is_ident("check") {
+                    check = true;
+                } else {
+                    panic!(
+                        "Unknown attribute `{}` inside tank macro",
+                        arg.path.to_token_stream()
+                    );
+                }
+                Ok(())
+            });
+        }
+    }
+    if integer && rename_all.is_some() {
+        panic!(
+            "Attributes `integer` and `rename_all` cannot be used together, the variants are stored as their discriminant"
+        );
+    }
+    let variants = item
+        .variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                panic!(
+                    "Variant `{}` has fields, `Enum` can be derived just for enums with unit variants",
+                    variant.ident
+                );
+            }
+            let mut label = variant.ident.to_string();
+            if let Some(case) = rename_all {
+                label = label.to_case(case);
+            }
+            for attr in variant.attrs.iter().filter(|v| v.path().is_ident("tank")) {
+                let _ = attr.parse_nested_meta(|arg| {
+                    if arg.path.is_ident("name") {
+                        let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                            panic!(
+                                "Error while parsing `name`, use it like: `#[tank(name = \"my_variant\")]`"
+                            );
+                        };
+                        label = value.value();
+                    } else {
+                        panic!(
+                            "Unknown attribute `{}` inside tank macro",
+                            arg.path.to_token_stream()
+                        );
+                    }
+                    Ok(())
+                });
+            }
+            (variant.ident.clone(), label)
+        })
+        .collect::<Vec<_>>();
+    if variants.is_empty() {
+        panic!("Enum `{}` must have at least one variant", item.ident);
+    }
+    let integer = integer.then(|| decode_repr(&item));
+    EnumMetadata {
+        item,
+        name,
+        schema,
+        variants,
+        integer,
+        check,
+    }
+}
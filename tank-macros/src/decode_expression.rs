@@ -13,12 +13,173 @@ fn unwrap_group(expr: &Expr) -> &Expr {
     }
 }
 
+/// Operands checked at compile time by the comparison operators.
+#[derive(Clone, Copy)]
+enum Check {
+    None,
+    Comparable,
+    Text,
+    In,
+}
+
+/// Column operand, written `Entity::field` (constants and enum variants are uppercase).
+fn column(expr: &Expr) -> Option<&Path> {
+    match unwrap_group(expr) {
+        Expr::Path(ExprPath { path, .. }) if path.segments.len() > 1 => {
+            let mut segments = path.segments.iter().rev();
+            let column = segments.next()?.ident.to_string();
+            let entity = segments.next()?.ident.to_string();
+            let is_column = column.starts_with(|c: char| c.is_lowercase() || c == '_');
+            let is_entity = entity == "Self" || entity.starts_with(|c: char| c.is_uppercase());
+            (is_column && is_entity).then_some(path)
+        }
+        Expr::Paren(v) => column(&v.expr),
+        _ => None,
+    }
+}
+
+/// Witness of the kind of an operand known at compile time, `None` if not known.
+fn typed_operand(expr: &Expr) -> Option<TokenStream> {
+    if let Some(path) = column(expr) {
+        // Resolves to the type of the field when the entity derives it, to unknown otherwise
+        let mut entity = path.clone();
+        let field = entity.segments.pop()?.into_value().ident.to_string();
+        entity.segments.pop_punct();
+        return Some(quote! {
+            &{
+                #[allow(unused_imports)]
+                use ::tank::check::{KnownField as _, UnknownField as _};
+                (&&::tank::check::Field::<#entity, { ::tank::field_hash(#field) }>::new()).typed()
+            }
+        });
+    }
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            syn::Lit::Int(..) | syn::Lit::Float(..) => Some(quote!(&::tank::LitNumeric)),
+            syn::Lit::Str(..) | syn::Lit::Char(..) => Some(quote!(&::tank::LitText)),
+            syn::Lit::Bool(..) => Some(quote!(&::tank::LitBoolean)),
+            _ => None,
+        },
+        Expr::Unary(v) if matches!(v.op, syn::UnOp::Neg(..)) => match unwrap_group(&v.expr) {
+            Expr::Lit(ExprLit {
+                lit: syn::Lit::Int(..) | syn::Lit::Float(..),
+                ..
+            }) => Some(quote!(&::tank::LitNumeric)),
+            _ => None,
+        },
+        Expr::Paren(v) => typed_operand(&v.expr),
+        Expr::Group(v) => typed_operand(&v.expr),
+        _ => None,
+    }
+}
+
+/// Tokens of a `#variable` operand.
+fn variable(expr: &Expr) -> Option<&TokenStream> {
+    match unwrap_group(expr) {
+        Expr::Macro(ExprMacro {
+            mac: Macro { path, tokens, .. },
+            ..
+        }) if path
+            .segments
+            .iter()
+            .map(|v| v.ident.to_string())
+            .eq(["tank", "evaluated"]) =>
+        {
+            Some(tokens)
+        }
+        Expr::Paren(v) => variable(&v.expr),
+        _ => None,
+    }
+}
+
+/// Variable whose value goes through the check before the conversion.
+fn checked_variable(check: TokenStream) -> TokenStream {
+    quote! { ::tank::Operand::Variable(::tank::AsValue::as_value(#check)) }
+}
+
+/// Decode the operands of a comparison, checking that their kinds match.
+fn decode_checked(
+    check: Check,
+    lhs: &Expr,
+    rhs: &Expr,
+) -> (Vec<TokenStream>, TokenStream, TokenStream) {
+    let (lhs_typed, rhs_typed) = (typed_operand(lhs), typed_operand(rhs));
+    let mut checks = Vec::new();
+    let mut lhs_decoded = None;
+    let mut rhs_decoded = None;
+    match check {
+        Check::None => {}
+        Check::Comparable => match (&lhs_typed, &rhs_typed) {
+            (Some(l), Some(r)) => checks.push(quote!(::tank::check::comparable(#l, #r);)),
+            (Some(typed), None) => {
+                if let Some(v) = variable(rhs) {
+                    rhs_decoded = Some(checked_variable(
+                        quote!(::tank::check::bind_comparable(#typed, #v)),
+                    ));
+                }
+            }
+            (None, Some(typed)) => {
+                if let Some(v) = variable(lhs) {
+                    lhs_decoded = Some(checked_variable(
+                        quote!(::tank::check::bind_comparable(#typed, #v)),
+                    ));
+                }
+            }
+            (None, None) => {}
+        },
+        Check::Text => {
+            for typed in lhs_typed.iter().chain(&rhs_typed) {
+                checks.push(quote!(::tank::check::text(#typed);));
+            }
+            if let Some(v) = variable(rhs) {
+                rhs_decoded = Some(checked_variable(quote!(::tank::check::bind_text(#v))));
+            }
+        }
+        Check::In => {
+            if let Some(typed) = &lhs_typed {
+                let (elems, list) = match unwrap_group(rhs) {
+                    Expr::Array(v) => (Some(&v.elems), quote!(LitList)),
+                    Expr::Tuple(v) => (Some(&v.elems), quote!(LitTuple)),
+                    _ => (None, Default::default()),
+                };
+                if let Some(elems) = elems {
+                    let elems = elems
+                        .iter()
+                        .map(|elem| {
+                            if let Some(elem_typed) = typed_operand(elem) {
+                                checks
+                                    .push(quote!(::tank::check::comparable(#typed, #elem_typed);));
+                            } else if let Some(v) = variable(elem) {
+                                return checked_variable(
+                                    quote!(::tank::check::bind_comparable(#typed, #v)),
+                                );
+                            }
+                            decode_expression(elem)
+                        })
+                        .collect::<Punctuated<_, Comma>>();
+                    rhs_decoded = Some(quote! { ::tank::Operand::#list(&[#elems]) });
+                } else if let Some(v) = variable(rhs) {
+                    rhs_decoded = Some(checked_variable(
+                        quote!(::tank::check::bind_contains(#typed, #v)),
+                    ));
+                }
+            }
+        }
+    }
+    (
+        checks,
+        lhs_decoded.unwrap_or_else(|| decode_expression(lhs)),
+        rhs_decoded.unwrap_or_else(|| decode_expression(rhs)),
+    )
+}
+
 pub fn decode_expression(expr: &Expr) -> TokenStream {
     match expr {
         Expr::Binary(expr_binary) => {
             let lhs = expr_binary.left.as_ref();
             let mut rhs = expr_binary.right.as_ref();
             let op = expr_binary.op;
+            let mut check = Check::None;
             let op = match expr_binary.op {
                 BinOp::Add(..) => quote! { ::tank::BinaryOpType::Addition },
                 BinOp::Sub(..) => quote! { ::tank::BinaryOpType::Subtraction },
@@ -32,6 +193,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                 BinOp::Shl(..) => quote! { ::tank::BinaryOpType::ShiftLeft },
                 BinOp::Shr(..) => quote! { ::tank::BinaryOpType::ShiftRight },
                 BinOp::Eq(..) | BinOp::Ne(..) => {
+                    check = Check::Comparable;
                     let mut result = match op {
                         BinOp::Eq(..) => quote! { ::tank::BinaryOpType::Equal },
                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotEqual },
@@ -50,6 +212,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                                     .ident;
                                 if identifier == "IN" {
                                     rhs = &cast.expr;
+                                    check = Check::In;
                                     result = match op {
                                         BinOp::Eq(..) => quote! { ::tank::BinaryOpType::In },
                                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotIn },
@@ -57,6 +220,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                                     }
                                 } else if identifier == "IS" {
                                     rhs = &cast.expr;
+                                    check = Check::None;
                                     result = match op {
                                         BinOp::Eq(..) => quote! { ::tank::BinaryOpType::Is },
                                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::IsNot },
@@ -64,6 +228,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                                     }
                                 } else if identifier == "LIKE" {
                                     rhs = &cast.expr;
+                                    check = Check::Text;
                                     result = match op {
                                         BinOp::Eq(..) => quote! { ::tank::BinaryOpType::Like },
                                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotLike },
@@ -71,6 +236,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                                     }
                                 } else if identifier == "REGEXP" {
                                     rhs = &cast.expr;
+                                    check = Check::Text;
                                     result = match op {
                                         BinOp::Eq(..) => quote! { ::tank::BinaryOpType::Regexp },
                                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotRegexp },
@@ -78,6 +244,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                                     }
                                 } else if identifier == "GLOB" {
                                     rhs = &cast.expr;
+                                    check = Check::Text;
                                     result = match op {
                                         BinOp::Eq(..) => quote! { ::tank::BinaryOpType::Glob },
                                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotGlob },
@@ -92,6 +259,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                     }) = expr_binary.right.as_ref()
                     {
                         if segments.iter().map(|v| &v.ident).eq(["NULL"].iter()) {
+                            check = Check::None;
                             result = match op {
                                 BinOp::Eq(..) => quote! { ::tank::BinaryOpType::Is },
                                 BinOp::Ne(..) => quote! { ::tank::BinaryOpType::IsNot },
@@ -101,14 +269,24 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                     }
                     result
                 }
-                BinOp::Lt(..) => quote! { ::tank::BinaryOpType::Less },
-                BinOp::Le(..) => quote! { ::tank::BinaryOpType::LessEqual },
-                BinOp::Ge(..) => quote! { ::tank::BinaryOpType::GreaterEqual },
-                BinOp::Gt(..) => quote! { ::tank::BinaryOpType::Greater },
+                BinOp::Lt(..) | BinOp::Le(..) | BinOp::Ge(..) | BinOp::Gt(..) => {
+                    check = Check::Comparable;
+                    match op {
+                        BinOp::Lt(..) => quote! { ::tank::BinaryOpType::Less },
+                        BinOp::Le(..) => quote! { ::tank::BinaryOpType::LessEqual },
+                        BinOp::Ge(..) => quote! { ::tank::BinaryOpType::GreaterEqual },
+                        BinOp::Gt(..) => quote! { ::tank::BinaryOpType::Greater },
+                        _ => unreachable!(),
+                    }
+                }
                 _ => panic!("Unexpected binary operator"),
             };
-            let lhs = decode_expression(lhs);
-            let rhs = decode_expression(rhs);
+            let (checks, lhs, rhs) = decode_checked(check, lhs, rhs);
+            let op = if checks.is_empty() {
+                op
+            } else {
+                quote! {{ #(#checks)* #op }}
+            };
             quote! {
                 ::tank::BinaryOp {
                     op: #op,
@@ -233,6 +411,13 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
             .into_iter())
             {
                 quote! { ::tank::Operand::QuestionMark }
+            } else if path
+                .segments
+                .iter()
+                .map(|v| v.ident.to_string())
+                .eq(["tank", "named_parameter"])
+            {
+                quote! { ::tank::Operand::NamedParameter(#tokens) }
             } else if path.segments.iter().map(|v| v.ident.to_string()).eq([
                 "tank",
                 "current_timestamp_ms",
@@ -279,6 +464,13 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                         }
                         current = &field.base;
                     }
+                    Expr::Path(path) if path.path.segments.len() > 1 => {
+                        // Fields of a column like `User::address.city` or flattened `Order::price.amount`
+                        let fields = segments.iter().rev();
+                        return quote! {
+                            ::tank::AccessFields::access_fields(#path, &[#(#fields),*])
+                        };
+                    }
                     Expr::Path(path) => {
                         for segment in path.path.segments.iter().rev() {
                             segments.push(segment.ident.to_string());
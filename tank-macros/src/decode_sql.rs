@@ -0,0 +1,144 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use syn::{
+    Error, Expr, Ident, LitStr, Result, Token,
+    parse::{Parse, ParseStream},
+    parse_str,
+};
+
+pub(crate) struct SqlParsed(pub(crate) TokenStream);
+
+/// Placeholder of the template: `{value}`, `{#list}` or `{ident:column}`.
+enum Placeholder {
+    Value(Expr),
+    List(Expr),
+    Identifier(Expr),
+}
+
+fn split_template(template: &LitStr) -> Result<Vec<(String, Option<String>)>> {
+    let value = template.value();
+    let mut result = Vec::new();
+    let mut text = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                // Braces of the expression itself are balanced
+                let mut depth = 1;
+                let mut placeholder = String::new();
+                for c in chars.by_ref() {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                    placeholder.push(c);
+                }
+                if depth != 0 {
+                    return Err(Error::new(
+                        template.span(),
+                        "Unclosed `{` in the sql, use `{{` to write a brace",
+                    ));
+                }
+                result.push((std::mem::take(&mut text), Some(placeholder)));
+            }
+            '}' => {
+                return Err(Error::new(
+                    template.span(),
+                    "Unexpected `}` in the sql, use `}}` to write a brace",
+                ));
+            }
+            _ => text.push(c),
+        }
+    }
+    result.push((text, None));
+    Ok(result)
+}
+
+fn decode_placeholder(
+    template: &LitStr,
+    placeholder: &str,
+    args: &HashMap<String, Expr>,
+) -> Result<Placeholder> {
+    let parse = |v: &str| {
+        let v = v.trim();
+        if let Some(arg) = args.get(v) {
+            return Ok(arg.clone());
+        }
+        if v.is_empty() {
+            return Err(Error::new(
+                template.span(),
+                "Empty `{}` in the sql, the placeholder must contain an expression like `{name}`",
+            ));
+        }
+        parse_str::<Expr>(v).map_err(|e| {
+            Error::new(
+                template.span(),
+                format!("Invalid expression `{v}` in the sql: {e}"),
+            )
+        })
+    };
+    let placeholder = placeholder.trim();
+    Ok(if let Some(v) = placeholder.strip_prefix('#') {
+        Placeholder::List(parse(v)?)
+    } else if let Some(v) = placeholder
+        .strip_prefix("ident")
+        .and_then(|v| v.trim_start().strip_prefix(':'))
+        .filter(|v| !v.starts_with(':'))
+    {
+        Placeholder::Identifier(parse(v)?)
+    } else {
+        Placeholder::Value(parse(placeholder)?)
+    })
+}
+
+impl Parse for SqlParsed {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let template = input.parse::<LitStr>()?;
+        let mut args = HashMap::new();
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<Expr>()?;
+            args.insert(name.to_string(), value);
+        }
+        if !input.is_empty() {
+            return Err(input.error("Expected named arguments like `name = value` after the sql"));
+        }
+        let mut parts = Vec::new();
+        for (text, placeholder) in split_template(&template)? {
+            if !text.is_empty() {
+                parts.push(quote!(::tank::SqlPart::Text(::std::borrow::Cow::Borrowed(#text))));
+            }
+            let Some(placeholder) = placeholder else {
+                continue;
+            };
+            parts.push(match decode_placeholder(&template, &placeholder, &args)? {
+                Placeholder::Value(v) => {
+                    quote!(::tank::SqlPart::Value(::tank::AsValue::as_value(#v)))
+                }
+                Placeholder::List(v) => quote! {
+                    ::tank::SqlPart::List(
+                        ::std::iter::IntoIterator::into_iter(#v)
+                            .map(::tank::AsValue::as_value)
+                            .collect(),
+                    )
+                },
+                Placeholder::Identifier(v) => quote!(::tank::IntoIdentifier::into_identifier(#v)),
+            });
+        }
+        Ok(SqlParsed(quote!(::tank::Sql::new(vec![#(#parts),*]))))
+    }
+}
@@ -0,0 +1,101 @@
+use crate::decode_enum::decode_rename_all;
+use convert_case::{Case, Casing};
+use quote::ToTokens;
+use syn::{Fields, Ident, ItemStruct, LitStr, Type, parse::ParseBuffer};
+
+pub(crate) struct StructMetadata {
+    pub(crate) item: ItemStruct,
+    pub(crate) name: String,
+    pub(crate) schema: String,
+    /// Field identifier, type and stored name.
+    pub(crate) fields: Vec<(Ident, Type, String)>,
+}
+
+pub fn decode_struct(item: ItemStruct) -> StructMetadata {
+    let mut name = item.ident.to_string().to_case(Case::Snake);
+    let mut schema = String::new();
+    let mut rename_all = None;
+    for attr in &item.attrs {
+        let meta = &attr.meta;
+        if meta.path().is_ident("tank") {
+            let Ok(list) = meta.require_list() else {
+                panic!("Error while parsing `tank`, use it like: `#[tank(attribute = value, ..)]`",);
+            };
+            let _ = list.parse_nested_meta(|arg| {
+                if arg.path.is_ident("name") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                        panic!(
+                            "Error while parsing `name`, use it like: `#[tank(name = \"my_type\")]`"
+                        );
+                    };
+                    name = value.value();
+                } else if arg.path.is_ident("schema") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                        panic!(
+                            "Error while parsing `schema`, use it like: `#[tank(schema = \"my_schema\")]`"
+                        );
+                    };
+                    schema = value.value();
+                } else if arg.path.is_ident("rename_all") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                        panic!(
+                            "Error while parsing `rename_all`, use it like: `#[tank(rename_all = \"camelCase\")]`"
+                        );
+                    };
+                    rename_all = Some(decode_rename_all(&value.value()));
+                } else {
+                    panic!(
+                        "Unknown attribute `{}` inside tank macro",
+                        arg.path.to_token_stream()
+                    );
+                }
+                Ok(())
+            });
+        }
+    }
+    let Fields::Named(named) = &item.fields else {
+        panic!(
+            "Struct `{}` must have named fields, `Struct` can be derived just for structs like `struct Address {{ city: String }}`",
+            item.ident
+        );
+    };
+    let fields = named
+        .named
+        .iter()
+        .map(|field| {
+            let ident = field.ident.clone().expect("Named fields have an identifier");
+            let mut label = ident.to_string();
+            if let Some(case) = rename_all {
+                label = label.to_case(case);
+            }
+            for attr in field.attrs.iter().filter(|v| v.path().is_ident("tank")) {
+                let _ = attr.parse_nested_meta(|arg| {
+                    if arg.path.is_ident("name") {
+                        let Ok(value) = arg.value().and_then(ParseBuffer::parse::<LitStr>) else {
+                            panic!(
+                                "Error while parsing `name`, use it like: `#[tank(name = \"my_field\")]`"
+                            );
+                        };
+                        label = value.value();
+                    } else {
+                        panic!(
+                            "Unknown attribute `{}` inside tank macro",
+                            arg.path.to_token_stream()
+                        );
+                    }
+                    Ok(())
+                });
+            }
+            (ident, field.ty.clone(), label)
+        })
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        panic!("Struct `{}` must have at least one field", item.ident);
+    }
+    StructMetadata {
+        item,
+        name,
+        schema,
+        fields,
+    }
+}
@@ -3,18 +3,82 @@ use crate::decode_column::ColumnMetadata;
 use convert_case::{Case, Casing};
 use proc_macro2::Span;
 use quote::ToTokens;
-use std::convert::identity;
+use std::{convert::identity, iter};
 use syn::spanned::Spanned;
-use syn::{Error, Expr, ExprLit, ExprPath, ItemStruct, Lit, LitStr, Result, parse::ParseBuffer};
-use tank_core::{PrimaryKeyType, matches_path};
+use syn::{
+    Error, Expr, ExprLit, ExprPath, Ident, ItemStruct, Lit, LitStr, Result, Token, parenthesized,
+    parse::{ParseBuffer, ParseStream},
+    token::Paren,
+};
+use tank_core::{Order, PrimaryKeyType, matches_path};
 
 pub(crate) struct TableMetadata {
     pub(crate) columns: Vec<ColumnMetadata>,
+    /// Fields marked `#[tank(flatten)]`, their columns follow the other ones.
+    pub(crate) flattened: Vec<ColumnMetadata>,
     pub(crate) name: String,
     pub(crate) item: ItemStruct,
     pub(crate) schema: String,
     pub(crate) primary_key: Vec<usize>,
     pub(crate) unique: Vec<Vec<usize>>,
+    pub(crate) checks: Vec<Expr>,
+    pub(crate) indexes: Vec<IndexMetadata>,
+    pub(crate) view: Option<Expr>,
+    pub(crate) materialized: bool,
+}
+
+pub(crate) struct IndexMetadata {
+    pub(crate) name: String,
+    pub(crate) columns: Vec<(usize, Order)>,
+    pub(crate) unique: bool,
+    pub(crate) condition: Option<Expr>,
+    pub(crate) using: String,
+}
+
+fn decode_index_columns(
+    item: &ItemStruct,
+    input: ParseStream,
+    columns: &[ColumnMetadata],
+) -> Result<Vec<(usize, Order)>> {
+    let parse_column = |input: ParseStream| -> Result<(usize, Order)> {
+        let column = if input.peek(LitStr) {
+            Expr::Lit(ExprLit {
+                attrs: Default::default(),
+                lit: Lit::Str(input.parse()?),
+            })
+        } else {
+            Expr::Path(ExprPath {
+                attrs: Default::default(),
+                qself: None,
+                path: input.parse()?,
+            })
+        };
+        let column = decode_set_columns(item, column, columns.iter())?[0];
+        let mut order = Order::ASC;
+        if input.peek(Ident) {
+            let ident = input.parse::<Ident>()?;
+            if ident.to_string().eq_ignore_ascii_case("desc") {
+                order = Order::DESC;
+            } else if !ident.to_string().eq_ignore_ascii_case("asc") {
+                return Err(Error::new(ident.span(), "Expected either `asc` or `desc`"));
+            }
+        }
+        Ok((column, order))
+    };
+    if input.peek(Paren) {
+        let content;
+        parenthesized!(content in input);
+        let mut result = Vec::new();
+        while !content.is_empty() {
+            result.push(parse_column(&content)?);
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
+        Ok(result)
+    } else {
+        Ok(vec![parse_column(input)?])
+    }
 }
 
 fn decode_set_columns<'a, I: Iterator<Item = &'a ColumnMetadata> + Clone>(
@@ -72,12 +136,12 @@ fn decode_set_columns<'a, I: Iterator<Item = &'a ColumnMetadata> + Clone>(
 }
 
 pub fn decode_table(item: ItemStruct) -> TableMetadata {
-    let mut columns: Vec<_> = item
+    let (flattened, mut columns): (Vec<_>, Vec<_>) = item
         .fields
         .iter()
         .map(decode_column)
         .filter(|c| !c.ignored)
-        .collect();
+        .partition(|c| c.flatten);
     let mut name = item.ident.to_string().to_case(Case::Snake);
     let mut schema = String::new();
     let mut primary_key: Vec<_> = columns
@@ -92,6 +156,10 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
         })
         .collect();
     let mut unique = vec![];
+    let mut checks = vec![];
+    let mut indexes = vec![];
+    let mut view = None;
+    let mut materialized = false;
     if name.starts_with('_') {
         name.remove(0);
     }
@@ -145,6 +213,49 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
                         panic!("Error while parsing `unique`, use it like: `#[tank(unique = (\"k1\", \"k2\", ..))]`, you can specify more than one");
                     };
                     unique.push(value);
+                } else if arg.path.is_ident("check") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<Expr>) else {
+                        panic!("Error while parsing `check`, use it like: `#[tank(check = expr!(Self::start < Self::end))]`, you can specify more than one");
+                    };
+                    checks.push(value);
+                } else if arg.path.is_ident("index") {
+                    let mut index = IndexMetadata {
+                        name: String::new(),
+                        columns: Vec::new(),
+                        unique: false,
+                        condition: None,
+                        using: String::new(),
+                    };
+                    let result = arg.parse_nested_meta(|arg| {
+                        if arg.path.is_ident("name") {
+                            index.name = arg.value()?.parse::<LitStr>()?.value();
+                        } else if arg.path.is_ident("columns") {
+                            index.columns = decode_index_columns(&item, arg.value()?, &columns)?;
+                        } else if arg.path.is_ident("unique") {
+                            index.unique = true;
+                        } else if arg.path.is_ident("where") {
+                            index.condition = Some(arg.value()?.parse::<Expr>()?);
+                        } else if arg.path.is_ident("using") {
+                            index.using = arg.value()?.parse::<LitStr>()?.value();
+                        } else {
+                            return Err(arg.error("Unknown index attribute"));
+                        }
+                        Ok(())
+                    });
+                    if let Err(e) = result {
+                        panic!("Error while parsing `index`, use it like: `#[tank(index(name = \"my_index\", columns = (a, b desc), unique, where = Self::a > 0, using = \"btree\"))]` ({e})");
+                    }
+                    if index.columns.is_empty() {
+                        panic!("Index `{}` must specify the columns, use it like: `#[tank(index(columns = (a, b desc)))]`", index.name);
+                    }
+                    indexes.push(index);
+                } else if arg.path.is_ident("view") {
+                    let Ok(value) = arg.value().and_then(ParseBuffer::parse::<Expr>) else {
+                        panic!("Error while parsing `view`, use it like: `#[tank(view = QueryBuilder::new().select(..).from(..))]` or `#[tank(view = \"SELECT ..\")]`");
+                    };
+                    view = Some(value);
+                } else if arg.path.is_ident("materialized") {
+                    materialized = true;
                 } else {
                     panic!("Unknown attribute `{}` inside tank macro", arg.path.to_token_stream().to_string());
                 }
@@ -152,6 +263,11 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
             });
         }
     }
+    if materialized && view.is_none() {
+        panic!(
+            "Attribute `materialized` requires `view`, use it like: `#[tank(view = .., materialized)]`"
+        );
+    }
     if !primary_key.is_empty() {
         let pk_type = if primary_key.len() == 1 {
             PrimaryKeyType::PrimaryKey
@@ -162,12 +278,24 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
             columns[*pk].primary_key = pk_type;
         }
     }
+    for index in indexes.iter_mut().filter(|v| v.name.is_empty()) {
+        index.name = iter::once(name.as_str())
+            .chain(index.columns.iter().map(|(i, _)| columns[*i].name.as_str()))
+            .chain(iter::once("idx"))
+            .collect::<Vec<_>>()
+            .join("_");
+    }
     TableMetadata {
         columns,
+        flattened,
         name,
         item,
         schema,
         primary_key,
         unique,
+        checks,
+        indexes,
+        view,
+        materialized,
     }
 }
@@ -5,23 +5,27 @@ use tank_core::{Value, future::Either, quote_btree_map, quote_option};
 
 pub fn encode_column_def(metadata: &ColumnMetadata, column_ref: TokenStream) -> TokenStream {
     let column_type = quote_btree_map(&metadata.column_type);
-    let value = if matches!(metadata.value, Value::Unknown(..)) {
-        let ty = if let Some(conversion_type) = &metadata.conversion_type {
-            conversion_type
-        } else {
-            &metadata.ty
-        };
+    let nullable = &metadata.nullable;
+    let ty = metadata.conversion_type.as_ref().unwrap_or(&metadata.ty);
+    // The AsValue implementation decides, the syntax only refines it (like the decimal precision)
+    let value = if contains_unknown(&metadata.value) {
         quote!(<#ty as ::tank::AsValue>::as_empty_value())
     } else {
-        metadata.value.to_token_stream()
+        let value = metadata.value.to_token_stream();
+        quote! {{
+            let value = <#ty as ::tank::AsValue>::as_empty_value();
+            if value.same_type(&#value) { #value } else { value }
+        }}
     };
-    let nullable = &metadata.nullable;
+    let enum_def = quote!(<#ty as ::tank::AsValue>::enum_def());
+    let nullable = quote!(#nullable || <#ty as ::tank::AsValue>::NULLABLE);
     let default = metadata
         .default
         .as_ref()
         .map_or(quote!(Default::default()), |v| quote!(#v.into()));
     let primary_key = &metadata.primary_key;
     let clustering_key = metadata.clustering_key;
+    let auto_increment = metadata.auto_increment;
     let references = if let Some(Either::Left(tokens)) = &metadata.references {
         let tokens = tokens.clone();
         quote!(Some(#tokens))
@@ -43,21 +47,45 @@ pub fn encode_column_def(metadata: &ColumnMetadata, column_ref: TokenStream) ->
     let on_delete = quote_option(&metadata.on_delete);
     let on_update = quote_option(&metadata.on_update);
     let unique = &metadata.unique;
+    let check = metadata
+        .check
+        .as_ref()
+        .map_or(quote!(None), |check| quote!(Some(|| Box::new(#check))));
+    let generated = metadata.generated.as_ref().map_or(
+        quote!(None),
+        |generated| quote!(Some(|| Box::new(#generated))),
+    );
+    let stored = metadata.stored;
     let comment = &metadata.comment;
     quote! {
         ::tank::ColumnDef {
             column_ref: #column_ref,
             column_type: #column_type,
             value: #value,
+            enum_def: #enum_def,
             nullable: #nullable,
             default: #default,
             primary_key: #primary_key,
             clustering_key: #clustering_key,
+            auto_increment: #auto_increment,
             references: #references,
             on_delete: #on_delete,
             on_update: #on_update,
             unique: #unique,
+            check: #check,
+            generated: #generated,
+            stored: #stored,
             comment: #comment,
         }
     }
 }
+
+/// Whether the type could not be decoded from the syntax alone (like a newtype).
+fn contains_unknown(value: &Value) -> bool {
+    match value {
+        Value::Unknown(..) => true,
+        Value::Array(_, v, ..) | Value::List(_, v, ..) => contains_unknown(v),
+        Value::Map(_, k, v, ..) => contains_unknown(k) || contains_unknown(v),
+        _ => false,
+    }
+}
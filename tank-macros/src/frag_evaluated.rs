@@ -51,6 +51,19 @@ pub fn flag_evaluated(input: TokenStream) -> TokenStream {
                             )));
                         }
 
+                        // Named parameter :name, @name or $name (but not the path separator ::)
+                        (prev, TokenTree::Punct(p), Some(TokenTree::Ident(name)))
+                            if matches!(p.as_char(), ':' | '@' | '$')
+                                && !matches!(prev, Some(TokenTree::Punct(v)) if v.as_char() == ':') =>
+                        {
+                            let name = name.to_string();
+                            iter.next(); // Consume the name
+                            return Some(TokenTree::Group(Group::new(
+                                Delimiter::None,
+                                quote!(::tank::named_parameter!(#name)),
+                            )));
+                        }
+
                         // Nested
                         (_, TokenTree::Group(group), _) => {
                             let content = do_flagging(group.stream());
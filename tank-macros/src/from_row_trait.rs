@@ -1,7 +1,7 @@
 use crate::{ColumnMetadata, TableMetadata};
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Ident, Type, spanned::Spanned};
+use quote::{format_ident, quote};
+use syn::{Ident, spanned::Spanned};
 
 pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
     let item = &table.item;
@@ -15,7 +15,7 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             let mut #ident: Option<#ty> = None;
         }
     });
-    type AssignmentFn = dyn Fn(&Ident, &Type, Option<&Type>) -> TokenStream;
+    type AssignmentFn = dyn Fn(&Ident, &TokenStream) -> TokenStream;
     type ProducerFn = Box<dyn Fn(&AssignmentFn) -> TokenStream>;
     let field_assignment = table
         .columns
@@ -26,14 +26,22 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
                  name,
                  ty,
                  conversion_type,
+                 json,
                  ..
              }| {
                 let ident = ident.clone();
                 let name = name.clone();
-                let ty = ty.clone();
-                let conversion_type = conversion_type.clone();
+                let decode = match conversion_type {
+                    Some(conversion_type) if *json => {
+                        quote!(<#conversion_type as ::tank::AsValue>::try_from_value(__v__)?.0)
+                    }
+                    Some(conversion_type) => {
+                        quote!(::std::convert::Into::<#ty>::into(<#conversion_type as ::tank::AsValue>::try_from_value(__v__)?))
+                    }
+                    None => quote!(<#ty as ::tank::AsValue>::try_from_value(__v__)?),
+                };
                 Box::new(move |assign: &AssignmentFn| {
-                    let assign = assign(&ident, &ty, conversion_type.as_ref());
+                    let assign = assign(&ident, &decode);
                     quote! {
                         if __n__ == #name {
                             #assign;
@@ -50,13 +58,71 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             }) as ProducerFn
         })
         .unwrap_or(Box::new(|_| TokenStream::new()));
-    let create_result = table.columns.iter().map(|c| {
-        let column = &c.name;
+    // Labels and values of the flattened columns, without the prefix
+    let flattened = table
+        .flattened
+        .iter()
+        .map(|c| {
+            let ident = &c.ident;
+            (
+                c,
+                format_ident!("__{}_labels__", ident),
+                format_ident!("__{}_values__", ident),
+            )
+        })
+        .collect::<Vec<_>>();
+    let flattened_declarations = flattened.iter().map(|(_, labels, values)| {
+        quote! {
+            let mut #labels = Vec::<String>::new();
+            let mut #values = Vec::<::tank::Value>::new();
+        }
+    });
+    let flattened_collect = flattened.iter().map(|(c, labels, values)| {
+        let ty = &c.ty;
+        let prefix = &c.prefix;
+        let len = prefix.len();
+        quote! {
+            if __n__.strip_prefix(#prefix).is_some_and(<#ty as ::tank::Columns>::contains) {
+                #labels.push(__n__[#len..].to_string());
+                #values.push(__v__);
+            }
+        }
+    });
+    let flattened_collect = quote!(#(#flattened_collect)else*);
+    let flattened_decode = |c: &ColumnMetadata, labels: &Ident, values: &Ident| {
+        let ty = &c.ty;
+        quote! {
+            <#ty as ::tank::Columns>::from_row(::tank::Row::new(#labels.into(), #values.into()))?
+        }
+    };
+    let flattened_default = flattened.iter().map(|(c, labels, values)| {
         let ident = &c.ident;
+        let decode = flattened_decode(c, labels, values);
         quote! {
-            #ident: #ident.ok_or(__make_error__(#column))?
+            if !#labels.is_empty() {
+                result.#ident = #decode;
+            }
         }
     });
+    let flattened_holder = flattened.iter().map(|(c, labels, values)| {
+        let ident = &c.ident;
+        let decode = flattened_decode(c, labels, values);
+        quote!(let #ident = #decode;)
+    });
+    let create_result = table
+        .columns
+        .iter()
+        .map(|c| {
+            let column = &c.name;
+            let ident = &c.ident;
+            quote! {
+                #ident: #ident.ok_or(__make_error__(#column))?
+            }
+        })
+        .chain(table.flattened.iter().map(|c| {
+            let ident = &c.ident;
+            quote!(#ident)
+        }));
     let remaining = item
         .fields
         .iter()
@@ -64,6 +130,7 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             table
                 .columns
                 .iter()
+                .chain(table.flattened.iter())
                 .find(|c| c.ident == *field.ident.as_ref().unwrap())
                 .is_none()
         })
@@ -77,20 +144,20 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             #(#remaining,)*
         }
     };
-    let field_assignment_default = field_assignment(&|field, ty, conversion_type| {
-        if let Some(conversion_type) = conversion_type {
-            quote!(result.#field = ::std::convert::Into::<#ty>::into(<#conversion_type as ::tank::AsValue>::try_from_value(__v__)?))
+    let field_assignment = |assign: &AssignmentFn| {
+        let assignment = field_assignment(assign);
+        if table.flattened.is_empty() {
+            assignment
+        } else if table.columns.is_empty() {
+            flattened_collect.clone()
         } else {
-            quote!(result.#field = <#ty as ::tank::AsValue>::try_from_value(__v__)?)
+            quote!(#assignment else #flattened_collect)
         }
-    });
-    let field_assignment_holder = field_assignment(&|field, ty, conversion_type| {
-        if let Some(conversion_type) = conversion_type {
-            quote!(#field = Some(::std::convert::Into::<#ty>::into(<#conversion_type as ::tank::AsValue>::try_from_value(__v__)?)))
-        } else {
-            quote!(#field = Some(<#ty as ::tank::AsValue>::try_from_value(__v__)?))
-        }
-    });
+    };
+    let field_assignment_default =
+        field_assignment(&|field, decode| quote!(result.#field = #decode));
+    let field_assignment_holder = field_assignment(&|field, decode| quote!(#field = Some(#decode)));
+    let flattened_declarations = quote!(#(#flattened_declarations)*);
     (
         factory_name.clone(),
         quote! {
@@ -102,10 +169,12 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
                 // Called when T has Default Trait
                 fn from_row(row: ::tank::Row) -> ::tank::Result<#struct_name> {
                     let mut result = T::default().into();
+                    #flattened_declarations
                     for (__n__, __v__) in ::std::iter::zip(row.labels.iter(), row.values.into_iter())
                     {
                         #field_assignment_default
                     }
+                    #(#flattened_default)*
                     Ok(result)
                 }
             }
@@ -113,10 +182,12 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
                 // Called when T doesn't have default trait
                 fn from_row(row: ::tank::Row) -> ::tank::Result<#struct_name> {
                     #(#fields_holder_declarations)*
+                    #flattened_declarations
                     for (__n__, __v__) in ::std::iter::zip(row.labels.iter(), row.values.into_iter())
                     {
                         #field_assignment_holder
                     }
+                    #(#flattened_holder)*
                     let __make_error__ = |name: &str| ::tank::anyhow!(
                         "Column `{name}` does not exist in the row provided (implement `Default` for {} or get all the columns)",
                         stringify!(#struct_name),
@@ -1,8 +1,11 @@
 mod cols;
 mod column_trait;
 mod decode_column;
+mod decode_enum;
 mod decode_expression;
 mod decode_join;
+mod decode_sql;
+mod decode_struct;
 mod decode_table;
 mod encode_column_def;
 mod encode_column_ref;
@@ -12,6 +15,8 @@ mod from_row_trait;
 use crate::{
     cols::ColList,
     decode_column::ColumnMetadata,
+    decode_enum::decode_enum,
+    decode_struct::decode_struct,
     decode_table::{TableMetadata, decode_table},
     encode_column_def::encode_column_def,
     from_row_trait::from_row_trait,
@@ -20,16 +25,59 @@ use column_trait::column_trait;
 use decode_column::decode_column;
 use decode_expression::decode_expression;
 use decode_join::JoinParsed;
+use decode_sql::SqlParsed;
 use frag_evaluated::flag_evaluated;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{
-    Expr, Ident, Index, ItemStruct, parse_macro_input, parse2, punctuated::Punctuated,
-    token::AndAnd,
+    Expr, Ident, Index, ItemEnum, ItemStruct, Member, parse_macro_input, parse_quote, parse2,
+    punctuated::Punctuated, token::AndAnd,
 };
 
 #[proc_macro_derive(Entity, attributes(tank))]
+/// Map a struct to a table, or to a view when declared with `#[tank(view = ..)]`.
+///
+/// Views are read-only, they can be queried like any table:
+/// ```
+/// # use tank::{Entity, Executor, stream::Stream};
+/// #[derive(Entity)]
+/// #[tank(view = "SELECT author, COUNT(*) AS books FROM book GROUP BY author")]
+/// struct Author { author: String, books: i64 }
+/// fn read(executor: &mut impl Executor) -> impl Stream {
+///     Author::find_many(executor, true, None)
+/// }
+/// ```
+/// But inserting a view row does not build:
+/// ```compile_fail
+/// # use tank::{Entity, Executor};
+/// # #[derive(Entity)]
+/// # #[tank(view = "SELECT author, COUNT(*) AS books FROM book GROUP BY author")]
+/// # struct Author { author: String, books: i64 }
+/// async fn write(executor: &mut impl Executor, author: Author) {
+///     Author::insert_one(executor, &author).await;
+/// }
+/// ```
+/// Neither does saving it:
+/// ```compile_fail
+/// # use tank::{Entity, Executor};
+/// # #[derive(Entity)]
+/// # #[tank(view = "SELECT author, COUNT(*) AS books FROM book GROUP BY author")]
+/// # struct Author { author: String, books: i64 }
+/// async fn write(executor: &mut impl Executor, author: Author) {
+///     author.save(executor).await;
+/// }
+/// ```
+/// Nor deleting it:
+/// ```compile_fail
+/// # use tank::{Entity, Executor};
+/// # #[derive(Entity)]
+/// # #[tank(view = "SELECT author, COUNT(*) AS books FROM book GROUP BY author")]
+/// # struct Author { author: String, books: i64 }
+/// async fn write(executor: &mut impl Executor, author: Author) {
+///     author.delete(executor).await;
+/// }
+/// ```
 pub fn derive_entity(input: TokenStream) -> TokenStream {
     let table = decode_table(parse_macro_input!(input as ItemStruct));
     let ident = &table.item.ident;
@@ -55,17 +103,54 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
         })
         .collect::<Vec<_>>();
     let unique_defs = quote!(vec![#(#unique_defs),*].into_boxed_slice());
+    let checks = table.checks.iter().map(|check| {
+        quote! {
+            (|| -> Box<dyn ::tank::Expression> { Box::new(#check) })
+                as fn() -> Box<dyn ::tank::Expression>
+        }
+    });
+    let indexes = table.indexes.iter().map(|index| {
+        let name = &index.name;
+        let columns = index
+            .columns
+            .iter()
+            .map(|(i, order)| quote!((&columns[#i], #order)));
+        let unique = index.unique;
+        let condition = index.condition.as_ref().map_or(quote!(None), |condition| {
+            quote!(Some(|| Box::new(::tank::expr!(#condition))))
+        });
+        let using = &index.using;
+        quote! {
+            ::tank::IndexDef {
+                name: #name,
+                columns: vec![#(#columns),*].into_boxed_slice(),
+                unique: #unique,
+                condition: #condition,
+                using: #using,
+            }
+        }
+    });
+    let view = table.view.as_ref().map(|view| {
+        let materialized = table.materialized;
+        quote! {
+            fn view() -> Option<&'static ::tank::ViewDef> {
+                Some(&::tank::ViewDef {
+                    materialized: #materialized,
+                })
+            }
+
+            fn write_view_query(writer: &impl ::tank::SqlWriter, out: &mut ::tank::DynQuery) {
+                ::tank::ViewQuery::write_view_query(&(#view), writer, out);
+            }
+        }
+    });
+    let writable = table
+        .view
+        .is_none()
+        .then(|| quote!(impl ::tank::Writable for #ident {}));
     let primary_key_types = primary_key_cols.clone().map(|col| col.ty.clone());
     let (column_trait, column) = column_trait(&table);
-    let row_values = table.columns.iter().map(
-        |ColumnMetadata { ident,  conversion_type,.. }| {
-            if let Some(conversion_type) = conversion_type {
-                quote!(::tank::AsValue::as_value(::std::convert::Into::<#conversion_type>::into(self.#ident.clone())))
-            } else {
-                quote!(::tank::AsValue::as_value(self.#ident.clone()))
-            }
-        },
-    );
+    let row_values = row_values(&table);
     let write_fields = table.columns.iter().enumerate().map(
         |(
             i,
@@ -90,10 +175,27 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             quote!(#sep writer.write_value(context, out, &#value_expr);)
         },
     );
+    let write_flattened = table.flattened.iter().enumerate().map(|(i, c)| {
+        let field = &c.ident;
+        let separate = i > 0 || !table.columns.is_empty();
+        quote! {
+            for (i, value) in ::tank::Columns::row_values(&self.#field).iter().enumerate() {
+                if #separate || i > 0 {
+                    out.push_str(", ");
+                }
+                writer.write_value(context, out, value);
+            }
+        }
+    });
     let columns = table.columns.iter().map(|c| {
         let field = &c.ident;
         encode_column_def(&c, quote!(<#ident as #column_trait>::#field))
     });
+    let flattened_columns = table.flattened.iter().map(|c| {
+        let ty = &c.ty;
+        let prefix = &c.prefix;
+        quote!(<#ty as ::tank::Columns>::columns(#prefix, #name, #schema))
+    });
     let primary_key_condition = table.primary_key.iter().enumerate().map(|(i, pki)| {
         let ident = table.columns[*pki].ident.clone();
         let span = ident.span();
@@ -150,7 +252,12 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
 
             fn columns() -> &'static [::tank::ColumnDef] {
                 static RESULT: ::std::sync::LazyLock<Box<[::tank::ColumnDef]>> =
-                    ::std::sync::LazyLock::new(|| vec![#(#columns),*].into_boxed_slice());
+                    ::std::sync::LazyLock::new(|| {
+                        vec![#(#columns),*]
+                            .into_iter()
+                            #(.chain(#flattened_columns))*
+                            .collect()
+                    });
                 &RESULT
             }
 
@@ -183,8 +290,25 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
                 RESULT.iter().map(|v| v.iter().copied())
             }
 
+            fn check_defs() -> &'static [fn() -> Box<dyn ::tank::Expression>] {
+                static RESULT: ::std::sync::LazyLock<Box<[fn() -> Box<dyn ::tank::Expression>]>> =
+                    ::std::sync::LazyLock::new(|| vec![#(#checks),*].into_boxed_slice());
+                &RESULT
+            }
+
+            fn indexes() -> &'static [::tank::IndexDef] {
+                static RESULT: ::std::sync::LazyLock<Box<[::tank::IndexDef]>> =
+                    ::std::sync::LazyLock::new(|| {
+                        let columns = #ident::columns();
+                        vec![#(#indexes),*].into_boxed_slice()
+                    });
+                &RESULT
+            }
+
+            #view
+
             fn row_values(&self) -> ::tank::RowValues {
-                [#(#row_values),*].into()
+                #row_values
             }
 
             fn from_row(row: ::tank::Row) -> ::tank::Result<Self> {
@@ -192,6 +316,8 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             }
         }
 
+        #writable
+
         impl ::tank::OpPrecedence for #ident {
             fn precedence(&self, _: &dyn ::tank::SqlWriter) -> i32 {
                 0
@@ -206,12 +332,313 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
                 out: &mut ::tank::DynQuery,
             ) {
                 #(#write_fields)*
+                #(#write_flattened)*
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(Enum, attributes(tank))]
+pub fn derive_enum(input: TokenStream) -> TokenStream {
+    let metadata = decode_enum(parse_macro_input!(input as ItemEnum));
+    let ident = &metadata.item.ident;
+    let name = &metadata.name;
+    let schema = &metadata.schema;
+    let check = metadata.check;
+    let variants = metadata.variants.iter().map(|(v, _)| v).collect::<Vec<_>>();
+    let len = variants.len();
+    let (empty, kind, values, decode) = if let Some((value, ty)) = &metadata.integer {
+        let expected = quote!([#(#ident::#variants as #ty),*]
+            .map(|v| v.to_string())
+            .join(", "));
+        (
+            quote!(::tank::Value::#value(None)),
+            quote!(Numeric),
+            variants
+                .iter()
+                .map(|v| quote!(::tank::Value::#value(Some(#ident::#v as #ty))))
+                .collect::<Vec<_>>(),
+            quote! {
+                if let ::tank::Value::#value(Some(v)) = value.try_as(&::tank::Value::#value(None))? {
+                    #(if v == #ident::#variants as #ty {
+                        return Ok(#ident::#variants);
+                    })*
+                    return Err(::tank::anyhow!(
+                        "Unknown discriminant {v} for the enum {}, expected one of: {}",
+                        stringify!(#ident),
+                        #expected,
+                    ));
+                }
+            },
+        )
+    } else {
+        let labels = metadata.variants.iter().map(|(_, v)| v).collect::<Vec<_>>();
+        let expected = labels
+            .iter()
+            .map(|v| v.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        (
+            quote!(::tank::Value::Varchar(None)),
+            quote!(Text),
+            labels
+                .iter()
+                .map(|v| quote!(::tank::Value::Varchar(Some(::std::borrow::Cow::Borrowed(#v)))))
+                .collect::<Vec<_>>(),
+            quote! {
+                if let ::tank::Value::Varchar(Some(v)) = value.try_as(&::tank::Value::Varchar(None))? {
+                    return match v.as_ref() {
+                        #(#labels => Ok(#ident::#variants),)*
+                        v => Err(::tank::anyhow!(
+                            "Unknown value `{v}` for the enum {}, expected one of: {}",
+                            stringify!(#ident),
+                            #expected,
+                        )),
+                    };
+                }
+            },
+        )
+    };
+    quote! {
+        impl ::tank::AsValue for #ident {
+            const KIND: ::tank::TypeKind = ::tank::TypeKind::#kind;
+            fn as_empty_value() -> ::tank::Value {
+                #empty
+            }
+            fn as_value(self) -> ::tank::Value {
+                match self {
+                    #(#ident::#variants => #values,)*
+                }
+            }
+            fn try_from_value(value: ::tank::Value) -> ::tank::Result<Self> {
+                #decode
+                Err(::tank::anyhow!("Cannot decode the enum {} from NULL", stringify!(#ident)))
+            }
+            fn enum_def() -> Option<&'static ::tank::EnumDef> {
+                static VALUES: [::tank::Value; #len] = [#(#values),*];
+                static ENUM_DEF: ::tank::EnumDef = ::tank::EnumDef {
+                    name: #name,
+                    schema: #schema,
+                    values: &VALUES,
+                    check: #check,
+                };
+                Some(&ENUM_DEF)
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(Struct, attributes(tank))]
+pub fn derive_struct(input: TokenStream) -> TokenStream {
+    let metadata = decode_struct(parse_macro_input!(input as ItemStruct));
+    let ident = &metadata.item.ident;
+    let name = &metadata.name;
+    let schema = &metadata.schema;
+    let fields = metadata.fields.iter().map(|(v, ..)| v).collect::<Vec<_>>();
+    let types = metadata
+        .fields
+        .iter()
+        .map(|(_, v, _)| v)
+        .collect::<Vec<_>>();
+    let labels = metadata.fields.iter().map(|(.., v)| v).collect::<Vec<_>>();
+    let fields_types = quote! {
+        vec![#((#labels.into(), <#types as ::tank::AsValue>::as_empty_value())),*]
+    };
+    let type_ref = quote! {
+        ::tank::TableRef {
+            name: ::std::borrow::Cow::Borrowed(#name),
+            schema: ::std::borrow::Cow::Borrowed(#schema),
+            ..Default::default()
+        }
+    };
+    quote! {
+        impl ::tank::AsValue for #ident {
+            const KIND: ::tank::TypeKind = ::tank::TypeKind::Struct;
+            fn as_empty_value() -> ::tank::Value {
+                ::tank::Value::Struct(None, #fields_types, #type_ref)
+            }
+            fn as_value(self) -> ::tank::Value {
+                ::tank::Value::Struct(
+                    Some(vec![#((#labels.into(), ::tank::AsValue::as_value(self.#fields))),*]),
+                    #fields_types,
+                    #type_ref,
+                )
+            }
+            fn try_from_value(value: ::tank::Value) -> ::tank::Result<Self> {
+                let mut fields = ::tank::ErrorContext::with_context(
+                    ::tank::struct_fields(value),
+                    || format!("While decoding the struct {}", stringify!(#ident)),
+                )?;
+                Ok(Self {
+                    #(#fields: ::tank::ErrorContext::with_context(
+                        <#types as ::tank::AsValue>::try_from_value(
+                            fields.remove(#labels).unwrap_or_default(),
+                        ),
+                        || format!("While decoding the field `{}` of the struct {}", #labels, stringify!(#ident)),
+                    )?,)*
+                })
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(Value)]
+pub fn derive_value(input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as ItemStruct);
+    let ident = item.ident.clone();
+    let mut fields = item.fields.iter();
+    let (Some(field), None) = (fields.next(), fields.next()) else {
+        panic!(
+            "Value can only be derived on structs with a single field, like: `struct UserId(Uuid);`"
+        );
+    };
+    let ty = field.ty.clone();
+    let member = field
+        .ident
+        .clone()
+        .map_or(Member::Unnamed(Index::from(0)), Member::Named);
+    item.generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::tank::AsValue));
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::tank::AsValue for #ident #ty_generics #where_clause {
+            const NULLABLE: bool = <#ty as ::tank::AsValue>::NULLABLE;
+            const KIND: ::tank::TypeKind = <#ty as ::tank::AsValue>::KIND;
+            fn as_empty_value() -> ::tank::Value {
+                <#ty as ::tank::AsValue>::as_empty_value()
+            }
+            fn as_value(self) -> ::tank::Value {
+                ::tank::AsValue::as_value(self.#member)
+            }
+            fn try_from_value(value: ::tank::Value) -> ::tank::Result<Self> {
+                Ok(Self {
+                    #member: <#ty as ::tank::AsValue>::try_from_value(value)?,
+                })
+            }
+            fn parse(input: impl AsRef<str>) -> ::tank::Result<Self> {
+                Ok(Self {
+                    #member: <#ty as ::tank::AsValue>::parse(input)?,
+                })
+            }
+            fn enum_def() -> Option<&'static ::tank::EnumDef> {
+                <#ty as ::tank::AsValue>::enum_def()
+            }
+        }
+    }
+    .into()
+}
+
+#[proc_macro_derive(Columns, attributes(tank))]
+pub fn derive_columns(input: TokenStream) -> TokenStream {
+    let table = decode_table(parse_macro_input!(input as ItemStruct));
+    let ident = &table.item.ident;
+    let (from_row_factory, from_row) = from_row_trait(&table);
+    let columns = table.columns.iter().map(|c| {
+        let name = &c.name;
+        encode_column_def(
+            c,
+            quote! {
+                ::tank::ColumnRef {
+                    name: ::std::borrow::Cow::Owned(format!("{prefix}{}", #name)),
+                    table: ::std::borrow::Cow::Borrowed(table),
+                    schema: ::std::borrow::Cow::Borrowed(schema),
+                }
+            },
+        )
+    });
+    let flattened_columns = table.flattened.iter().map(|c| {
+        let ty = &c.ty;
+        let prefix = &c.prefix;
+        quote!(<#ty as ::tank::Columns>::columns(&format!("{prefix}{}", #prefix), table, schema))
+    });
+    let names = table.columns.iter().map(|c| &c.name).collect::<Vec<_>>();
+    let fields = table.columns.iter().map(|c| c.ident.to_string());
+    let contains_flattened = table.flattened.iter().map(|c| {
+        let ty = &c.ty;
+        let prefix = &c.prefix;
+        quote!(name.strip_prefix(#prefix).is_some_and(<#ty as ::tank::Columns>::contains))
+    });
+    let field_column_flattened = table.flattened.iter().map(|c| {
+        let field = c.ident.to_string();
+        let ty = &c.ty;
+        let prefix = &c.prefix;
+        quote! {
+            #field => <#ty as ::tank::Columns>::field_column(&path[1..])
+                .map(|(name, n)| (::std::borrow::Cow::Owned(format!("{}{name}", #prefix)), n + 1)),
+        }
+    });
+    let row_values = row_values(&table);
+    quote! {
+        #from_row
+
+        impl ::tank::Columns for #ident {
+            fn columns(
+                prefix: &str,
+                table: &'static str,
+                schema: &'static str,
+            ) -> Vec<::tank::ColumnDef> {
+                vec![#(#columns),*]
+                    .into_iter()
+                    #(.chain(#flattened_columns))*
+                    .collect()
+            }
+
+            fn contains(name: &str) -> bool {
+                [#(#names),*].contains(&name) #(|| #contains_flattened)*
+            }
+
+            fn field_column(path: &[&str]) -> ::tank::FieldColumn {
+                match *path.first()? {
+                    #(#fields => Some((::std::borrow::Cow::Borrowed(#names), 1)),)*
+                    #(#field_column_flattened)*
+                    _ => None,
+                }
+            }
+
+            fn row_values(&self) -> ::tank::RowValues {
+                #row_values
+            }
+
+            fn from_row(row: ::tank::Row) -> ::tank::Result<Self> {
+                #from_row_factory::<Self>::from_row(row)
             }
         }
     }
     .into()
 }
 
+/// Values of the columns (followed by the flattened ones) of `self`.
+fn row_values(table: &TableMetadata) -> TokenStream2 {
+    let values = table.columns.iter().map(
+        |ColumnMetadata {
+             ident,
+             conversion_type,
+             ..
+         }| {
+            if let Some(conversion_type) = conversion_type {
+                quote!(::tank::AsValue::as_value(::std::convert::Into::<#conversion_type>::into(self.#ident.clone())))
+            } else {
+                quote!(::tank::AsValue::as_value(self.#ident.clone()))
+            }
+        },
+    );
+    let flattened = table.flattened.iter().map(|c| {
+        let ident = &c.ident;
+        quote!(::tank::Columns::row_values(&self.#ident))
+    });
+    quote! {
+        [#(#values),*]
+            .into_iter()
+            #(.chain(#flattened))*
+            .collect()
+    }
+}
+
 #[proc_macro]
 /// Build a typed join tree from a concise SQL-like syntax.
 ///
@@ -238,6 +665,17 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
 ///     .try_collect::<HashSet<_>>()
 ///     .await?;
 /// ```
+///
+/// The columns compared in `ON` are checked against each other like in [`expr!`], a number
+/// column joined to a string column does not build:
+/// ```compile_fail
+/// # use tank::{Entity, join};
+/// # #[derive(Entity)]
+/// # struct Author { id: u64, name: String }
+/// # #[derive(Entity)]
+/// # struct Book { author: u64, title: String }
+/// let books = join!(Book JOIN Author ON Book::title == Author::id);
+/// ```
 pub fn join(input: TokenStream) -> TokenStream {
     let result = parse_macro_input!(input as JoinParsed);
     result.0.into()
@@ -275,6 +713,44 @@ pub fn join(input: TokenStream) -> TokenStream {
 /// let rust_articles = expr!(Post::title == "Rust%" as LIKE);
 /// let first_user = expr!(CAST(User::active as i32) == 1);
 /// ```
+///
+/// Comparisons with literals, variables and other columns are checked at compile time against
+/// the Rust type of the columns of derived entities:
+/// ```
+/// # use tank::{Entity, expr};
+/// # #[derive(Entity)]
+/// # struct User { name: String, age: u32 }
+/// let min_age = 18;
+/// let condition = expr!(User::age > #min_age && User::name == "A%" as LIKE && User::age == (1, 2) as IN);
+/// ```
+/// A number column compared to a string does not build:
+/// ```compile_fail
+/// # use tank::{Entity, expr};
+/// # #[derive(Entity)]
+/// # struct User { name: String, age: u32 }
+/// let condition = expr!(User::age > "abc");
+/// ```
+/// Neither does `LIKE` on a non string column:
+/// ```compile_fail
+/// # use tank::{Entity, expr};
+/// # #[derive(Entity)]
+/// # struct User { name: String, age: u32 }
+/// let condition = expr!(User::age == "1%" as LIKE);
+/// ```
+/// Nor an `IN` list whose elements don't match the column:
+/// ```compile_fail
+/// # use tank::{Entity, expr};
+/// # #[derive(Entity)]
+/// # struct User { name: String, age: u32 }
+/// let condition = expr!(User::age == (1, "two") as IN);
+/// ```
+/// Nor two columns of different types:
+/// ```compile_fail
+/// # use tank::{Entity, expr};
+/// # #[derive(Entity)]
+/// # struct User { name: String, age: u32 }
+/// let condition = expr!(User::age < User::name);
+/// ```
 pub fn expr(input: TokenStream) -> TokenStream {
     let mut input: TokenStream = flag_evaluated(input.into()).into();
     if input.is_empty() {
@@ -317,3 +793,34 @@ pub fn cols(input: TokenStream) -> TokenStream {
         &[ #( &#generated as &dyn ::tank::Expression ),* ]
     })
 }
+
+#[proc_macro]
+/// Build a raw SQL query interpolating values and identifiers safely.
+///
+/// The string is written as is, except for the placeholders in braces:
+/// - `{name}`, `{user.age + 1}` value of the expression (implementing `AsValue`), moved into the query
+/// - `{#ids}` every value of the iterable, written as a parenthesized list for `IN`
+/// - `{ident:User::name}` column or table (`User::table()`) identifier, quoted by the driver
+/// - `{{` and `}}` literal braces
+///
+/// Values can also be passed as named arguments after the string, like `format!`.
+/// `Sql::fetch`, `Sql::execute` and `Sql::prepare` bind the values to the native placeholders
+/// of the driver, `Sql::inline` writes them as escaped literals instead. An empty `{#ids}` is an
+/// error, `IN ()` is not valid SQL.
+/// Binding prepares the query, which connection pools cannot do: run it on a connection checked
+/// out of the pool.
+///
+/// *Example*:
+/// ```ignore
+/// let rows = sql!(
+///     "SELECT {ident:User::name} FROM {ident:User::table()} WHERE age > {min_age} AND id IN {#ids}",
+///     min_age = 18,
+/// )
+/// .fetch(executor)
+/// .try_collect::<Vec<_>>()
+/// .await?;
+/// ```
+pub fn sql(input: TokenStream) -> TokenStream {
+    let result = parse_macro_input!(input as SqlParsed);
+    result.0.into()
+}
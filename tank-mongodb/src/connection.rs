@@ -1,15 +1,20 @@
 use crate::{
     AggregatePayload, BatchPayload, CreateCollectionPayload, DeletePayload, DropCollectionPayload,
     DropDatabasePayload, FindManyPayload, FindOnePayload, InsertManyPayload, InsertOnePayload,
-    MongoDBDriver, MongoDBTransaction, Payload, RowWrap, UpsertPayload,
+    MongoDBDriver, MongoDBTransaction, Payload, RowWrap, UpsertPayload, bson_bind_params,
+    bson_type_name, bson_type_to_value,
 };
 use anyhow::anyhow;
 use async_stream::try_stream;
-use mongodb::{Client, ClientSession, Collection, Database, bson::Bson};
-use std::{borrow::Cow, future, i64};
+use mongodb::{
+    Client, ClientSession, Collection, Database,
+    bson::{Bson, Document, doc},
+    options::WriteModel,
+};
+use std::{borrow::Cow, future, i64, mem};
 use tank_core::{
-    AsQuery, Connection, Error, ErrorContext, Executor, Query, QueryResult, Result, RowsAffected,
-    TableRef,
+    AsQuery, ColumnInfo, ColumnRef, Connection, Error, ErrorContext, Executor, Params,
+    PrimaryKeyType, Query, QueryResult, Result, RowsAffected, TableRef,
     stream::{Stream, TryStreamExt},
     truncate_long,
 };
@@ -85,6 +90,14 @@ impl Connection for MongoDBConnection {
         session.start_transaction().await?;
         Ok(MongoDBTransaction::new(self, end_connection_session))
     }
+
+    async fn ping(&mut self) -> Result<()> {
+        self.default_database
+            .run_command(doc! { "ping": 1 })
+            .await
+            .context("While pinging MongoDB")?;
+        Ok(())
+    }
 }
 
 impl Executor for MongoDBConnection {
@@ -302,7 +315,11 @@ impl Executor for MongoDBConnection {
                     });
                 }
 
-                Payload::CreateCollection(CreateCollectionPayload { table, options, .. }) => {
+                Payload::CreateCollection(CreateCollectionPayload {
+                    table,
+                    options,
+                    indexes,
+                }) => {
                     let database = self.database(table);
                     let mut operation = database
                         .create_collection(table.name.to_string())
@@ -311,6 +328,14 @@ impl Executor for MongoDBConnection {
                         operation = operation.session(session);
                     }
                     operation.await.with_context(|| make_context!(payload))?;
+                    if !indexes.is_empty() {
+                        let collection = self.collection(table);
+                        let mut operation = collection.create_indexes(indexes.iter().cloned());
+                        if let Some(session) = &mut self.session {
+                            operation = operation.session(session);
+                        }
+                        operation.await.with_context(|| make_context!(payload))?;
+                    }
                 }
 
                 Payload::DropCollection(DropCollectionPayload { table, .. }) => {
@@ -423,4 +448,189 @@ impl Executor for MongoDBConnection {
             e
         })
     }
+
+    /// Sends a single bulk write, the placeholders of each params set are replaced with literals.
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<MongoDBDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let context = format!(
+            "While executing a batch of the query:\n{}",
+            truncate_long!(format!("{query:?}"), true)
+        );
+        let Query::Prepared(prepared) = query else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        let mut models = Vec::new();
+        let mut ranges = Vec::with_capacity(params.len());
+        for params in params {
+            params.bind(prepared).context(context.clone())?;
+            let values = prepared.take_params()?.unwrap_or_default();
+            let batch = match prepared.get_payload() {
+                Payload::Batch(BatchPayload { batch, .. }) => {
+                    batch.iter().filter_map(Payload::as_write_models).collect()
+                }
+                payload => payload.as_write_models().into_iter().collect::<Vec<_>>(),
+            };
+            if batch.is_empty() {
+                let error = anyhow!("Only inserts, upserts and deletes can be executed in a batch")
+                    .context(context);
+                log::error!("{error:#}");
+                return Err(error);
+            }
+            let start = models.len();
+            for mut model in batch {
+                let filter = match &mut model {
+                    WriteModel::UpdateOne(model) => Some(&mut model.filter),
+                    WriteModel::DeleteMany(model) => Some(&mut model.filter),
+                    _ => None,
+                };
+                if let Some(filter) = filter {
+                    let mut bson = Bson::Document(mem::take(filter));
+                    bson_bind_params(&mut bson, &values);
+                    if let Bson::Document(v) = bson {
+                        *filter = v;
+                    }
+                }
+                models.push(model);
+            }
+            ranges.push(start..models.len());
+        }
+        let mut operation = self.client.bulk_write(models).verbose_results();
+        if let Some(session) = &mut self.session {
+            operation = operation.session(session);
+        }
+        let result = operation.await.map_err(|e| {
+            let error = Error::new(e).context(context);
+            log::error!("{error:#}");
+            error
+        })?;
+        Ok(ranges
+            .into_iter()
+            .map(|range| {
+                let rows_affected = range
+                    .map(|i| {
+                        if result.insert_results.contains_key(&i) {
+                            1
+                        } else if let Some(v) = result.update_results.get(&i) {
+                            v.modified_count + v.upserted_id.is_some() as u64
+                        } else {
+                            result.delete_results.get(&i).map_or(0, |v| v.deleted_count)
+                        }
+                    })
+                    .sum();
+                RowsAffected {
+                    rows_affected: Some(rows_affected),
+                    last_affected_id: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Reads the fields from the `$jsonSchema` validator of the collection, or infers them from a
+    /// sampled document when the collection has no validator.
+    async fn describe_table(&mut self, table: &TableRef) -> Result<Vec<ColumnInfo>> {
+        let context = || format!("While describing the collection {}", table.full_name("."));
+        let database = self.database(table);
+        let Some(specification) = database
+            .list_collections()
+            .filter(doc! { "name": table.name.as_ref() })
+            .await
+            .with_context(context)?
+            .try_next()
+            .await
+            .with_context(context)?
+        else {
+            return Ok(Vec::new());
+        };
+        let column = |name: &str, column_type: &str| ColumnInfo {
+            column_ref: ColumnRef {
+                name: name.to_string().into(),
+                table: table.name.clone(),
+                schema: table.schema.clone(),
+            },
+            column_type: column_type.to_string(),
+            value: bson_type_to_value(column_type),
+            nullable: true,
+            ..Default::default()
+        };
+        let mut result = Vec::new();
+        let json_schema = specification
+            .options
+            .validator
+            .as_ref()
+            .and_then(|v| v.get_document("$jsonSchema").ok());
+        if let Some(json_schema) = json_schema {
+            let required = json_schema
+                .get_array("required")
+                .map(|v| v.iter().filter_map(Bson::as_str).collect::<Vec<_>>())
+                .unwrap_or_default();
+            let properties = json_schema.get_document("properties").ok();
+            for (name, property) in properties.into_iter().flatten() {
+                let property = property.as_document();
+                // `bsonType` is either a single type or a list, possibly containing "null"
+                let types = match property.and_then(|v| v.get("bsonType")) {
+                    Some(Bson::String(v)) => vec![v.as_str()],
+                    Some(Bson::Array(v)) => v.iter().filter_map(Bson::as_str).collect(),
+                    _ => Vec::new(),
+                };
+                let column_type = types.iter().find(|v| **v != "null").unwrap_or(&"");
+                let mut column = column(name, column_type);
+                column.nullable = !required.contains(&name.as_str()) || types.contains(&"null");
+                column.comment = property
+                    .and_then(|v| v.get_str("description").ok())
+                    .unwrap_or_default()
+                    .to_string();
+                result.push(column);
+            }
+        } else if let Some(document) = database
+            .collection::<Document>(&table.name)
+            .find_one(doc! {})
+            .await
+            .with_context(context)?
+        {
+            for (name, value) in &document {
+                result.push(column(name, bson_type_name(value)));
+            }
+        }
+        match result.iter().position(|v| v.name() == "_id") {
+            Some(i) => {
+                let id = &mut result[i];
+                id.nullable = false;
+                id.unique = true;
+                id.primary_key = PrimaryKeyType::PrimaryKey;
+            }
+            None if !result.is_empty() => {
+                let mut id = column("_id", "objectId");
+                id.nullable = false;
+                id.unique = true;
+                id.primary_key = PrimaryKeyType::PrimaryKey;
+                result.insert(0, id);
+            }
+            None => {}
+        }
+        Ok(result)
+    }
+
+    async fn list_tables(&mut self, schema: &str) -> Result<Vec<TableRef>> {
+        let database = if schema.is_empty() {
+            self.default_database.clone()
+        } else {
+            self.client.database(schema)
+        };
+        let mut names = database
+            .list_collection_names()
+            .await
+            .with_context(|| format!("While listing the collections of {}", database.name()))?;
+        names.sort();
+        Ok(names
+            .into_iter()
+            .map(|name| TableRef {
+                name: name.into(),
+                schema: database.name().to_string().into(),
+                ..Default::default()
+            })
+            .collect())
+    }
 }
@@ -1,7 +1,7 @@
-use crate::{RowWrap, bson_is_empty};
+use crate::{RowWrap, bson_has_param, bson_is_empty};
 use anyhow::anyhow;
 use mongodb::{
-    Namespace,
+    IndexModel, Namespace,
     bson::{Bson, Document},
     options::{
         AggregateOptions, BulkWriteOptions, CreateCollectionOptions, DeleteManyModel,
@@ -60,6 +60,7 @@ pub struct DeletePayload {
 pub struct CreateCollectionPayload {
     pub table: TableRef,
     pub options: CreateCollectionOptions,
+    pub indexes: Vec<IndexModel>,
 }
 
 #[derive(Default, Debug)]
@@ -144,6 +145,24 @@ impl Payload {
             }
         }
     }
+    /// True if the payload references the named parameter.
+    pub fn has_parameter(&self, name: &str) -> bool {
+        match self {
+            Payload::Fragment(v) => bson_has_param(v, name),
+            Payload::FindOne(v) => bson_has_param(&v.filter, name),
+            Payload::FindMany(v) => bson_has_param(&v.filter, name),
+            Payload::Upsert(v) => bson_has_param(&v.filter, name),
+            Payload::Delete(v) => bson_has_param(&v.filter, name),
+            Payload::Aggregate(v) => v
+                .pipeline
+                .iter()
+                .any(|v| v.values().any(|v| bson_has_param(v, name))),
+            Payload::Batch(BatchPayload { batch, .. }) => {
+                batch.iter().any(|v| v.has_parameter(name))
+            }
+            _ => false,
+        }
+    }
     pub fn current_bson_mut(&mut self) -> Option<&mut Bson> {
         match self {
             Payload::Fragment(v) => Some(v),
@@ -12,6 +12,7 @@ pub struct MongoDBPrepared {
     payload: Payload,
     pub(crate) count: u32,
     pub(crate) params: Vec<Value>,
+    pub(crate) named_params: Vec<(String, Value)>,
     pub(crate) index: u64,
 }
 
@@ -21,6 +22,7 @@ impl MongoDBPrepared {
             payload,
             count,
             params: Default::default(),
+            named_params: Default::default(),
             index: Default::default(),
         }
     }
@@ -46,13 +48,16 @@ impl MongoDBPrepared {
     }
     pub(crate) fn take_params(&mut self) -> Result<Option<Document>> {
         self.index = 0;
-        if self.count == 0 {
+        if self.count == 0 && self.named_params.is_empty() {
             Ok(None)
         } else {
             let mut doc = Document::new();
             for (i, v) in mem::take(&mut self.params).into_iter().enumerate() {
                 doc.insert(format!("param_{i}"), value_to_bson(&v)?);
             }
+            for (name, v) in mem::take(&mut self.named_params) {
+                doc.insert(format!("param_{name}"), value_to_bson(&v)?);
+            }
             Ok(Some(doc))
         }
     }
@@ -64,6 +69,7 @@ impl Prepared for MongoDBPrepared {
     }
     fn clear_bindings(&mut self) -> Result<&mut Self> {
         self.params.clear();
+        self.named_params.clear();
         self.index = 0;
         Ok(self)
     }
@@ -82,6 +88,20 @@ impl Prepared for MongoDBPrepared {
         self.index = index + 1;
         Ok(self)
     }
+    fn bind_named(&mut self, name: &str, value: impl AsValue) -> Result<&mut Self> {
+        if !self.payload.has_parameter(name) {
+            return Err(anyhow!(
+                "The query does not have a parameter named `{name}`"
+            ));
+        }
+        // The payload references it as `$$param_{name}`, no index mapping needed
+        let value = value.as_value();
+        match self.named_params.iter_mut().find(|(k, _)| k == name) {
+            Some((_, target)) => *target = value,
+            None => self.named_params.push((name.to_string(), value)),
+        }
+        Ok(self)
+    }
     fn is_empty(&self) -> bool {
         self.payload.is_empty()
     }
@@ -1,24 +1,24 @@
 use crate::{
     AggregatePayload, BatchPayload, CreateCollectionPayload, CreateDatabasePayload, DeletePayload,
     DropCollectionPayload, DropDatabasePayload, FieldType, FindManyPayload, FindOnePayload,
-    InsertManyPayload, InsertOnePayload, IsField, MongoDBDriver, MongoDBPrepared, NegateNumber,
-    Payload, RowWrap, UpsertPayload, WriteMatchExpression, glob_to_regex, like_to_regex,
-    value_to_bson,
+    InsertManyPayload, InsertOnePayload, IsField, JsonSchemaRules, MongoDBDriver, MongoDBPrepared,
+    NegateNumber, Payload, RowWrap, UpsertPayload, WriteMatchExpression, glob_to_regex,
+    like_to_regex, value_to_bson,
 };
 use mongodb::{
-    Namespace,
+    IndexModel, Namespace,
     bson::{self, Binary, Bson, Document, Regex, doc, spec::BinarySubtype},
     options::{
         AggregateOptions, CreateCollectionOptions, DeleteOptions, FindOneOptions, FindOptions,
-        InsertManyOptions, InsertOneOptions, UpdateModifications, UpdateOptions,
+        IndexOptions, InsertManyOptions, InsertOneOptions, UpdateModifications, UpdateOptions,
     },
 };
 use std::{borrow::Cow, collections::HashMap, f64, iter, mem, ops::Deref, sync::Arc};
 use tank_core::{
     AsEntity, AsValue, BinaryOp, BinaryOpType, ColumnRef, Context, Dataset, DynQuery, Entity,
-    ErrorContext, Expression, FindOrder, Fragment, Interval, IsAggregateFunction, IsAsterisk,
-    IsConstant, Operand, Order, SelectQuery, SqlWriter, TableRef, UnaryOp, UnaryOpType, Value,
-    truncate_long,
+    ErrorContext, Expression, FieldAccess, FindOrder, Fragment, Interval, IsAggregateFunction,
+    IsAsterisk, IsConstant, Operand, Order, SelectQuery, SqlWriter, TableRef, UnaryOp, UnaryOpType,
+    Value, truncate_long,
 };
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 use uuid::Uuid;
@@ -443,6 +443,28 @@ impl SqlWriter for MongoDBSqlWriter {
         *target = Bson::Document(doc);
     }
 
+    fn write_field_access(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &FieldAccess<&dyn Expression>,
+    ) {
+        value.expression.write_query(self, context, out);
+        let Some(Bson::String(path)) = out
+            .as_prepared::<MongoDBDriver>()
+            .and_then(MongoDBPrepared::current_bson)
+        else {
+            log::error!(
+                "MongoDB can access the fields of a subdocument only if it is a field path (like `$address`)"
+            );
+            return;
+        };
+        for field in value.fields {
+            path.push('.');
+            path.push_str(field);
+        }
+    }
+
     fn write_question_mark(&self, context: &mut Context, out: &mut DynQuery) {
         let Some(target) = out
             .as_prepared::<MongoDBDriver>()
@@ -457,6 +479,17 @@ impl SqlWriter for MongoDBSqlWriter {
         context.counter += 1;
     }
 
+    fn write_named_parameter(&self, _context: &mut Context, out: &mut DynQuery, name: &str) {
+        let Some(target) = out
+            .as_prepared::<MongoDBDriver>()
+            .and_then(MongoDBPrepared::current_bson)
+        else {
+            log::error!("Failed to get the bson in MongoDBSqlWriter::write_named_parameter");
+            return;
+        };
+        *target = Bson::String(format!("$$param_{name}"));
+    }
+
     fn write_current_timestamp_ms(&self, _context: &mut Context, out: &mut DynQuery) {
         let Some(target) = out
             .as_prepared::<MongoDBDriver>()
@@ -729,14 +762,119 @@ impl SqlWriter for MongoDBSqlWriter {
     {
         let table = E::table().clone();
         let name = table.full_name(self.separator());
+        if let Some(column) = E::columns().iter().find(|c| c.auto_increment) {
+            log::error!(
+                "MongoDB does not support auto increment columns, `{}` must be assigned by the application",
+                column.name()
+            );
+        }
+        if let Some(column) = E::columns().iter().find(|c| c.generated.is_some()) {
+            log::error!(
+                "MongoDB does not support generated columns, `{}` must be computed by the application",
+                column.name()
+            );
+        }
+        let mut indexes = Vec::with_capacity(E::indexes().len());
+        for index in E::indexes() {
+            let keys = index
+                .columns
+                .iter()
+                .map(|(column, order)| {
+                    let value = if !index.using.is_empty() {
+                        // Special index type, for example text or 2dsphere
+                        Bson::String(index.using.to_string())
+                    } else if *order == Order::DESC {
+                        Bson::Int32(-1)
+                    } else {
+                        Bson::Int32(1)
+                    };
+                    (column.name().to_string(), value)
+                })
+                .collect::<Document>();
+            let filter = if let Some(condition) = &index.condition {
+                let mut context = Context::fragment(Fragment::SqlCreateIndex);
+                context.quote_identifiers = false;
+                let mut query = Self::make_prepared();
+                condition().accept_visitor(
+                    &mut WriteMatchExpression::new(),
+                    self,
+                    &mut context,
+                    &mut query,
+                );
+                let Some(Bson::Document(document)) = query
+                    .as_prepared::<MongoDBDriver>()
+                    .and_then(MongoDBPrepared::current_bson)
+                    .map(mem::take)
+                else {
+                    log::error!(
+                        "Failed to get the bson in MongoDBSqlWriter::write_create_table while rendering the condition of the index {}",
+                        index.name(),
+                    );
+                    return;
+                };
+                Some(document)
+            } else {
+                None
+            };
+            indexes.push(
+                IndexModel::builder()
+                    .keys(keys)
+                    .options(
+                        IndexOptions::builder()
+                            .name(index.name.to_string())
+                            .unique(index.unique)
+                            .partial_filter_expression(filter)
+                            .build(),
+                    )
+                    .build(),
+            );
+        }
+        let mut rules = JsonSchemaRules::default();
+        let checks = E::columns()
+            .iter()
+            .filter_map(|c| c.check)
+            .chain(E::check_defs().iter().copied());
+        for check in checks {
+            let mut context = Context::fragment(Fragment::SqlCreateTableCheck);
+            context.quote_identifiers = false;
+            let check = check();
+            let mut current = JsonSchemaRules::default();
+            if check.accept_visitor(&mut current, self, &mut context, &mut Default::default()) {
+                for (name, value) in current.properties {
+                    match (rules.properties.get_mut(&name), value) {
+                        (Some(Bson::Document(property)), Bson::Document(value)) => {
+                            property.extend(value)
+                        }
+                        (_, value) => {
+                            rules.properties.insert(name, value);
+                        }
+                    }
+                }
+            } else {
+                log::warn!(
+                    "The check `{}` on {name} cannot be expressed as a MongoDB $jsonSchema validator, it is ignored",
+                    check.as_identifier(&mut context),
+                );
+            }
+        }
+        let mut options = CreateCollectionOptions::builder()
+            .comment(Bson::String(format!("Tank: create collection {name}")))
+            .build();
+        if !rules.properties.is_empty() {
+            options.validator = Some(doc! {
+                "$jsonSchema": {
+                    "bsonType": "object",
+                    "properties": rules.properties,
+                }
+            });
+        }
         Self::prepare_query(
             out,
             &mut Context::empty(),
             CreateCollectionPayload {
                 table: E::table().clone(),
-                options: CreateCollectionOptions::builder()
-                    .comment(Bson::String(format!("Tank: create collection {name}")))
-                    .build(),
+                options,
+                indexes,
             }
             .into(),
         );
@@ -757,6 +895,117 @@ impl SqlWriter for MongoDBSqlWriter {
         );
     }
 
+    fn write_create_view<E>(&self, out: &mut DynQuery, _if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let table = E::table().clone();
+        let name = table.full_name(self.separator());
+        if E::view().is_some_and(|v| v.materialized) {
+            log::warn!("MongoDB does not support materialized views, {name} is a standard view");
+        }
+        let mut query = DynQuery::default();
+        E::write_view_query(self, &mut query);
+        let (source, pipeline) = match query
+            .as_prepared::<MongoDBDriver>()
+            .map(|v| v.get_payload())
+        {
+            Some(Payload::Aggregate(AggregatePayload {
+                table, pipeline, ..
+            })) => (table, pipeline.clone()),
+            Some(Payload::FindMany(FindManyPayload {
+                table,
+                filter,
+                options,
+            })) => {
+                let mut pipeline = Vec::new();
+                if let Bson::Document(filter) = filter
+                    && !filter.is_empty()
+                {
+                    pipeline.push(doc! { "$match": filter.clone() });
+                }
+                if let Some(sort) = &options.sort {
+                    pipeline.push(doc! { "$sort": sort.clone() });
+                }
+                if let Some(limit) = options.limit {
+                    pipeline.push(doc! { "$limit": limit });
+                }
+                if let Some(projection) = &options.projection {
+                    pipeline.push(doc! { "$project": projection.clone() });
+                }
+                (table, pipeline)
+            }
+            Some(Payload::FindOne(FindOnePayload {
+                table,
+                filter,
+                options,
+            })) => {
+                let mut pipeline = Vec::new();
+                if let Bson::Document(filter) = filter
+                    && !filter.is_empty()
+                {
+                    pipeline.push(doc! { "$match": filter.clone() });
+                }
+                if let Some(sort) = &options.sort {
+                    pipeline.push(doc! { "$sort": sort.clone() });
+                }
+                pipeline.push(doc! { "$limit": 1 });
+                if let Some(projection) = &options.projection {
+                    pipeline.push(doc! { "$project": projection.clone() });
+                }
+                (table, pipeline)
+            }
+            _ => {
+                log::error!(
+                    "MongoDB views must be defined by a select query builder, cannot create {name}"
+                );
+                return;
+            }
+        };
+        if source.schema != table.schema {
+            log::error!(
+                "MongoDB views must be in the same database of their source, cannot create {name} on {}",
+                source.full_name(self.separator())
+            );
+            return;
+        }
+        let options = CreateCollectionOptions::builder()
+            .view_on(source.name.to_string())
+            .pipeline(pipeline)
+            .comment(Bson::String(format!("Tank: create view {name}")))
+            .build();
+        Self::prepare_query(
+            out,
+            &mut Context::empty(),
+            CreateCollectionPayload {
+                table,
+                options,
+                indexes: Vec::new(),
+            }
+            .into(),
+        );
+    }
+
+    fn write_drop_view<E>(&self, out: &mut DynQuery, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        self.write_drop_table::<E>(out, if_exists);
+    }
+
+    fn write_refresh_view<E>(&self, _out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "MongoDB does not support materialized views, {} is always up to date",
+            E::table().full_name(self.separator())
+        );
+    }
+
     fn write_select<'a, Data>(&self, out: &mut DynQuery, query: &impl SelectQuery<Data>)
     where
         Self: Sized,
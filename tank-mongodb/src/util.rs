@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use mongodb::bson::{self, Binary, Bson, Document, spec::BinarySubtype};
+use mongodb::bson::{self, Binary, Bson, Document, doc, spec::BinarySubtype};
 use std::{borrow::Cow, cell::OnceCell, collections::HashMap};
 use tank_core::{AsValue, Result, Value};
 use time::PrimitiveDateTime;
@@ -152,6 +152,55 @@ pub fn bson_to_value(bson: &Bson) -> Result<Value> {
     })
 }
 
+/// Returns the `$type` alias of a bson value, the name used by `$jsonSchema` validators.
+pub fn bson_type_name(bson: &Bson) -> &'static str {
+    match bson {
+        Bson::Double(..) => "double",
+        Bson::String(..) => "string",
+        Bson::Array(..) => "array",
+        Bson::Document(..) => "object",
+        Bson::Boolean(..) => "bool",
+        Bson::Null => "null",
+        Bson::RegularExpression(..) => "regex",
+        Bson::JavaScriptCode(..) => "javascript",
+        Bson::JavaScriptCodeWithScope(..) => "javascriptWithScope",
+        Bson::Int32(..) => "int",
+        Bson::Int64(..) => "long",
+        Bson::Timestamp(..) => "timestamp",
+        Bson::Binary(..) => "binData",
+        Bson::ObjectId(..) => "objectId",
+        Bson::DateTime(..) => "date",
+        Bson::Symbol(..) => "symbol",
+        Bson::Decimal128(..) => "decimal",
+        Bson::Undefined => "undefined",
+        Bson::MaxKey => "maxKey",
+        Bson::MinKey => "minKey",
+        Bson::DbPointer(..) => "dbPointer",
+    }
+}
+
+/// Maps a bson `$type` alias to the corresponding value type, see [`bson_to_value`].
+pub fn bson_type_to_value(name: &str) -> Value {
+    match name {
+        "double" => Value::Float64(None),
+        "string" => Value::Varchar(None),
+        "array" => Value::Array(None, Value::Unknown(None).into(), 0),
+        "object" => Value::Map(
+            None,
+            Value::Varchar(None).into(),
+            Value::Unknown(None).into(),
+        ),
+        "bool" => Value::Boolean(None),
+        "int" => Value::Int32(None),
+        "long" => Value::Int64(None),
+        "binData" => Value::Blob(None),
+        "objectId" => Value::UInt128(None),
+        "date" => Value::Timestamp(None),
+        "decimal" => Value::Decimal(None, 0, 0),
+        _ => Value::Unknown(None),
+    }
+}
+
 pub fn bson_is_empty(bson: &Bson) -> bool {
     match bson {
         Bson::Document(document) => document.is_empty(),
@@ -160,6 +209,38 @@ pub fn bson_is_empty(bson: &Bson) -> bool {
     }
 }
 
+/// Replace the `$$param_*` variables with `$literal` values, for operations that cannot use `let`.
+pub fn bson_bind_params(bson: &mut Bson, params: &Document) {
+    match bson {
+        Bson::String(v) => {
+            if let Some(value) = v.strip_prefix("$$").and_then(|v| params.get(v)) {
+                *bson = doc! { "$literal": value.clone() }.into();
+            }
+        }
+        Bson::Document(document) => {
+            for (_, v) in document.iter_mut() {
+                bson_bind_params(v, params);
+            }
+        }
+        Bson::Array(array) => {
+            for v in array {
+                bson_bind_params(v, params);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// True if the bson references the `$$param_{name}` variable.
+pub fn bson_has_param(bson: &Bson, name: &str) -> bool {
+    match bson {
+        Bson::String(v) => v.strip_prefix("$$param_") == Some(name),
+        Bson::Document(document) => document.values().any(|v| bson_has_param(v, name)),
+        Bson::Array(array) => array.iter().any(|v| bson_has_param(v, name)),
+        _ => false,
+    }
+}
+
 pub fn like_to_regex(like_pattern: &str) -> String {
     let mut regex = String::with_capacity(like_pattern.len() * 2 + 2);
     regex.push('^');
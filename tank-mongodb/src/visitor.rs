@@ -203,6 +203,7 @@ impl<'a> ExpressionVisitor for WriteMatchExpression<'a> {
                     | Operand::Call(..)
                     | Operand::Asterisk
                     | Operand::QuestionMark
+                    | Operand::NamedParameter(..)
             ) {
                 is_expr = true;
             }
@@ -686,3 +687,72 @@ impl ExpressionVisitor for NegateNumber {
         false
     }
 }
+
+/// Translates a check constraint into `$jsonSchema` property rules.
+///
+/// Returns `false` when the expression cannot be represented.
+#[derive(Default, Debug)]
+pub struct JsonSchemaRules {
+    pub properties: Document,
+}
+impl ExpressionVisitor for JsonSchemaRules {
+    fn visit_binary_op(
+        &mut self,
+        writer: &dyn SqlWriter,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &BinaryOp<&dyn Expression, &dyn Expression>,
+    ) -> bool {
+        if value.op == BinaryOpType::And {
+            return value.lhs.accept_visitor(self, writer, context, out)
+                && value.rhs.accept_visitor(self, writer, context, out);
+        }
+        let mut is_field = IsField::default();
+        if !value
+            .lhs
+            .accept_visitor(&mut is_field, writer, context, out)
+            || !value
+                .rhs
+                .accept_visitor(&mut IsConstant, writer, context, out)
+        {
+            return false;
+        }
+        let name = match is_field.field {
+            FieldType::Identifier(v) => v,
+            FieldType::Column(v) => v.name.to_string(),
+            FieldType::None => return false,
+        };
+        let mut query = MongoDBSqlWriter::make_prepared();
+        value.rhs.write_query(writer, context, &mut query);
+        let Some(bson) = query
+            .as_prepared::<MongoDBDriver>()
+            .and_then(MongoDBPrepared::current_bson)
+            .map(mem::take)
+        else {
+            return false;
+        };
+        let is_number = matches!(
+            bson,
+            Bson::Int32(..) | Bson::Int64(..) | Bson::Double(..) | Bson::Decimal128(..)
+        );
+        let rules = match value.op {
+            BinaryOpType::Equal => doc! { "enum": [bson] },
+            BinaryOpType::In if matches!(bson, Bson::Array(..)) => doc! { "enum": bson },
+            BinaryOpType::GreaterEqual if is_number => doc! { "minimum": bson },
+            BinaryOpType::Greater if is_number => {
+                doc! { "minimum": bson, "exclusiveMinimum": true }
+            }
+            BinaryOpType::LessEqual if is_number => doc! { "maximum": bson },
+            BinaryOpType::Less if is_number => doc! { "maximum": bson, "exclusiveMaximum": true },
+            _ => return false,
+        };
+        let property = self
+            .properties
+            .entry(name)
+            .or_insert_with(|| Document::new().into());
+        if let Bson::Document(property) = property {
+            property.extend(rules);
+        }
+        true
+    }
+}
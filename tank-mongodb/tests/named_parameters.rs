@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use mongodb::bson::{Bson, doc};
+    use tank::{Driver, DynQuery, Entity, Prepared, SqlWriter, expr};
+    use tank_mongodb::{DeletePayload, MongoDBDriver, Payload};
+    use tank_tests::init_logs;
+
+    const DRIVER: MongoDBDriver = MongoDBDriver {};
+
+    #[derive(Entity)]
+    #[tank(schema = "test_db", name = "items")]
+    struct Item {
+        #[tank(primary_key)]
+        pub id: i64,
+        pub name: String,
+    }
+
+    #[test]
+    fn named_parameters_bind() {
+        init_logs();
+        let writer = DRIVER.sql_writer();
+        let mut query = DynQuery::default();
+        writer.write_delete::<Item>(&mut query, expr!(Item::name == :name && Item::id > :id));
+        let prepared = query
+            .as_prepared::<MongoDBDriver>()
+            .expect("Expected a prepared query");
+        let Payload::Delete(DeletePayload { filter, .. }) = prepared.get_payload() else {
+            panic!("Expected a delete payload");
+        };
+        assert_eq!(
+            *filter,
+            Bson::Document(doc! {
+                "$expr": {
+                    "$and": [
+                        { "$eq": ["$name", "$$param_name"] },
+                        { "$gt": ["$id", "$$param_id"] },
+                    ]
+                }
+            })
+        );
+        assert!(prepared.bind_named("missing", 1).is_err());
+        prepared
+            .bind_named("name", "first")
+            .expect("Failed to bind name")
+            .bind_named("id", 5)
+            .expect("Failed to bind id");
+        assert!(prepared.clear_bindings().is_ok());
+        assert!(prepared.bind_named("name", "second").is_ok());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{MySQLDriver, MySQLQueryable, MySQLTransaction};
 use anyhow::anyhow;
 use core::fmt;
-use mysql_async::{ClientIdentity, Conn, Opts, OptsBuilder};
+use mysql_async::{ClientIdentity, Conn, Opts, OptsBuilder, prelude::Queryable};
 use std::{borrow::Cow, env, fmt::Debug, path::PathBuf};
 use tank_core::{Connection, ErrorContext, Result, impl_executor_transaction};
 
@@ -87,6 +87,24 @@ impl Connection for MySQLConnection {
     fn begin(&mut self) -> impl Future<Output = Result<MySQLTransaction<'_>>> + Send {
         MySQLTransaction::new(self)
     }
+
+    async fn ping(&mut self) -> Result<()> {
+        self.conn
+            .executor
+            .ping()
+            .await
+            .context("While pinging MySQL")?;
+        Ok(())
+    }
+
+    async fn reset(&mut self) -> Result<bool> {
+        Ok(self
+            .conn
+            .executor
+            .reset()
+            .await
+            .context("While resetting the MySQL session")?)
+    }
 }
 
 impl Debug for MySQLConnection {
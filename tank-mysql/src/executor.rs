@@ -1,8 +1,9 @@
 use crate::{MySQLDriver, MySQLPrepared, RowWrap};
 use async_stream::try_stream;
+use mysql_async::{Conn, Transaction, consts::StatusFlags, prelude::Queryable};
 use std::sync::Arc;
 use tank_core::{
-    AsQuery, Error, Executor, Query, RawQuery, Result,
+    AsQuery, Error, ErrorContext, Executor, Params, Query, RawQuery, Result, RowsAffected,
     stream::{Stream, StreamExt, TryStreamExt},
 };
 
@@ -11,7 +12,28 @@ pub(crate) struct MySQLQueryable<T: mysql_async::prelude::Queryable> {
     pub(crate) driver: MySQLDriver,
 }
 
-impl<T: mysql_async::prelude::Queryable + Send> Executor for MySQLQueryable<T> {
+/// A `mysql_async` connection or transaction the executor runs on.
+pub(crate) trait MySQLSession: Queryable + Send {
+    /// Whether the statements already run inside a transaction.
+    fn in_transaction(&self) -> bool;
+}
+
+impl MySQLSession for Conn {
+    fn in_transaction(&self) -> bool {
+        self.last_ok_packet().is_some_and(|v| {
+            v.status_flags()
+                .contains(StatusFlags::SERVER_STATUS_IN_TRANS)
+        })
+    }
+}
+
+impl MySQLSession for Transaction<'_> {
+    fn in_transaction(&self) -> bool {
+        true
+    }
+}
+
+impl<T: MySQLSession> Executor for MySQLQueryable<T> {
     type Driver = MySQLDriver;
 
     fn driver(&self) -> MySQLDriver {
@@ -65,4 +87,44 @@ impl<T: mysql_async::prelude::Queryable + Send> Executor for MySQLQueryable<T> {
             error
         })
     }
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<MySQLDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let context = format!("While executing a batch of the query:\n{query}");
+        let Query::Prepared(prepared) = query else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        let len = params.len();
+        let mut values = Vec::with_capacity(len);
+        for params in params {
+            params.bind(prepared).context(context.clone())?;
+            values.push(prepared.take_params()?);
+        }
+        let transaction = !self.executor.in_transaction();
+        async {
+            if transaction {
+                self.executor.query_drop("START TRANSACTION").await?;
+            }
+            // Sent as a single bulk operation when the server supports it (MariaDB)
+            let result = self.executor.exec_batch(&prepared.statement, values).await;
+            match (result, transaction) {
+                (Ok(()), true) => self.executor.query_drop("COMMIT").await,
+                (Err(error), true) => {
+                    let _ = self.executor.query_drop("ROLLBACK").await;
+                    Err(error)
+                }
+                (result, false) => result,
+            }
+        }
+        .await
+        .map_err(|e| {
+            let error = Error::new(e).context(context);
+            log::error!("{error:#}");
+            error
+        })?;
+        // The driver reports the affected rows of the last execution only
+        Ok(vec![RowsAffected::default(); len])
+    }
 }
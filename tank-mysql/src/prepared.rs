@@ -6,7 +6,7 @@ use std::{
     fmt::{self, Debug, Display},
     mem,
 };
-use tank_core::{AsValue, Prepared, Result, Value};
+use tank_core::{AsValue, ParameterNames, Prepared, Result, Value};
 
 #[derive(Debug)]
 /// Prepared statement wrapper for MySQL/MariaDB.
@@ -17,6 +17,7 @@ pub struct MySQLPrepared {
     pub(crate) statement: Statement,
     pub(crate) params: Vec<Value>,
     pub(crate) index: u64,
+    pub(crate) names: ParameterNames,
 }
 
 impl MySQLPrepared {
@@ -25,6 +26,7 @@ impl MySQLPrepared {
             statement,
             params: Vec::new(),
             index: 0,
+            names: Default::default(),
         }
     }
     pub(crate) fn take_params(&mut self) -> Result<mysql_async::Params> {
@@ -62,6 +64,13 @@ impl Prepared for MySQLPrepared {
         self.index = index + 1;
         Ok(self)
     }
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        Some(&self.names)
+    }
+    fn set_parameter_names(&mut self, names: ParameterNames) -> Result<()> {
+        self.names = names;
+        Ok(())
+    }
 }
 
 impl Display for MySQLPrepared {
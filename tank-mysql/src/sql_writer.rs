@@ -3,8 +3,9 @@ use std::{
     fmt::Write,
 };
 use tank_core::{
-    ColumnDef, Context, DynQuery, EitherIterator, Entity, Error, Expression, Fragment,
-    GenericSqlWriter, Interval, PrimaryKeyType, SqlWriter, Value, separated_by, write_escaped,
+    ColumnChanges, ColumnDef, Context, DynQuery, EitherIterator, Entity, EnumDef, Error,
+    Expression, FieldAccess, Fragment, GenericSqlWriter, IndexDef, Interval, Order, PrimaryKeyType,
+    SqlWriter, TableRef, Value, separated_by, write_escaped,
 };
 use time::{OffsetDateTime, PrimitiveDateTime};
 
@@ -36,6 +37,15 @@ impl SqlWriter for MySQLSqlWriter {
         self
     }
 
+    fn named_parameter_prefixes(&self) -> &'static [char] {
+        // `@name` is a user variable
+        &[':', '$']
+    }
+
+    fn string_backslash_escapes(&self) -> bool {
+        true
+    }
+
     fn write_identifier(
         &self,
         _context: &mut Context,
@@ -133,6 +143,7 @@ impl SqlWriter for MySQLSqlWriter {
             Value::List(..) => out.push_str("JSON"),
             Value::Map(..) => out.push_str("JSON"),
             Value::Json(..) => out.push_str("JSON"),
+            Value::Struct(..) => out.push_str("JSON"),
             _ => log::error!("Unexpected tank::Value, MySQL does not support {value:?}"),
         };
     }
@@ -286,6 +297,113 @@ impl SqlWriter for MySQLSqlWriter {
         }
     }
 
+    fn write_struct(&self, context: &mut Context, out: &mut DynQuery, value: &[(String, Value)]) {
+        let inside_string = context.fragment == Fragment::Json;
+        let mut context = context.switch_fragment(Fragment::Json);
+        if !inside_string {
+            out.push('\'');
+        }
+        out.push('{');
+        separated_by(
+            out,
+            value,
+            |out, (k, v)| {
+                {
+                    let mut context = context.current.switch_fragment(Fragment::JsonKey);
+                    self.write_string(&mut context.current, out, k);
+                }
+                out.push(':');
+                self.write_value(&mut context.current, out, v);
+            },
+            ",",
+        );
+        out.push('}');
+        if !inside_string {
+            out.push('\'');
+        }
+    }
+
+    fn write_field_access(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &FieldAccess<&dyn Expression>,
+    ) {
+        out.push_str("JSON_VALUE(");
+        value.expression.write_query(self, context, out);
+        out.push_str(", '$");
+        for field in value.fields {
+            let _ = write!(out, ".\"{field}\"");
+        }
+        out.push_str("')");
+    }
+
+    fn write_describe_table(&self, out: &mut DynQuery, table: &TableRef) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.buffer().reserve(1536);
+        out.push_str(
+            "SELECT c.COLUMN_NAME AS column_name, c.COLUMN_TYPE AS data_type, c.IS_NULLABLE AS is_nullable,\n\
+            c.COLUMN_DEFAULT AS column_default,\n\
+            (SELECT COUNT(*) FROM information_schema.KEY_COLUMN_USAGE p WHERE c.COLUMN_KEY = 'PRI' AND p.CONSTRAINT_NAME = 'PRIMARY'\n\
+            AND p.TABLE_SCHEMA = c.TABLE_SCHEMA AND p.TABLE_NAME = c.TABLE_NAME) AS primary_key,\n\
+            EXISTS (SELECT 1 FROM information_schema.STATISTICS s WHERE s.NON_UNIQUE = 0 AND s.INDEX_NAME <> 'PRIMARY'\n\
+            AND s.TABLE_SCHEMA = c.TABLE_SCHEMA AND s.TABLE_NAME = c.TABLE_NAME AND s.COLUMN_NAME = c.COLUMN_NAME\n\
+            AND (SELECT COUNT(*) FROM information_schema.STATISTICS i WHERE i.TABLE_SCHEMA = s.TABLE_SCHEMA AND i.TABLE_NAME = s.TABLE_NAME AND i.INDEX_NAME = s.INDEX_NAME) = 1\n\
+            ) AS is_unique,\n\
+            k.REFERENCED_TABLE_SCHEMA AS referenced_schema, k.REFERENCED_TABLE_NAME AS referenced_table, k.REFERENCED_COLUMN_NAME AS referenced_column,\n\
+            r.DELETE_RULE AS on_delete, r.UPDATE_RULE AS on_update, c.COLUMN_COMMENT AS comment\n\
+            FROM information_schema.COLUMNS c\n\
+            LEFT JOIN information_schema.KEY_COLUMN_USAGE k ON k.TABLE_SCHEMA = c.TABLE_SCHEMA AND k.TABLE_NAME = c.TABLE_NAME\n\
+            AND k.COLUMN_NAME = c.COLUMN_NAME AND k.REFERENCED_TABLE_NAME IS NOT NULL\n\
+            LEFT JOIN information_schema.REFERENTIAL_CONSTRAINTS r ON r.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA AND r.CONSTRAINT_NAME = k.CONSTRAINT_NAME\n\
+            WHERE c.TABLE_NAME = ",
+        );
+        self.write_string(&mut context, out, &table.name);
+        out.push_str(" AND c.TABLE_SCHEMA = COALESCE(NULLIF(");
+        self.write_string(&mut context, out, &table.schema);
+        out.push_str(", ''), DATABASE())\nORDER BY c.ORDINAL_POSITION;");
+    }
+
+    fn write_list_tables(&self, out: &mut DynQuery, schema: &str) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.push_str(
+            "SELECT TABLE_SCHEMA AS table_schema, TABLE_NAME AS table_name FROM information_schema.TABLES \
+            WHERE TABLE_TYPE = 'BASE TABLE' AND TABLE_SCHEMA = COALESCE(NULLIF(",
+        );
+        self.write_string(&mut context, out, schema);
+        out.push_str(", ''), DATABASE()) ORDER BY TABLE_NAME;");
+    }
+
+    fn write_advisory_lock(&self, out: &mut DynQuery, name: &str) {
+        let mut context = Context::new(Fragment::SqlSelect, false);
+        out.push_str("SELECT GET_LOCK(");
+        self.write_string(&mut context, out, name);
+        out.push_str(", -1);");
+    }
+
+    fn write_advisory_unlock(&self, out: &mut DynQuery, name: &str) {
+        let mut context = Context::new(Fragment::SqlSelect, false);
+        out.push_str("SELECT RELEASE_LOCK(");
+        self.write_string(&mut context, out, name);
+        out.push_str(");");
+    }
+
+    fn parse_column_type(&self, name: &str) -> Value {
+        if name.trim().eq_ignore_ascii_case("tinyint(1)") {
+            return Value::Boolean(None);
+        }
+        GenericSqlWriter::new().parse_column_type(name)
+    }
+
+    fn write_create_table_auto_increment_fragment(
+        &self,
+        _context: &mut Context,
+        out: &mut DynQuery,
+        _column: &ColumnDef,
+    ) {
+        out.push_str(" AUTO_INCREMENT");
+    }
+
     fn write_column_comment_inline(
         &self,
         mut context: &mut Context,
@@ -305,6 +423,170 @@ impl SqlWriter for MySQLSqlWriter {
     {
     }
 
+    fn is_native_enum(&self, enum_def: &EnumDef) -> bool {
+        enum_def.is_textual()
+    }
+
+    fn write_column_enum_type(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        enum_def: &EnumDef,
+    ) {
+        out.push_str("ENUM(");
+        separated_by(
+            out,
+            enum_def.values,
+            |out, value| self.write_value(context, out, value),
+            ", ",
+        );
+        out.push(')');
+    }
+
+    fn write_create_view<E>(&self, out: &mut DynQuery, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if E::view().is_some_and(|v| v.materialized) {
+            log::warn!(
+                "MySQL does not support materialized views, {} is a standard view",
+                E::table().full_name(self.separator())
+            );
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // MySQL has no IF NOT EXISTS for views, an existing one is replaced by the entity definition
+        out.push_str(if if_not_exists {
+            "CREATE OR REPLACE VIEW "
+        } else {
+            "CREATE VIEW "
+        });
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS");
+        E::write_view_query(self, out);
+        if !out.as_str().ends_with(';') {
+            out.push(';');
+        }
+    }
+
+    fn write_drop_view<E>(&self, out: &mut DynQuery, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("DROP VIEW ");
+        if if_exists {
+            out.push_str("IF EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlDropView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push(';');
+    }
+
+    fn write_refresh_view<E>(&self, _out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "MySQL does not support materialized views, {} is always up to date",
+            E::table().full_name(self.separator())
+        );
+    }
+
+    fn write_create_index<E>(&self, out: &mut DynQuery, index: &IndexDef, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if index.condition.is_some() {
+            log::error!(
+                "MySQL/MariaDB does not support partial indexes, cannot create {} on {}",
+                index.name(),
+                E::table().full_name(self.separator()),
+            );
+            return;
+        }
+        let mut context = Context::new(Fragment::SqlCreateIndex, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE ");
+        let kind = ["FULLTEXT", "SPATIAL"]
+            .into_iter()
+            .find(|v| v.eq_ignore_ascii_case(index.using));
+        if index.unique {
+            out.push_str("UNIQUE ");
+        } else if let Some(kind) = kind {
+            out.push_str(kind);
+            out.push(' ');
+        }
+        out.push_str("INDEX ");
+        if if_not_exists && self.mariadb {
+            // MySQL does not have IF NOT EXISTS for indexes
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_identifier(&mut context, out, index.name(), true);
+        if !index.using.is_empty() && kind.is_none() {
+            out.push_str(" USING ");
+            out.push_str(index.using);
+        }
+        out.push_str(" ON ");
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" (");
+        separated_by(
+            out,
+            index.columns.iter(),
+            |out, (column, order)| {
+                self.write_identifier(&mut context, out, column.name(), true);
+                if *order == Order::DESC {
+                    out.push_str(" DESC");
+                }
+            },
+            ", ",
+        );
+        out.push_str(");");
+    }
+
+    fn write_alter_table_alter_column(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+        _changes: &ColumnChanges,
+    ) where
+        Self: Sized,
+    {
+        // MODIFY COLUMN restates the whole definition, the constraints are left untouched
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" MODIFY COLUMN ");
+        self.write_identifier(&mut context, out, column.name(), true);
+        out.push(' ');
+        self.write_alter_table_column_type(&mut context, out, column);
+        if !column.nullable || column.primary_key != PrimaryKeyType::None {
+            out.push_str(" NOT NULL");
+        }
+        if column.default.is_set() {
+            out.push_str(" DEFAULT ");
+            column.default.write_query(self.as_dyn(), &mut context, out);
+        }
+        if !column.comment.is_empty() {
+            self.write_column_comment_inline(&mut context, out, column);
+        }
+        out.push(';');
+    }
+
     fn write_insert_update_fragment<'a, E>(
         &self,
         context: &mut Context,
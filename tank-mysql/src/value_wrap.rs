@@ -160,10 +160,14 @@ impl<'a> TryFrom<ValueWrap<'a>> for mysql_async::Value {
                 )
             }
             TankValue::Uuid(Some(v), ..) => MySQLValue::from(v.to_string()),
+            TankValue::Json(Some(v), ..) => MySQLValue::from(v.to_string()),
             // TankValue::Array(Some(v), ..) => MySQLValue::from(v),
             // TankValue::List(Some(v), ..) => MySQLValue::from(v),
             // TankValue::Map(Some(v), ..) => MySQLValue::from(v),
-            // TankValue::Struct(Some(v), ..) => MySQLValue::from(v),
+            v @ TankValue::Struct(Some(..), ..) => match tank_core::value_to_json(&v) {
+                Some(json) => MySQLValue::from(json.to_string()),
+                None => return Err(anyhow!("Could not convert `{v:?}` to JSON").into()),
+            },
             TankValue::Unknown(Some(v), ..) => MySQLValue::from(v),
             v => {
                 return Err(
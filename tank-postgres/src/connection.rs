@@ -1,8 +1,8 @@
 use crate::{
     PostgresDriver, PostgresPrepared, PostgresTransaction, ValueWrap,
     util::{
-        postgres_type_to_value, stream_postgres_row_to_tank_row,
-        stream_postgres_simple_query_message_to_tank_query_result, value_to_postgres_type,
+        column_to_postgres_type, execute_pipelined, postgres_type_to_value,
+        stream_postgres_row_to_tank_row, stream_postgres_simple_query_message_to_tank_query_result,
     },
 };
 use async_stream::try_stream;
@@ -17,8 +17,8 @@ use std::{
     str::FromStr,
 };
 use tank_core::{
-    AsEntity, AsQuery, Connection, Driver, DynQuery, Entity, Error, ErrorContext, Executor, Query,
-    QueryResult, RawQuery, Result, RowsAffected, SqlWriter, Transaction,
+    AsEntity, AsQuery, Connection, Driver, DynQuery, Entity, Error, ErrorContext, Executor, Params,
+    Query, QueryResult, RawQuery, Result, RowsAffected, SqlWriter, Transaction, Value,
     future::Either,
     stream::{Stream, StreamExt, TryStreamExt},
     truncate_long,
@@ -137,6 +137,24 @@ impl Executor for PostgresConnection {
         })
     }
 
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<PostgresDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let context = format!("While executing a batch of the query:\n{query}");
+        let Query::Prepared(prepared) = query else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        execute_pipelined(&self.client, prepared, params)
+            .await
+            .map_err(|e| {
+                let error = e.context(context);
+                log::error!("{error:#}");
+                error
+            })
+    }
+
     async fn append<It>(&mut self, entities: It) -> Result<RowsAffected>
     where
         It: IntoIterator + Send,
@@ -144,8 +162,16 @@ impl Executor for PostgresConnection {
         It::Item: AsEntity,
     {
         type E<It> = <<It as IntoIterator>::Item as AsEntity>::Entity;
-        let mut iter = entities.into_iter();
         let writer = self.driver().sql_writer();
+        if E::<It>::columns().iter().any(|c| {
+            c.auto_increment || c.generated.is_some() || matches!(c.value, Value::Struct(..))
+        }) {
+            // COPY cannot leave the generated values to the database nor encode composite types
+            let mut query = DynQuery::default();
+            writer.write_insert(&mut query, entities, false);
+            return self.execute(query).await;
+        }
+        let mut iter = entities.into_iter();
         let context = || {
             format!(
                 "While appending to the table `{}`",
@@ -168,7 +194,7 @@ impl Executor for PostgresConnection {
         };
         let types: Vec<_> = E::<It>::columns()
             .into_iter()
-            .map(|c| value_to_postgres_type(&c.value))
+            .map(column_to_postgres_type)
             .collect();
         let writer = BinaryCopyInWriter::new(sink, &types);
         let mut writer = pin!(writer);
@@ -298,6 +324,25 @@ impl Connection for PostgresConnection {
         PostgresTransaction::new(self)
     }
 
+    async fn ping(&mut self) -> Result<()> {
+        if self.client.is_closed() {
+            return Err(Error::msg("The Postgres connection is closed"));
+        }
+        self.client
+            .simple_query("")
+            .await
+            .context("While pinging Postgres")?;
+        Ok(())
+    }
+
+    async fn reset(&mut self) -> Result<bool> {
+        self.client
+            .batch_execute("DISCARD ALL")
+            .await
+            .context("While resetting the Postgres session")?;
+        Ok(true)
+    }
+
     async fn disconnect(self) -> Result<()> {
         drop(self.client);
         if let Err(e) = self.handle.await {
@@ -20,4 +20,8 @@ impl Driver for PostgresDriver {
     fn sql_writer(&self) -> PostgresSqlWriter {
         PostgresSqlWriter {}
     }
+
+    fn supports_transactional_ddl(&self) -> bool {
+        true
+    }
 }
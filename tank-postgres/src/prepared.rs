@@ -5,7 +5,7 @@ use std::{
     fmt::{self, Debug, Display},
     mem,
 };
-use tank_core::{AsValue, Prepared, Result, Value};
+use tank_core::{AsValue, ParameterNames, Prepared, Result, Value};
 use tokio_postgres::Statement;
 
 /// Postgres prepared statement.
@@ -14,6 +14,7 @@ pub struct PostgresPrepared {
     pub(crate) statement: Statement,
     pub(crate) params: Vec<Value>,
     pub(crate) index: u64,
+    pub(crate) names: ParameterNames,
 }
 
 impl PostgresPrepared {
@@ -22,6 +23,7 @@ impl PostgresPrepared {
             statement,
             params: Vec::new(),
             index: 0,
+            names: Default::default(),
         }
     }
 
@@ -59,6 +61,13 @@ impl Prepared for PostgresPrepared {
         self.index = index + 1;
         Ok(self)
     }
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        Some(&self.names)
+    }
+    fn set_parameter_names(&mut self, names: ParameterNames) -> Result<()> {
+        self.names = names;
+        Ok(())
+    }
 }
 
 impl Display for PostgresPrepared {
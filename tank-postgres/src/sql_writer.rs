@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, fmt::Write};
 use tank_core::{
-    ColumnDef, Context, Dataset, DynQuery, Entity, Expression, Fragment, SqlWriter, Value,
-    separated_by,
+    ColumnChanges, ColumnDef, Context, Dataset, DynQuery, Entity, EnumDef, Expression, Fragment,
+    PrimaryKeyType, SqlWriter, TableRef, Value, separated_by,
 };
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 
@@ -93,6 +93,9 @@ impl SqlWriter for PostgresSqlWriter {
                 self.write_column_type(context, out, inner);
                 out.push_str("[]");
             }
+            Value::Struct(.., name) if !name.name.is_empty() => {
+                self.write_table_ref(context, out, name)
+            }
             Value::Map(..) | Value::Json(..) | Value::Struct(..) => out.push_str("JSON"),
             _ => log::error!("Unexpected tank::Value, Postgres does not support {value:?}"),
         };
@@ -220,6 +223,256 @@ impl SqlWriter for PostgresSqlWriter {
         }
     }
 
+    fn write_struct(&self, context: &mut Context, out: &mut DynQuery, value: &[(String, Value)]) {
+        out.push_str("ROW(");
+        separated_by(
+            out,
+            value,
+            |out, (_, v)| self.write_value(context, out, v),
+            ", ",
+        );
+        out.push(')');
+    }
+
+    fn write_describe_table(&self, out: &mut DynQuery, table: &TableRef) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.buffer().reserve(1536);
+        out.push_str(
+            "SELECT a.attname::text AS column_name, format_type(a.atttypid, a.atttypmod) AS data_type,\n\
+            NOT a.attnotnull AS is_nullable, pg_get_expr(d.adbin, d.adrelid) AS column_default,\n\
+            COALESCE((SELECT cardinality(p.conkey) FROM pg_constraint p WHERE p.conrelid = c.oid AND p.contype = 'p' AND a.attnum = ANY(p.conkey)), 0) AS primary_key,\n\
+            EXISTS (SELECT 1 FROM pg_constraint u WHERE u.conrelid = c.oid AND u.contype = 'u' AND u.conkey = ARRAY[a.attnum]) AS is_unique,\n\
+            rn.nspname::text AS referenced_schema, rc.relname::text AS referenced_table, ra.attname::text AS referenced_column,\n\
+            CASE f.confdeltype WHEN 'a' THEN 'NO ACTION' WHEN 'r' THEN 'RESTRICT' WHEN 'c' THEN 'CASCADE' WHEN 'n' THEN 'SET NULL' WHEN 'd' THEN 'SET DEFAULT' END AS on_delete,\n\
+            CASE f.confupdtype WHEN 'a' THEN 'NO ACTION' WHEN 'r' THEN 'RESTRICT' WHEN 'c' THEN 'CASCADE' WHEN 'n' THEN 'SET NULL' WHEN 'd' THEN 'SET DEFAULT' END AS on_update,\n\
+            col_description(c.oid, a.attnum) AS comment\n\
+            FROM pg_attribute a\n\
+            JOIN pg_class c ON c.oid = a.attrelid\n\
+            JOIN pg_namespace n ON n.oid = c.relnamespace\n\
+            LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum\n\
+            LEFT JOIN pg_constraint f ON f.conrelid = c.oid AND f.contype = 'f' AND a.attnum = ANY(f.conkey)\n\
+            LEFT JOIN pg_class rc ON rc.oid = f.confrelid\n\
+            LEFT JOIN pg_namespace rn ON rn.oid = rc.relnamespace\n\
+            LEFT JOIN pg_attribute ra ON ra.attrelid = f.confrelid AND ra.attnum = f.confkey[array_position(f.conkey, a.attnum)]\n\
+            WHERE a.attnum > 0 AND NOT a.attisdropped AND c.relname = ",
+        );
+        self.write_string(&mut context, out, &table.name);
+        out.push_str(" AND n.nspname = COALESCE(NULLIF(");
+        self.write_string(&mut context, out, &table.schema);
+        out.push_str(", ''), current_schema())\nORDER BY a.attnum;");
+    }
+
+    fn write_list_tables(&self, out: &mut DynQuery, schema: &str) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.push_str(
+            "SELECT table_schema::text, table_name::text FROM information_schema.tables \
+            WHERE table_type = 'BASE TABLE' AND table_schema = COALESCE(NULLIF(",
+        );
+        self.write_string(&mut context, out, schema);
+        out.push_str(", ''), current_schema()) ORDER BY table_name;");
+    }
+
+    fn write_advisory_lock(&self, out: &mut DynQuery, name: &str) {
+        let mut context = Context::new(Fragment::SqlSelect, false);
+        out.push_str("SELECT pg_advisory_lock(hashtext(");
+        self.write_string(&mut context, out, name);
+        out.push_str("));");
+    }
+
+    fn write_advisory_unlock(&self, out: &mut DynQuery, name: &str) {
+        let mut context = Context::new(Fragment::SqlSelect, false);
+        out.push_str("SELECT pg_advisory_unlock(hashtext(");
+        self.write_string(&mut context, out, name);
+        out.push_str("));");
+    }
+
+    fn is_native_enum(&self, enum_def: &EnumDef) -> bool {
+        enum_def.is_textual()
+    }
+
+    fn write_column_enum_type(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        enum_def: &EnumDef,
+    ) {
+        if !enum_def.schema.is_empty() {
+            self.write_identifier(context, out, enum_def.schema, true);
+            out.push('.');
+        }
+        self.write_identifier(context, out, enum_def.name, true);
+    }
+
+    fn write_create_enum_type(&self, out: &mut DynQuery, enum_def: &EnumDef) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // CREATE TYPE has no IF NOT EXISTS and the type can be shared by many tables
+        out.push_str("DO $$ BEGIN CREATE TYPE ");
+        let mut context = Context::new(Fragment::SqlCreateType, false);
+        self.write_column_enum_type(&mut context, out, enum_def);
+        out.push_str(" AS ENUM (");
+        separated_by(
+            out,
+            enum_def.values,
+            |out, value| self.write_value(&mut context, out, value),
+            ", ",
+        );
+        out.push_str("); EXCEPTION WHEN duplicate_object THEN NULL; END $$;");
+    }
+
+    fn write_create_struct_type(&self, out: &mut DynQuery, value: &Value) {
+        let Value::Struct(.., fields, name) = value else {
+            return;
+        };
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("DO $$ BEGIN CREATE TYPE ");
+        let mut context = Context::new(Fragment::SqlCreateType, false);
+        self.write_table_ref(&mut context, out, name);
+        out.push_str(" AS (");
+        separated_by(
+            out,
+            fields,
+            |out, (field, value)| {
+                self.write_identifier(&mut context, out, field, true);
+                out.push(' ');
+                self.write_column_type(&mut context, out, value);
+            },
+            ", ",
+        );
+        out.push_str("); EXCEPTION WHEN duplicate_object THEN NULL; END $$;");
+    }
+
+    fn write_create_view<E>(&self, out: &mut DynQuery, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let materialized = E::view().is_some_and(|v| v.materialized);
+        // Plain views do not support IF NOT EXISTS, and OR REPLACE would overwrite an existing one
+        out.push_str(match (materialized, if_not_exists) {
+            (true, true) => "CREATE MATERIALIZED VIEW IF NOT EXISTS ",
+            (true, false) => "CREATE MATERIALIZED VIEW ",
+            (false, true) => "DO $$ BEGIN CREATE VIEW ",
+            (false, false) => "CREATE VIEW ",
+        });
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS");
+        E::write_view_query(self, out);
+        if !out.as_str().ends_with(';') {
+            out.push(';');
+        }
+        if if_not_exists && !materialized {
+            out.push_str(" EXCEPTION WHEN duplicate_table THEN NULL; END $$;");
+        }
+    }
+
+    fn write_create_table_generated_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        _column: &ColumnDef,
+        generated: &dyn Expression,
+    ) {
+        // Virtual generated columns need Postgres 18, stored ones work on every supported version
+        out.push_str(" GENERATED ALWAYS AS (");
+        generated.write_query(
+            self.as_dyn(),
+            &mut context
+                .switch_fragment(Fragment::SqlCreateTableGenerated)
+                .current,
+            out,
+        );
+        out.push_str(") STORED");
+    }
+
+    fn write_alter_table_alter_column(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+        changes: &ColumnChanges,
+    ) where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        let mut actions = Vec::new();
+        if changes.column_type {
+            // Explicit cast, otherwise an implicit one must exist between the types
+            let mut action = DynQuery::default();
+            action.push_str("TYPE ");
+            self.write_alter_table_column_type(&mut context, &mut action, column);
+            action.push_str(" USING ");
+            self.write_identifier(&mut context, &mut action, column.name(), true);
+            action.push_str("::");
+            self.write_alter_table_column_type(&mut context, &mut action, column);
+            actions.push(action);
+        }
+        if changes.nullable {
+            actions.push(DynQuery::new(
+                if column.nullable && column.primary_key == PrimaryKeyType::None {
+                    "DROP NOT NULL"
+                } else {
+                    "SET NOT NULL"
+                }
+                .into(),
+            ));
+        }
+        if changes.default && column.default.is_set() {
+            let mut action = DynQuery::new("SET DEFAULT ".into());
+            column
+                .default
+                .write_query(self.as_dyn(), &mut context, &mut action);
+            actions.push(action);
+        }
+        if actions.is_empty() {
+            return;
+        }
+        // Postgres applies multiple actions in a single statement
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        separated_by(
+            out,
+            actions,
+            |out, action| {
+                out.push_str(" ALTER COLUMN ");
+                self.write_identifier(&mut context, out, column.name(), true);
+                out.push(' ');
+                out.push_str(&action.as_str());
+            },
+            ",",
+        );
+        out.push(';');
+    }
+
+    fn write_insert_returning_fragment<'a>(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        columns: impl Iterator<Item = &'a ColumnDef>,
+    ) where
+        Self: Sized,
+    {
+        out.push_str("\nRETURNING ");
+        separated_by(
+            out,
+            columns,
+            |out, col| {
+                self.write_identifier(context, out, col.name(), true);
+            },
+            ", ",
+        );
+    }
+
     fn write_question_mark(&self, context: &mut Context, out: &mut DynQuery) {
         context.counter += 1;
         let _ = write!(out, "${}", context.counter);
@@ -1,9 +1,10 @@
 use crate::{
     PostgresConnection, PostgresDriver, PostgresPrepared, ValueWrap,
-    util::stream_postgres_row_to_tank_row,
+    util::{execute_pipelined, stream_postgres_row_to_tank_row},
 };
 use tank_core::{
-    AsQuery, Error, Executor, Query, QueryResult, RawQuery, Result, Transaction,
+    AsQuery, Error, Executor, Params, Query, QueryResult, RawQuery, Result, RowsAffected,
+    Transaction,
     future::{Either, TryFutureExt},
     stream::{Stream, TryStreamExt},
 };
@@ -60,6 +61,22 @@ impl<'c> Executor for PostgresTransaction<'c> {
             e
         })
     }
+
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<PostgresDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let Query::Prepared(prepared) = query else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        execute_pipelined(&self.0, prepared, params)
+            .await
+            .map_err(|e| {
+                log::error!("{e:#}");
+                e
+            })
+    }
 }
 
 impl<'c> Transaction<'c> for PostgresTransaction<'c> {
@@ -1,4 +1,4 @@
-use crate::{ValueWrap, interval_wrap::IntervalWrap};
+use crate::{PostgresPrepared, ValueWrap, interval_wrap::IntervalWrap};
 use anyhow::anyhow;
 use async_stream::try_stream;
 use postgres_protocol::types::{ArrayDimension, array_from_sql};
@@ -6,11 +6,12 @@ use postgres_types::{FromSql, Kind, Type};
 use rust_decimal::Decimal;
 use std::{error::Error, iter, mem, pin::pin};
 use tank_core::{
-    ErrorContext, Value,
+    ColumnDef, ErrorContext, Params, RowsAffected, TableRef, Value,
+    future::try_join_all,
     stream::{Stream, StreamExt},
 };
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
-use tokio_postgres::{SimpleQueryMessage, fallible_iterator::FallibleIterator};
+use tokio_postgres::{GenericClient, SimpleQueryMessage, fallible_iterator::FallibleIterator};
 use uuid::Uuid;
 
 pub(crate) fn row_to_tank_row(row: tokio_postgres::Row) -> tank_core::Result<tank_core::RowValues> {
@@ -74,6 +75,31 @@ where
     }
 }
 
+/// Executes the statement once for each params set, all the requests are sent before waiting for
+/// the responses (pipelining).
+pub(crate) async fn execute_pipelined(
+    client: &impl GenericClient,
+    prepared: &mut PostgresPrepared,
+    params: Vec<Params>,
+) -> tank_core::Result<Vec<RowsAffected>> {
+    let statement = prepared.statement.clone();
+    let requests = params
+        .into_iter()
+        .map(|params| {
+            params.bind(prepared)?;
+            Ok(client.execute_raw(&statement, prepared.take_params()))
+        })
+        .collect::<tank_core::Result<Vec<_>>>()?;
+    Ok(try_join_all(requests)
+        .await?
+        .into_iter()
+        .map(|v| RowsAffected {
+            rows_affected: Some(v),
+            last_affected_id: None,
+        })
+        .collect())
+}
+
 pub(crate) fn stream_postgres_simple_query_message_to_tank_query_result<V, R>(
     stream: impl AsyncFnOnce() -> tank_core::Result<V>,
 ) -> impl Stream<Item = tank_core::Result<R>>
@@ -161,6 +187,12 @@ pub(crate) fn extract_value(
             Type::VARCHAR | Type::TEXT | Type::NAME | Type::BPCHAR | Type::JSON | Type::XML => {
                 Value::Varchar(convert::<String>(ty, raw)?.map(Into::into))
             }
+            Type::JSONB => Value::Json(match raw {
+                // Binary jsonb is the version byte (always 1) followed by the json text
+                Some([1, json @ ..]) => Some(serde_json::from_slice(json)?),
+                Some(..) => return Err(anyhow!("Unexpected jsonb binary format version").into()),
+                None => None,
+            }),
             Type::BYTEA => Value::Blob(convert::<Vec<u8>>(ty, raw)?.map(Into::into)),
             Type::DATE => Value::Date(convert::<Date>(ty, raw)?),
             Type::TIME => Value::Time(convert::<Time>(ty, raw)?),
@@ -173,6 +205,7 @@ pub(crate) fn extract_value(
                 return Err(anyhow!("Unexpected {ty} variant for Kind::Simple").into());
             }
         },
+        Kind::Enum(..) => Value::Varchar(convert::<String>(ty, raw)?.map(Into::into)),
         Kind::Array(inner_ty) => {
             let ty = extract_value(inner_ty, None)?;
             if let Some(raw) = raw {
@@ -188,10 +221,63 @@ pub(crate) fn extract_value(
                 Value::List(None, Box::new(ty))
             }
         }
+        Kind::Composite(fields) => {
+            let types = fields
+                .iter()
+                .map(|f| Ok((f.name().to_string(), extract_value(f.type_(), None)?)))
+                .collect::<Result<Vec<_>, Box<dyn Error + Sync + Send>>>()?;
+            let name = TableRef {
+                name: ty.name().to_string().into(),
+                schema: ty.schema().to_string().into(),
+                ..Default::default()
+            };
+            let values = match raw {
+                Some(mut raw) => {
+                    // Binary record: field count, then oid, length (-1 when null) and bytes of each field
+                    let count = read_i32(&mut raw)?;
+                    if count as usize != fields.len() {
+                        return Err(anyhow!(
+                            "The composite type {ty} has {} fields but {count} were received",
+                            fields.len()
+                        )
+                        .into());
+                    }
+                    let mut values = Vec::with_capacity(fields.len());
+                    for field in fields {
+                        let _oid = read_i32(&mut raw)?;
+                        let len = read_i32(&mut raw)?;
+                        let value = if len < 0 {
+                            None
+                        } else {
+                            let (value, rest) = raw
+                                .split_at_checked(len as usize)
+                                .ok_or_else(|| anyhow!("Truncated composite value of type {ty}"))?;
+                            raw = rest;
+                            Some(value)
+                        };
+                        values.push((
+                            field.name().to_string(),
+                            extract_value(field.type_(), value)?,
+                        ));
+                    }
+                    Some(values)
+                }
+                None => None,
+            };
+            Value::Struct(values, types, name)
+        }
         _ => return Err(tank_core::anyhow!("Unexpected kind {kind:?}").into()),
     })
 }
 
+fn read_i32(raw: &mut &[u8]) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    let (value, rest) = raw
+        .split_first_chunk::<4>()
+        .ok_or_else(|| anyhow!("Unexpected end of the binary value"))?;
+    *raw = rest;
+    Ok(i32::from_be_bytes(*value))
+}
+
 fn build_array<'a>(
     begin: usize,
     values: &mut Vec<Value>,
@@ -308,8 +394,8 @@ pub fn postgres_type_to_value(ty: &Type) -> Value {
         Type::TIMESTAMPTZ_ARRAY => Value::List(None, Box::new(Value::TimestampWithTimezone(None))),
         Type::INTERVAL_ARRAY => Value::List(None, Box::new(Value::Interval(None))),
         Type::UUID_ARRAY => Value::List(None, Box::new(Value::Uuid(None))),
-        Type::JSON_ARRAY => Value::List(None, Box::new(Value::Json(None))),
-        Type::JSON => Value::Json(None),
+        Type::JSON_ARRAY | Type::JSONB_ARRAY => Value::List(None, Box::new(Value::Json(None))),
+        Type::JSON | Type::JSONB => Value::Json(None),
         _ => Value::Null,
     }
 }
@@ -372,3 +458,16 @@ pub fn value_to_postgres_type(value: &Value) -> Type {
         _ => Type::UNKNOWN,
     }
 }
+
+/// Type of the column, `JSONB` for the json columns declared so (like the `#[tank(json)]` ones).
+pub fn column_to_postgres_type(column: &ColumnDef) -> Type {
+    let jsonb = column
+        .column_type
+        .iter()
+        .any(|(k, v)| (*k == "postgres" || *k == "postgresql") && v.eq_ignore_ascii_case("JSONB"));
+    if jsonb && matches!(column.value, Value::Json(..)) {
+        Type::JSONB
+    } else {
+        value_to_postgres_type(&column.value)
+    }
+}
@@ -3,9 +3,9 @@ use crate::{
     util::{extract_value, flatten_array},
 };
 use anyhow::anyhow;
-use bytes::BytesMut;
+use bytes::{BufMut, BytesMut};
 use postgres_protocol::types::array_to_sql;
-use postgres_types::{FromSql, IsNull, ToSql, Type, to_sql_checked};
+use postgres_types::{FromSql, IsNull, Kind, ToSql, Type, to_sql_checked};
 use rust_decimal::{Decimal, prelude::FromPrimitive};
 use std::{borrow::Cow, error::Error};
 use tank_core::Value;
@@ -114,6 +114,34 @@ impl<'a> ToSql for ValueWrap<'a> {
                 }
                 None => None::<Vec<ValueWrap>>.to_sql(ty, out),
             },
+            Value::Json(None) => None::<String>.to_sql(ty, out),
+            Value::Json(Some(v)) => {
+                if *ty == Type::JSONB {
+                    out.put_u8(1);
+                }
+                out.put_slice(v.to_string().as_bytes());
+                Ok(IsNull::No)
+            }
+            Value::Struct(None, ..) => None::<String>.to_sql(ty, out),
+            Value::Struct(Some(v), ..) if let Kind::Composite(fields) = ty.kind() => {
+                // Binary record: field count, then oid, length (-1 when null) and bytes of each field
+                out.put_i32(fields.len() as i32);
+                for field in fields {
+                    out.put_u32(field.type_().oid());
+                    let value = v
+                        .iter()
+                        .find_map(|(k, v)| (k == field.name()).then_some(v))
+                        .unwrap_or(&Value::Null);
+                    let len = out.len();
+                    out.put_i32(0);
+                    let size = match ValueWrap(Cow::Borrowed(value)).to_sql(field.type_(), out)? {
+                        IsNull::Yes => -1,
+                        IsNull::No => (out.len() - len - 4) as i32,
+                    };
+                    out[len..len + 4].copy_from_slice(&size.to_be_bytes());
+                }
+                Ok(IsNull::No)
+            }
             _ => {
                 return Err(anyhow!(
                     "tank::Value variant `{:?}` is not supported by Postgres",
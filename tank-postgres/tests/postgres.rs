@@ -3,10 +3,11 @@ mod init;
 #[cfg(test)]
 mod tests {
     use super::init::init;
-    use std::{env, path::PathBuf, sync::Mutex};
-    use tank_core::{Connection, ConnectionPool, Driver, PoolConfig};
+    use std::{env, path::PathBuf, sync::Mutex, time::Duration};
+    use tank_core::{Connection, ConnectionPool, Driver, Executor, PoolConfig, RoutingPool};
     use tank_postgres::{PostgresConnection, PostgresDriver};
     use tank_tests::{execute_tests, init_logs, silent_logs};
+    use tokio::time::sleep;
     use url::Url;
 
     static MUTEX: Mutex<()> = Mutex::new(());
@@ -38,6 +39,65 @@ mod tests {
         drop(container);
     }
 
+    #[tokio::test]
+    async fn routing_dead_replica() {
+        init_logs();
+        let _guard = MUTEX.lock().unwrap();
+
+        let (url, container) = init(false).await;
+        let _container = container.expect("Could not launch the container");
+        let replica = DRIVER
+            .connect_pool(
+                url.clone().into(),
+                PoolConfig {
+                    max_size: 1,
+                    test_on_checkout: false,
+                    reset_on_release: false,
+                    ..PoolConfig::new()
+                },
+            )
+            .await
+            .expect("Failed to connect");
+        let pool = RoutingPool::new(
+            DRIVER
+                .connect_pool(url.into(), PoolConfig::new())
+                .await
+                .expect("Failed to connect"),
+        )
+        .replica(replica.clone());
+        drop(pool.get_read().await.expect("Could not get a connection"));
+        assert_eq!(replica.status().size, 1);
+        pool.primary()
+            .get()
+            .await
+            .expect("Could not get a connection")
+            .execute(
+                "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+                WHERE pid <> pg_backend_pid() AND datname = current_database();",
+            )
+            .await
+            .expect("Could not terminate the replica connections");
+        sleep(Duration::from_millis(200)).await;
+        silent_logs! {
+            pool.get_read()
+                .await
+                .expect("Reads must fall back to the primary")
+                .ping()
+                .await
+                .expect("The primary connection must be alive");
+        }
+        assert_eq!(
+            pool.available_replicas(),
+            0,
+            "The dead replica must be ejected"
+        );
+        assert_eq!(
+            replica.status().size,
+            0,
+            "The dead connection must be closed"
+        );
+    }
+
     #[tokio::test]
     async fn wrong_url() {
         init_logs();
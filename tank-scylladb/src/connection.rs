@@ -15,11 +15,16 @@ use std::{
     time::Duration,
 };
 use tank_core::{
-    AsQuery, Connection, Error, ErrorContext, Executor, Query, QueryResult, RawQuery, Result, Row,
+    AsQuery, ColumnInfo, Connection, Driver, DynQuery, Error, ErrorContext, Executor, Params,
+    PrimaryKeyType, Query, QueryResult, RawQuery, Result, Row, RowsAffected, SqlWriter, TableRef,
+    Value,
     stream::{Stream, StreamExt, TryStreamExt},
-    truncate_long,
+    table_refs_from_rows, truncate_long,
 };
 
+/// Maximum number of statements sent in one batch by `execute_batch`.
+const BATCH_SIZE: usize = 100;
+
 /// Connection wrapper for ScyllaDB/Cassandra sessions.
 ///
 /// Holds the underlying `scylla::Session` and exposes `Executor`/`Connection` implementations for the ScyllaDB driver.
@@ -60,6 +65,17 @@ impl ScyllaDBConnection {
         &self.session
     }
 
+    /// Returns `keyspace`, or the one the session is using when empty.
+    fn keyspace(&self, keyspace: &str) -> Result<String> {
+        if !keyspace.is_empty() {
+            return Ok(keyspace.to_string());
+        }
+        self.session
+            .get_keyspace()
+            .map(|v| v.to_string())
+            .ok_or_else(|| anyhow!("No keyspace specified and the session is not using any"))
+    }
+
     /// Access the underlying session object from `scylla` crate
     pub fn session_mut(&mut self) -> &mut Session {
         &mut self.session
@@ -161,6 +177,93 @@ impl Executor for ScyllaDBConnection {
             error
         })
     }
+
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<ScyllaDBDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let context = format!("While executing a batch of the query:\n{query:?}");
+        let Query::Prepared(prepared) = query else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        let len = params.len();
+        let mut values = Vec::with_capacity(len);
+        for params in params {
+            params.bind(prepared).context(context.clone())?;
+            values.push(prepared.take_params()?);
+        }
+        // Large batches are rejected or slow down the coordinator, each chunk is atomic on its own
+        let mut values = values.into_iter().peekable();
+        while values.peek().is_some() {
+            let chunk = values.by_ref().take(BATCH_SIZE).collect::<Vec<_>>();
+            let mut batch = Batch::new(BatchType::Logged);
+            for _ in 0..chunk.len() {
+                batch.append_statement(prepared.statement.clone());
+            }
+            self.session.batch(&batch, chunk).await.map_err(|e| {
+                let error = Error::new(e).context(context.clone());
+                log::error!("{error:#}");
+                error
+            })?;
+        }
+        // The driver does not give the number of affected rows
+        Ok(vec![RowsAffected::default(); len])
+    }
+
+    async fn describe_table(&mut self, table: &TableRef) -> Result<Vec<ColumnInfo>> {
+        let mut table = table.clone();
+        table.schema = self.keyspace(&table.schema)?.into();
+        let mut query = DynQuery::default();
+        self.driver()
+            .sql_writer()
+            .write_describe_table(&mut query, &table);
+        let rows = self.fetch(query).try_collect::<Vec<_>>().await?;
+        let kind = |row: &Row| match row.get_column("kind") {
+            Some(Value::Varchar(Some(v))) => match v.as_ref() {
+                "partition_key" => 0,
+                "clustering" => 1,
+                _ => 2,
+            },
+            _ => 2,
+        };
+        let position = |row: &Row| match row.get_column("position") {
+            Some(Value::Int32(Some(v))) => *v,
+            _ => 0,
+        };
+        let keys = rows.iter().filter(|v| kind(v) < 2).count();
+        let mut columns = ColumnInfo::from_rows(
+            rows.iter().cloned(),
+            &table,
+            self.driver().sql_writer().as_dyn(),
+        )?
+        .into_iter()
+        .zip(rows.iter().map(|v| (kind(v), position(v))))
+        .map(|(mut column, (kind, position))| {
+            column.nullable = kind == 2;
+            column.clustering_key = kind == 1;
+            column.primary_key = match (kind, keys) {
+                (2, _) => PrimaryKeyType::None,
+                (_, 1) => PrimaryKeyType::PrimaryKey,
+                _ => PrimaryKeyType::PartOfPrimaryKey,
+            };
+            ((kind, position), column)
+        })
+        .collect::<Vec<_>>();
+        // Key columns first, in key order, then the regular ones by name
+        columns.sort_by_key(|(key, _)| *key);
+        Ok(columns.into_iter().map(|(_, v)| v).collect())
+    }
+
+    async fn list_tables(&mut self, schema: &str) -> Result<Vec<TableRef>> {
+        let schema = self.keyspace(schema)?;
+        let mut query = DynQuery::default();
+        self.driver()
+            .sql_writer()
+            .write_list_tables(&mut query, &schema);
+        let rows = self.fetch(query).try_collect::<Vec<_>>().await?;
+        table_refs_from_rows(rows)
+    }
 }
 
 impl Connection for ScyllaDBConnection {
@@ -363,4 +466,12 @@ impl Connection for ScyllaDBConnection {
     async fn begin<'c>(&'c mut self) -> Result<ScyllaDBTransaction<'c>> {
         Ok(Self::begin_logged_batch(self))
     }
+
+    async fn ping(&mut self) -> Result<()> {
+        self.session
+            .query_unpaged("SELECT now() FROM system.local", &[])
+            .await
+            .context("While pinging ScyllaDB")?;
+        Ok(())
+    }
 }
@@ -5,7 +5,7 @@ use std::{
     fmt::{self, Debug, Display, Formatter},
     mem,
 };
-use tank_core::{AsValue, Prepared, Result};
+use tank_core::{AsValue, ParameterNames, Prepared, Result};
 
 /// Prepared statement wrapper for ScyllaDB.
 ///
@@ -14,6 +14,7 @@ pub struct ScyllaDBPrepared {
     pub(crate) statement: PreparedStatement,
     pub(crate) params: Vec<ValueWrap>,
     pub(crate) index: u64,
+    pub(crate) names: ParameterNames,
 }
 
 impl ScyllaDBPrepared {
@@ -22,6 +23,7 @@ impl ScyllaDBPrepared {
             statement,
             params: Vec::new(),
             index: 0,
+            names: Default::default(),
         }
     }
     pub(crate) fn take_params(&mut self) -> Result<Vec<ValueWrap>> {
@@ -54,6 +56,13 @@ impl Prepared for ScyllaDBPrepared {
         self.index = index + 1;
         Ok(self)
     }
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        Some(&self.names)
+    }
+    fn set_parameter_names(&mut self, names: ParameterNames) -> Result<()> {
+        self.names = names;
+        Ok(())
+    }
 }
 
 impl Display for ScyllaDBPrepared {
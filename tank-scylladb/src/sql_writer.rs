@@ -4,8 +4,9 @@ use indoc::indoc;
 use std::fmt::Write;
 use std::{collections::BTreeMap, iter};
 use tank_core::{
-    AsEntity, ColumnDef, Context, Dataset, DynQuery, Entity, Expression, Fragment,
-    GenericSqlWriter, Interval, IsTrue, PrimaryKeyType, Result, SqlWriter, Value, separated_by,
+    AsEntity, ColumnChanges, ColumnDef, Context, Dataset, DynQuery, Entity, Expression,
+    FieldAccess, Fragment, GenericSqlWriter, IndexDef, Interval, IsTrue, PrimaryKeyType, Result,
+    SqlWriter, TableRef, Value, separated_by,
 };
 use uuid::Uuid;
 
@@ -82,6 +83,12 @@ impl SqlWriter for ScyllaDBSqlWriter {
                 out.push('>');
             }
             Value::Json(..) => out.push_str("TEXT"),
+            Value::Struct(.., name) => {
+                // User defined types nested in collections or other types must be frozen
+                out.push_str("FROZEN<");
+                self.write_table_ref(context, out, name);
+                out.push('>');
+            }
             _ => log::error!(
                 "Unexpected tank::Value, variant {:?} is not supported",
                 value
@@ -217,6 +224,110 @@ impl SqlWriter for ScyllaDBSqlWriter {
         out.push(']');
     }
 
+    fn write_struct(&self, context: &mut Context, out: &mut DynQuery, value: &[(String, Value)]) {
+        out.push('{');
+        separated_by(
+            out,
+            value,
+            |out, (k, v)| {
+                self.write_identifier(context, out, k, true);
+                out.push(':');
+                self.write_value(context, out, v);
+            },
+            ",",
+        );
+        out.push('}');
+    }
+
+    fn write_field_access(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &FieldAccess<&dyn Expression>,
+    ) {
+        value.expression.write_query(self, context, out);
+        for field in value.fields {
+            out.push('.');
+            self.write_identifier(context, out, field, true);
+        }
+    }
+
+    fn write_describe_table(&self, out: &mut DynQuery, table: &TableRef) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        // The key roles are derived from `kind` and `position` by the connection
+        out.push_str(
+            "SELECT column_name, type AS data_type, kind, position FROM system_schema.columns WHERE keyspace_name = ",
+        );
+        self.write_string(&mut context, out, &table.schema);
+        out.push_str(" AND table_name = ");
+        self.write_string(&mut context, out, &table.name);
+        out.push(';');
+    }
+
+    fn write_list_tables(&self, out: &mut DynQuery, schema: &str) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.push_str(
+            "SELECT keyspace_name AS table_schema, table_name FROM system_schema.tables WHERE keyspace_name = ",
+        );
+        self.write_string(&mut context, out, schema);
+        out.push(';');
+    }
+
+    fn parse_column_type(&self, name: &str) -> Value {
+        let name = name.trim().to_ascii_lowercase();
+        let name = name
+            .strip_prefix("frozen<")
+            .and_then(|v| v.strip_suffix('>'))
+            .unwrap_or(&name);
+        fn generic(v: &str) -> Option<(&str, &str)> {
+            let (base, args) = v.split_once('<')?;
+            Some((base.trim(), args.strip_suffix('>')?))
+        }
+        match generic(name) {
+            Some(("list" | "set", inner)) => {
+                return Value::List(None, self.parse_column_type(inner).into());
+            }
+            Some(("map", args)) => {
+                let mut depth = 0;
+                let split = args.char_indices().find(|(_, c)| {
+                    match c {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        ',' if depth == 0 => return true,
+                        _ => {}
+                    }
+                    false
+                });
+                if let Some((i, _)) = split {
+                    return Value::Map(
+                        None,
+                        self.parse_column_type(&args[..i]).into(),
+                        self.parse_column_type(&args[i + 1..]).into(),
+                    );
+                }
+            }
+            Some(("vector", args)) => {
+                if let Some((inner, len)) = args.rsplit_once(',')
+                    && let Ok(len) = len.trim().parse::<u32>()
+                {
+                    return Value::Array(None, self.parse_column_type(inner).into(), len);
+                }
+            }
+            _ => {}
+        }
+        match name {
+            "ascii" => Value::Varchar(None),
+            "varint" => Value::Int128(None),
+            "counter" => Value::Int64(None),
+            "float" => Value::Float32(None),
+            "timestamp" => Value::Timestamp(None),
+            "duration" => Value::Interval(None),
+            "timeuuid" => Value::Uuid(None),
+            "inet" => Value::Varchar(None),
+            _ => GenericSqlWriter::new().parse_column_type(name),
+        }
+    }
+
     fn write_current_timestamp_ms(&self, _context: &mut Context, out: &mut DynQuery) {
         out.push_str("toUnixTimestamp(currentTimestamp())");
     }
@@ -285,6 +396,37 @@ impl SqlWriter for ScyllaDBSqlWriter {
             // Composite primary key will be printed elsewhere
             out.push_str(" PRIMARY KEY");
         }
+        if let Some(generated) = column.generated {
+            self.write_create_table_generated_fragment(context, out, column, generated().as_ref());
+        }
+        if column.auto_increment {
+            self.write_create_table_auto_increment_fragment(context, out, column);
+        }
+    }
+
+    fn write_create_table_generated_fragment(
+        &self,
+        _context: &mut Context,
+        _out: &mut DynQuery,
+        column: &ColumnDef,
+        _generated: &dyn Expression,
+    ) {
+        log::error!(
+            "ScyllaDB/Cassandra does not support generated columns, `{}` must be computed by the application",
+            column.name()
+        );
+    }
+
+    fn write_create_table_auto_increment_fragment(
+        &self,
+        _context: &mut Context,
+        _out: &mut DynQuery,
+        column: &ColumnDef,
+    ) {
+        log::error!(
+            "ScyllaDB/Cassandra does not support auto increment columns, `{}` must be assigned by the application",
+            column.name()
+        );
     }
 
     fn write_create_table_primary_key_fragment<'a, It>(
@@ -328,6 +470,18 @@ impl SqlWriter for ScyllaDBSqlWriter {
         out.push(')');
     }
 
+    fn write_create_table_check_fragment(
+        &self,
+        context: &mut Context,
+        _out: &mut DynQuery,
+        check: &dyn Expression,
+    ) {
+        log::warn!(
+            "ScyllaDB/Cassandra does not support check constraints, `{}` is ignored",
+            check.as_identifier(context),
+        );
+    }
+
     fn write_column_comments_statements<E>(&self, _context: &mut Context, _out: &mut DynQuery)
     where
         Self: Sized,
@@ -335,6 +489,182 @@ impl SqlWriter for ScyllaDBSqlWriter {
     {
     }
 
+    fn write_create_struct_type(&self, out: &mut DynQuery, value: &Value) {
+        let Value::Struct(.., fields, name) = value else {
+            return;
+        };
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE TYPE IF NOT EXISTS ");
+        let mut context = Context::new(Fragment::SqlCreateType, false);
+        self.write_table_ref(&mut context, out, name);
+        out.push_str(" (");
+        separated_by(
+            out,
+            fields,
+            |out, (field, value)| {
+                self.write_identifier(&mut context, out, field, true);
+                out.push(' ');
+                self.write_column_type(&mut context, out, value);
+            },
+            ", ",
+        );
+        out.push_str(");");
+    }
+
+    fn write_create_view<E>(&self, _out: &mut DynQuery, _if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "ScyllaDB/Cassandra does not support views defined by a query, cannot create {}",
+            E::table().full_name(self.separator())
+        );
+    }
+
+    fn write_drop_view<E>(&self, _out: &mut DynQuery, _if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "ScyllaDB/Cassandra does not support views defined by a query, cannot drop {}",
+            E::table().full_name(self.separator())
+        );
+    }
+
+    fn write_refresh_view<E>(&self, _out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "ScyllaDB/Cassandra does not support views defined by a query, cannot refresh {}",
+            E::table().full_name(self.separator())
+        );
+    }
+
+    fn write_create_index<E>(&self, out: &mut DynQuery, index: &IndexDef, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let table = E::table();
+        if index.unique || index.condition.is_some() {
+            log::error!(
+                "ScyllaDB/Cassandra does not support unique or partial indexes, cannot create {} on {}",
+                index.name(),
+                table.full_name(self.separator()),
+            );
+            return;
+        }
+        let [(column, ..)] = index.columns.as_ref() else {
+            log::error!(
+                "ScyllaDB/Cassandra secondary indexes cover a single column, cannot create {} on {}",
+                index.name(),
+                table.full_name(self.separator()),
+            );
+            return;
+        };
+        let mut context = Context::new(Fragment::SqlCreateIndex, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE ");
+        if !index.using.is_empty() {
+            out.push_str("CUSTOM ");
+        }
+        out.push_str("INDEX ");
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_identifier(&mut context, out, index.name(), true);
+        out.push_str(" ON ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" (");
+        self.write_identifier(&mut context, out, column.name(), true);
+        out.push(')');
+        if !index.using.is_empty() {
+            out.push_str(" USING ");
+            self.write_string(&mut context, out, index.using);
+        }
+        out.push(';');
+    }
+
+    fn write_alter_table_add_column(&self, out: &mut DynQuery, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" ADD ");
+        self.write_identifier(&mut context, out, column.name(), true);
+        out.push(' ');
+        self.write_alter_table_column_type(&mut context, out, column);
+        out.push(';');
+    }
+
+    fn write_alter_table_drop_column(&self, out: &mut DynQuery, table: &TableRef, name: &str) {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" DROP ");
+        self.write_identifier(&mut context, out, name, true);
+        out.push(';');
+    }
+
+    fn write_alter_table_alter_column(
+        &self,
+        _out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+        changes: &ColumnChanges,
+    ) where
+        Self: Sized,
+    {
+        // Columns have neither NOT NULL nor DEFAULT
+        if changes.column_type {
+            log::error!(
+                "ScyllaDB/Cassandra does not support changing the type of {} in {}",
+                column.name(),
+                table.full_name(self.separator()),
+            );
+        }
+    }
+
+    fn write_alter_table_add_unique(
+        &self,
+        _out: &mut DynQuery,
+        table: &TableRef,
+        _columns: &[&ColumnDef],
+    ) {
+        log::error!(
+            "ScyllaDB/Cassandra does not support unique constraints, cannot add one to {}",
+            table.full_name(self.separator()),
+        );
+    }
+
+    fn write_alter_table_add_foreign_key(
+        &self,
+        _out: &mut DynQuery,
+        table: &TableRef,
+        _column: &ColumnDef,
+    ) {
+        log::error!(
+            "ScyllaDB/Cassandra does not support foreign keys, cannot add one to {}",
+            table.full_name(self.separator()),
+        );
+    }
+
     fn write_insert<It>(&self, out: &mut DynQuery, entities: It, _update: bool)
     where
         Self: Sized,
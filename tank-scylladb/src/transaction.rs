@@ -2,7 +2,7 @@ use crate::{ScyllaDBConnection, ScyllaDBDriver, ScyllaDBPrepared, ValueWrap};
 use scylla::statement::batch::Batch;
 use std::future;
 use tank_core::{
-    AsEntity, AsQuery, Driver, DynQuery, Error, ErrorContext, Executor, Query, QueryResult,
+    AsEntity, AsQuery, Driver, DynQuery, Error, ErrorContext, Executor, Params, Query, QueryResult,
     RawQuery, Result, RowsAffected, SqlWriter, Transaction,
     future::Either,
     stream::{self, Stream},
@@ -76,6 +76,24 @@ impl<'c> Executor for ScyllaDBTransaction<'c> {
         Either::Right(stream::empty())
     }
 
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<ScyllaDBDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let Query::Prepared(prepared) = query else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        let len = params.len();
+        for params in params {
+            params.bind(prepared)?;
+            self.params.push(prepared.take_params()?);
+            self.batch.append_statement(prepared.statement.clone());
+        }
+        // Appended to the batch, executed on commit
+        Ok(vec![RowsAffected::default(); len])
+    }
+
     async fn append<It>(&mut self, entities: It) -> Result<RowsAffected>
     where
         It: IntoIterator + Send,
@@ -100,7 +100,10 @@ impl SerializeValue for ValueWrap {
                 NativeType::Float => do_serialize::<f32>(value, ty, writer),
                 NativeType::Int => do_serialize::<i32>(value, ty, writer),
                 NativeType::BigInt => do_serialize::<i64>(value, ty, writer),
-                NativeType::Text => do_serialize::<String>(value, ty, writer),
+                NativeType::Text => match value {
+                    Value::Json(Some(v)) => v.to_string().serialize(ty, writer),
+                    value => do_serialize::<String>(value, ty, writer),
+                },
                 NativeType::Timestamp => todo!(),
                 NativeType::Inet => todo!(),
                 NativeType::SmallInt => do_serialize::<i16>(value, ty, writer),
@@ -17,8 +17,9 @@ use std::{
     },
 };
 use tank_core::{
-    AsQuery, Connection, Error, ErrorContext, Executor, Prepared, Query, QueryResult, RawQuery,
-    Result, Row, RowsAffected, error_message_from_ptr, send_value, stream::Stream, truncate_long,
+    AsQuery, Connection, Error, ErrorContext, Executor, Params, Prepared, Query, QueryResult,
+    RawQuery, Result, Row, RowsAffected, Transaction, error_message_from_ptr, send_value,
+    stream::Stream, truncate_long,
 };
 use tokio::task::spawn_blocking;
 
@@ -160,6 +161,28 @@ impl SQLiteConnection {
             }
         };
     }
+
+    pub(crate) fn do_run_batch(
+        connection: *mut sqlite3,
+        prepared: &mut SQLitePrepared,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let mut result = Vec::with_capacity(params.len());
+        for params in params {
+            params.bind(prepared)?;
+            let (tx, rx) = flume::unbounded();
+            Self::do_run_prepared(connection, prepared.statement(), tx);
+            let mut affected = RowsAffected::default();
+            for value in rx.drain() {
+                if let QueryResult::Affected(value) = value? {
+                    affected.extend([value]);
+                }
+            }
+            result.push(affected);
+        }
+        prepared.clear_bindings()?;
+        Ok(result)
+    }
 }
 
 impl Executor for SQLiteConnection {
@@ -247,6 +270,44 @@ impl Executor for SQLiteConnection {
             *query.as_mut() = mem::take(&mut join.await?);
         }
     }
+
+    async fn do_execute_batch<'s>(
+        &'s mut self,
+        query: &'s mut Query<SQLiteDriver>,
+        params: Vec<Params>,
+    ) -> Result<Vec<RowsAffected>> {
+        let context = format!("While executing a batch of the query:\n{query}");
+        let connection = AtomicPtr::new(*self.connection);
+        // Autocommit is disabled when already inside a transaction
+        let transaction = if unsafe { sqlite3_get_autocommit(*self.connection) } != 0 {
+            Some(self.begin().await?)
+        } else {
+            None
+        };
+        let Query::Prepared(mut prepared) = mem::take(query) else {
+            unreachable!("Batches are executed only on prepared queries");
+        };
+        let (prepared, result) = spawn_blocking(move || {
+            let result =
+                Self::do_run_batch(connection.load(Ordering::Relaxed), &mut prepared, params);
+            (prepared, result)
+        })
+        .await?;
+        *query = prepared.into();
+        let result = match (result, transaction) {
+            (Ok(result), Some(transaction)) => transaction.commit().await.map(|_| result),
+            (Err(error), Some(transaction)) => {
+                let _ = transaction.rollback().await;
+                Err(error)
+            }
+            (result, None) => result,
+        };
+        result.map_err(|e| {
+            let error = e.context(context);
+            log::error!("{error:#}");
+            error
+        })
+    }
 }
 
 impl Connection for SQLiteConnection {
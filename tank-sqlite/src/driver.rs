@@ -20,4 +20,8 @@ impl Driver for SQLiteDriver {
     fn sql_writer(&self) -> SQLiteSqlWriter {
         SQLiteSqlWriter {}
     }
+
+    fn supports_transactional_ddl(&self) -> bool {
+        true
+    }
 }
@@ -8,8 +8,8 @@ use std::{
     os::raw::{c_char, c_void},
 };
 use tank_core::{
-    error_message_from_ptr, truncate_long, AsValue, Context, DynQuery, Error, Fragment, Prepared,
-    Result, SqlWriter, Value,
+    error_message_from_ptr, truncate_long, value_to_json, AsValue, Context, DynQuery, Error,
+    Fragment, ParameterNames, Prepared, Result, SqlWriter, Value,
 };
 
 /// Prepared statement wrapper for SQLite.
@@ -19,6 +19,7 @@ use tank_core::{
 pub struct SQLitePrepared {
     pub(crate) statement: CBox<*mut sqlite3_stmt>,
     pub(crate) index: u64,
+    pub(crate) names: ParameterNames,
 }
 
 impl SQLitePrepared {
@@ -27,6 +28,7 @@ impl SQLitePrepared {
         Self {
             statement: statement.into(),
             index: 1,
+            names: Default::default(),
         }
     }
     pub(crate) fn statement(&self) -> *mut sqlite3_stmt {
@@ -213,6 +215,28 @@ impl Prepared for SQLitePrepared {
                         SQLITE_TRANSIENT(),
                     )
                 }
+                Value::Json(Some(v), ..) => {
+                    let v = v.to_string();
+                    sqlite3_bind_text(
+                        statement,
+                        index,
+                        v.as_ptr() as *const c_char,
+                        v.len() as c_int,
+                        SQLITE_TRANSIENT(),
+                    )
+                }
+                Value::Struct(Some(..), ..) => {
+                    let v = value_to_json(&value)
+                        .ok_or_else(|| anyhow!("Cannot bind the struct `{value:?}` as JSON"))?
+                        .to_string();
+                    sqlite3_bind_text(
+                        statement,
+                        index,
+                        v.as_ptr() as *const c_char,
+                        v.len() as c_int,
+                        SQLITE_TRANSIENT(),
+                    )
+                }
                 _ => {
                     let error = anyhow!("Cannot use a {:?} as a query parameter", value);
                     log::error!("{error:#}");
@@ -234,6 +258,14 @@ impl Prepared for SQLitePrepared {
             Ok(self)
         }
     }
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        Some(&self.names)
+    }
+    fn set_parameter_names(&mut self, names: ParameterNames) -> Result<()> {
+        // Native indexes start from 1
+        self.names = names.offset(1);
+        Ok(())
+    }
 }
 
 impl Display for SQLitePrepared {
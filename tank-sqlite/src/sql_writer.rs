@@ -4,8 +4,9 @@ use std::{
     mem,
 };
 use tank_core::{
-    ColumnDef, ColumnRef, Context, DynQuery, Entity, Expression, GenericSqlWriter, SqlWriter,
-    TableRef, Value, write_escaped,
+    ColumnChanges, ColumnDef, ColumnRef, Context, DynQuery, Entity, Expression, FieldAccess,
+    Fragment, GenericSqlWriter, IndexDef, Order, PrimaryKeyType, SqlWriter, TableRef, Value,
+    separated_by, value_to_json, write_escaped,
 };
 
 /// SQL writer for SQLite dialect.
@@ -104,6 +105,8 @@ impl SqlWriter for SQLiteSqlWriter {
             Value::Timestamp(..) => out.push_str("TEXT"),
             Value::TimestampWithTimezone(..) => out.push_str("TEXT"),
             Value::Uuid(..) => out.push_str("TEXT"),
+            Value::Json(..) => out.push_str("TEXT"),
+            Value::Struct(..) => out.push_str("TEXT"),
             _ => log::error!("Unexpected tank::Value, SQLite does not support {value:?}"),
         };
     }
@@ -172,6 +175,80 @@ impl SqlWriter for SQLiteSqlWriter {
         log::error!("SQLite does not support maps");
     }
 
+    fn write_struct(&self, context: &mut Context, out: &mut DynQuery, value: &[(String, Value)]) {
+        let json = serde_json::Value::Object(
+            value
+                .iter()
+                .map(|(k, v)| (k.clone(), value_to_json(v).unwrap_or_default()))
+                .collect(),
+        );
+        self.write_string(context, out, &json.to_string());
+    }
+
+    fn write_field_access(
+        &self,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &FieldAccess<&dyn Expression>,
+    ) {
+        out.push_str("json_extract(");
+        value.expression.write_query(self, context, out);
+        out.push_str(", '$");
+        for field in value.fields {
+            let _ = write!(out, ".\"{field}\"");
+        }
+        out.push_str("')");
+    }
+
+    fn json_validation_function(&self) -> Option<&'static str> {
+        Some("json_valid")
+    }
+
+    fn write_describe_table(&self, out: &mut DynQuery, table: &TableRef) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        let mut args = DynQuery::default();
+        // Schemas are part of the table name, see write_table_ref
+        let name = table.full_name(self.separator());
+        self.write_string(&mut context, &mut args, &name);
+        let args = args.as_str();
+        let _ = write!(
+            out,
+            "SELECT p.name AS column_name, p.type AS data_type, p.\"notnull\" = 0 AND p.pk = 0 AS is_nullable,\n\
+            p.dflt_value AS column_default,\n\
+            CASE WHEN p.pk = 0 THEN 0 ELSE (SELECT COUNT(*) FROM pragma_table_info({args}) WHERE pk > 0) END AS primary_key,\n\
+            EXISTS (SELECT 1 FROM pragma_index_list({args}) l WHERE l.\"unique\" AND l.origin <> 'pk'\n\
+            AND (SELECT COUNT(*) FROM pragma_index_info(l.name)) = 1 AND (SELECT name FROM pragma_index_info(l.name)) = p.name\n\
+            ) AS is_unique,\n\
+            f.\"table\" AS referenced_table, f.\"to\" AS referenced_column, f.on_delete, f.on_update\n\
+            FROM pragma_table_info({args}) p\n\
+            LEFT JOIN pragma_foreign_key_list({args}) f ON f.\"from\" = p.name\n\
+            ORDER BY p.cid;"
+        );
+    }
+
+    fn write_list_tables(&self, out: &mut DynQuery, schema: &str) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        out.push_str("SELECT ");
+        self.write_string(&mut context, out, schema);
+        out.push_str(" AS table_schema, name AS table_name FROM ");
+        if !schema.is_empty() {
+            self.write_identifier(&mut context, out, schema, true);
+            out.push('.');
+        }
+        out.push_str(
+            "sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' ORDER BY name;",
+        );
+    }
+
+    fn parse_column_type(&self, name: &str) -> Value {
+        // Type affinity names, INTEGER PRIMARY KEY is a 64 bit rowid
+        match name.trim().to_ascii_uppercase().as_str() {
+            "INTEGER" => Value::Int64(None),
+            "REAL" => Value::Float64(None),
+            _ => GenericSqlWriter::new().parse_column_type(name),
+        }
+    }
+
     fn write_create_schema<E>(&self, _out: &mut DynQuery, _if_not_exists: bool)
     where
         Self: Sized,
@@ -194,4 +271,200 @@ impl SqlWriter for SQLiteSqlWriter {
         E: Entity,
     {
     }
+
+    fn write_create_table_auto_increment_fragment(
+        &self,
+        _context: &mut Context,
+        out: &mut DynQuery,
+        column: &ColumnDef,
+    ) {
+        if column.primary_key != PrimaryKeyType::PrimaryKey {
+            log::error!(
+                "SQLite supports auto increment only on a INTEGER PRIMARY KEY column, `{}` is not",
+                column.name()
+            );
+            return;
+        }
+        out.push_str(" AUTOINCREMENT");
+    }
+
+    fn write_insert_default_value(&self, _context: &mut Context, out: &mut DynQuery) {
+        // VALUES does not accept DEFAULT, NULL makes the INTEGER PRIMARY KEY generate the rowid
+        out.push_str("NULL");
+    }
+
+    fn write_create_view<E>(&self, out: &mut DynQuery, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if E::view().is_some_and(|v| v.materialized) {
+            log::warn!(
+                "SQLite does not support materialized views, {} is a standard view",
+                E::table().full_name(self.separator())
+            );
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE VIEW ");
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS");
+        E::write_view_query(self, out);
+        if !out.as_str().ends_with(';') {
+            out.push(';');
+        }
+    }
+
+    fn write_drop_view<E>(&self, out: &mut DynQuery, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("DROP VIEW ");
+        if if_exists {
+            out.push_str("IF EXISTS ");
+        }
+        let mut context = Context::new(Fragment::SqlDropView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push(';');
+    }
+
+    fn write_refresh_view<E>(&self, _out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "SQLite does not support materialized views, {} is always up to date",
+            E::table().full_name(self.separator())
+        );
+    }
+
+    fn write_create_index<E>(&self, out: &mut DynQuery, index: &IndexDef, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        if !index.using.is_empty() {
+            log::error!(
+                "SQLite does not support index methods, cannot create {} using {} on {}",
+                index.name(),
+                index.using,
+                E::table().full_name(self.separator()),
+            );
+            return;
+        }
+        let mut context = Context::new(Fragment::SqlCreateIndex, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE ");
+        if index.unique {
+            out.push_str("UNIQUE ");
+        }
+        out.push_str("INDEX ");
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_identifier(&mut context, out, index.name(), true);
+        out.push_str(" ON ");
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" (");
+        separated_by(
+            out,
+            index.columns.iter(),
+            |out, (column, order)| {
+                self.write_identifier(&mut context, out, column.name(), true);
+                if *order == Order::DESC {
+                    out.push_str(" DESC");
+                }
+            },
+            ", ",
+        );
+        out.push(')');
+        if let Some(condition) = &index.condition {
+            out.push_str(" WHERE ");
+            condition().write_query(self.as_dyn(), &mut context, out);
+        }
+        out.push(';');
+    }
+
+    fn write_alter_table_add_column(&self, out: &mut DynQuery, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        // Added columns cannot be UNIQUE, the constraint becomes an index
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push_str(" ADD COLUMN ");
+        self.write_identifier(&mut context, out, column.name(), true);
+        out.push(' ');
+        self.write_alter_table_column_type(&mut context, out, column);
+        if !column.nullable {
+            out.push_str(" NOT NULL");
+        }
+        if column.default.is_set() {
+            out.push_str(" DEFAULT ");
+            column.default.write_query(self.as_dyn(), &mut context, out);
+        }
+        out.push(';');
+        if column.unique {
+            self.write_alter_table_add_unique(out, table, &[column]);
+        }
+    }
+
+    fn can_alter_columns(&self) -> bool {
+        false
+    }
+
+    fn write_alter_table_alter_column(
+        &self,
+        _out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+        _changes: &ColumnChanges,
+    ) where
+        Self: Sized,
+    {
+        log::error!(
+            "SQLite does not support altering columns, the table {} must be recreated to change {}",
+            table.full_name(self.separator()),
+            column.name(),
+        );
+    }
+
+    fn write_alter_table_add_unique(
+        &self,
+        out: &mut DynQuery,
+        table: &TableRef,
+        columns: &[&ColumnDef],
+    ) {
+        // SQLite cannot add constraints, a unique index is equivalent
+        self.write_create_unique_index(out, table, columns);
+    }
+
+    fn write_alter_table_add_foreign_key(
+        &self,
+        _out: &mut DynQuery,
+        table: &TableRef,
+        column: &ColumnDef,
+    ) {
+        log::error!(
+            "SQLite does not support adding foreign keys, the table {} must be recreated to reference {}",
+            table.full_name(self.separator()),
+            column.name(),
+        );
+    }
 }
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use tank_core::{Action, Connection, Executor, PrimaryKeyType, TableRef, Value};
+    use tank_sqlite::{SQLiteConnection, SQLiteDriver};
+    use tank_tests::init_logs;
+
+    #[tokio::test]
+    async fn describe_table() {
+        init_logs();
+        let mut connection =
+            SQLiteConnection::connect(&SQLiteDriver::new(), "sqlite://:memory:".into())
+                .await
+                .expect("Could not open the database");
+        connection
+            .execute(
+                "CREATE TABLE author (id INTEGER PRIMARY KEY, name TEXT NOT NULL);\n\
+                CREATE TABLE book (\n\
+                    isbn TEXT PRIMARY KEY,\n\
+                    title TEXT NOT NULL UNIQUE,\n\
+                    price REAL DEFAULT 9.99,\n\
+                    author INTEGER REFERENCES author(id) ON DELETE CASCADE\n\
+                );",
+            )
+            .await
+            .expect("Could not create the tables");
+
+        let tables = connection
+            .list_tables("")
+            .await
+            .expect("Could not list the tables");
+        let names: Vec<_> = tables.iter().map(|v| v.name.as_ref()).collect();
+        assert_eq!(names, ["author", "book"]);
+
+        let columns = connection
+            .describe_table(&TableRef::from("book"))
+            .await
+            .expect("Could not describe the table");
+        let names: Vec<_> = columns.iter().map(|v| v.name()).collect();
+        assert_eq!(names, ["isbn", "title", "price", "author"]);
+
+        let isbn = &columns[0];
+        assert_eq!(isbn.primary_key, PrimaryKeyType::PrimaryKey);
+        assert!(!isbn.nullable);
+        assert_eq!(isbn.value, Value::Varchar(None));
+
+        let title = &columns[1];
+        assert_eq!(title.primary_key, PrimaryKeyType::None);
+        assert!(title.unique);
+        assert!(!title.nullable);
+
+        let price = &columns[2];
+        assert!(price.nullable);
+        assert!(!price.unique);
+        assert_eq!(price.default.as_deref(), Some("9.99"));
+        assert_eq!(price.value, Value::Float64(None));
+
+        let author = &columns[3];
+        assert_eq!(author.value, Value::Int64(None));
+        let references = author.references.as_ref().expect("Missing foreign key");
+        assert_eq!(references.table, "author");
+        assert_eq!(references.name, "id");
+        assert_eq!(author.on_delete, Some(Action::Cascade));
+        assert_eq!(author.on_update, Some(Action::NoAction));
+
+        let missing = connection
+            .describe_table(&TableRef::from("missing"))
+            .await
+            .expect("Could not describe the table");
+        assert!(missing.is_empty());
+    }
+}
@@ -0,0 +1,480 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::pin,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+        },
+        time::{Duration, Instant},
+    };
+    use tank_core::{
+        AsValue, Connection, ConnectionPool, Driver, Executor, PoolConfig, PoolHooks, Result,
+        RoutingPool, Sql, SqlPart, anyhow,
+        future::{self, FutureExt},
+        stream::{StreamExt, TryStreamExt},
+    };
+    use tank_sqlite::SQLiteDriver;
+    use tank_tests::{init_logs, silent_logs};
+    use tokio::time::sleep;
+
+    const MARKER: &str = "CREATE TABLE marker (id INTEGER);";
+
+    async fn has_marker(pool: &impl ConnectionPool<SQLiteDriver>) -> bool {
+        let mut connection = pool.get().await.expect("Could not get a connection");
+        let result;
+        silent_logs! {
+            result = connection.execute("SELECT * FROM marker;").await;
+        }
+        result.is_ok()
+    }
+
+    fn alive(down: &AtomicBool) -> Result<()> {
+        if down.load(Ordering::Relaxed) {
+            return Err(anyhow!("The replica is down"));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ping() {
+        init_logs();
+        let config = PoolConfig::new();
+        assert!(
+            config.test_on_checkout,
+            "Checked out connections are pinged"
+        );
+        assert!(config.reset_on_release, "Returned connections are reset");
+        let driver = SQLiteDriver::new();
+        let pool = driver
+            .connect_pool("sqlite://:memory:".into(), PoolConfig::new())
+            .await
+            .expect("Could not create the pool");
+        let mut connection = pool.get().await.expect("Could not get a connection");
+        connection.ping().await.expect("Ping should succeed");
+    }
+
+    #[tokio::test]
+    async fn max_lifetime() {
+        init_logs();
+        let driver = SQLiteDriver::new();
+        let pool = driver
+            .connect_pool(
+                "sqlite://:memory:".into(),
+                PoolConfig {
+                    max_size: 1,
+                    max_lifetime: Some(Duration::from_millis(100)),
+                    ..PoolConfig::new()
+                },
+            )
+            .await
+            .expect("Could not create the pool");
+        {
+            let mut connection = pool.get().await.expect("Could not get a connection");
+            connection
+                .execute(MARKER)
+                .await
+                .expect("Could not create the marker");
+        }
+        assert!(
+            has_marker(&pool).await,
+            "The same connection must be reused"
+        );
+        sleep(Duration::from_millis(150)).await;
+        assert!(
+            !has_marker(&pool).await,
+            "The connection must be replaced after its lifetime"
+        );
+    }
+
+    #[tokio::test]
+    async fn idle_timeout() {
+        init_logs();
+        let driver = SQLiteDriver::new();
+        let pool = driver
+            .connect_pool(
+                "sqlite://:memory:".into(),
+                PoolConfig {
+                    max_size: 1,
+                    idle_timeout: Some(Duration::from_millis(100)),
+                    reaper_interval: Duration::from_millis(20),
+                    ..PoolConfig::new()
+                },
+            )
+            .await
+            .expect("Could not create the pool");
+        {
+            let mut connection = pool.get().await.expect("Could not get a connection");
+            connection
+                .execute(MARKER)
+                .await
+                .expect("Could not create the marker");
+        }
+        assert!(
+            has_marker(&pool).await,
+            "The same connection must be reused"
+        );
+        sleep(Duration::from_millis(200)).await;
+        assert!(
+            !has_marker(&pool).await,
+            "The idle connection must be closed after the timeout"
+        );
+    }
+
+    #[tokio::test]
+    async fn hooks() {
+        init_logs();
+        let released = Arc::new(AtomicUsize::new(0));
+        let acquired = Arc::new(AtomicUsize::new(0));
+        let hooks = PoolHooks::<SQLiteDriver>::new()
+            .after_connect(|c| {
+                async move {
+                    c.execute("PRAGMA foreign_keys = ON;").await?;
+                    Ok(())
+                }
+                .boxed()
+            })
+            .after_release({
+                let released = released.clone();
+                move |_| {
+                    released.fetch_add(1, Ordering::Relaxed);
+                    async { Ok(()) }.boxed()
+                }
+            })
+            .before_acquire({
+                let acquired = acquired.clone();
+                move |_| {
+                    acquired.fetch_add(1, Ordering::Relaxed);
+                    async { Ok(()) }.boxed()
+                }
+            });
+        let driver = SQLiteDriver::new();
+        let pool = driver
+            .connect_pool_with_hooks(
+                "sqlite://:memory:".into(),
+                PoolConfig {
+                    max_size: 1,
+                    reset_on_release: true,
+                    ..PoolConfig::new()
+                },
+                hooks,
+            )
+            .await
+            .expect("Could not create the pool");
+        for _ in 0..3 {
+            let mut connection = pool.get().await.expect("Could not get a connection");
+            let row = pin!(connection.fetch("PRAGMA foreign_keys;"))
+                .next()
+                .await
+                .expect("Expected a row")
+                .expect("Could not read foreign_keys");
+            assert_eq!(
+                i64::try_from_value(row.values[0].clone()).expect("Expected an integer"),
+                1
+            );
+        }
+        // The last connection is released in the background
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(released.load(Ordering::Relaxed), 3);
+        assert_eq!(acquired.load(Ordering::Relaxed), 2);
+        let error = driver
+            .connect_pool_with_hooks(
+                "sqlite://:memory:".into(),
+                PoolConfig::new(),
+                PoolHooks::<SQLiteDriver>::new().after_connect(|c| {
+                    async move {
+                        c.execute("PRAGMA journal_mode = WAL;").await?;
+                        Err(anyhow!("Rejected"))
+                    }
+                    .boxed()
+                }),
+            )
+            .await
+            .expect("Could not create the pool")
+            .get()
+            .await;
+        assert!(
+            error.is_err(),
+            "A failing after_connect must fail the checkout"
+        );
+    }
+
+    #[tokio::test]
+    async fn status() {
+        init_logs();
+        let driver = SQLiteDriver::new();
+        let pool = driver
+            .connect_pool(
+                "sqlite://:memory:".into(),
+                PoolConfig {
+                    max_size: 2,
+                    ..PoolConfig::new()
+                },
+            )
+            .await
+            .expect("Could not create the pool");
+        let status = pool.status();
+        assert_eq!(status.max_size, 2);
+        assert_eq!(status.size, 0);
+        assert_eq!(status.acquired, 0);
+        {
+            let mut first = pool.get().await.expect("Could not get a connection");
+            let _second = pool.get().await.expect("Could not get a connection");
+            first
+                .execute("SELECT 1;")
+                .await
+                .expect("Could not run the query");
+            let status = pool.status();
+            assert_eq!(status.size, 2);
+            assert_eq!(status.in_use, 2);
+            assert_eq!(status.idle, 0);
+            assert_eq!(status.saturation(), 1.0);
+            assert!(
+                pool.timeout_get(Duration::from_millis(10)).await.is_err(),
+                "The pool is exhausted"
+            );
+        }
+        let status = pool.status();
+        assert_eq!(status.idle, 2);
+        assert_eq!(status.in_use, 0);
+        assert_eq!(status.acquired, 2);
+        assert_eq!(status.timeouts, 1);
+        assert_eq!(status.queries, 1);
+        assert!(status.mean_query_time().is_some());
+    }
+
+    #[tokio::test]
+    async fn routing() {
+        init_logs();
+        let driver = SQLiteDriver::new();
+        let connect = |url: &'static str| driver.connect_pool(url.into(), PoolConfig::new());
+        let pool = RoutingPool::new(connect("sqlite://:memory:").await.unwrap())
+            .replica(
+                connect("sqlite:///nonexistent/tank/replica.sqlite")
+                    .await
+                    .unwrap(),
+            )
+            .replica(connect("sqlite://:memory:").await.unwrap());
+        pool.primary()
+            .get()
+            .await
+            .expect("Could not get a connection")
+            .execute(MARKER)
+            .await
+            .expect("Could not create the marker");
+        for _ in 0..3 {
+            let mut connection = pool.get_read().await.expect("Could not get a connection");
+            let result;
+            silent_logs! {
+                result = connection.execute("SELECT * FROM marker;").await;
+            }
+            assert!(result.is_err(), "Reads must go to the healthy replica");
+        }
+        assert_eq!(
+            pool.available_replicas(),
+            1,
+            "The broken replica must be ejected"
+        );
+        assert!(has_marker(&pool).await, "Writes must go to the primary");
+
+        let pool = pool.read_your_writes(Duration::from_secs(60));
+        drop(pool.get().await.expect("Could not get a connection"));
+        let mut connection = pool.get_read().await.expect("Could not get a connection");
+        connection
+            .execute("SELECT * FROM marker;")
+            .await
+            .expect("Reads after a write must go to the primary");
+        drop(connection);
+        assert_eq!(pool.status().max_size, 3 * PoolConfig::new().max_size);
+
+        let replica = connect("sqlite://:memory:").await.unwrap();
+        let pool =
+            RoutingPool::new(connect("sqlite://:memory:").await.unwrap()).replica(replica.clone());
+        pool.close().await.expect("Could not close the pool");
+        assert!(
+            pool.primary().get().await.is_err(),
+            "The primary must be closed"
+        );
+        assert!(replica.get().await.is_err(), "The replicas must be closed");
+    }
+
+    #[tokio::test]
+    async fn routing_unhealthy_replica() {
+        init_logs();
+        let driver = SQLiteDriver::new();
+        let down = Arc::new(AtomicBool::new(false));
+        let replica = driver
+            .connect_pool_with_hooks(
+                "sqlite://:memory:".into(),
+                PoolConfig {
+                    max_size: 1,
+                    test_on_checkout: false,
+                    ..PoolConfig::new()
+                },
+                PoolHooks::new()
+                    .after_connect({
+                        let down = down.clone();
+                        move |_| future::ready(alive(&down)).boxed()
+                    })
+                    .before_acquire({
+                        let down = down.clone();
+                        move |_| future::ready(alive(&down)).boxed()
+                    }),
+            )
+            .await
+            .unwrap();
+        let pool = RoutingPool::new(
+            driver
+                .connect_pool("sqlite://:memory:".into(), PoolConfig::new())
+                .await
+                .unwrap(),
+        )
+        .replica(replica.clone())
+        .checkout_timeout(Duration::from_millis(100));
+        pool.primary()
+            .get()
+            .await
+            .expect("Could not get a connection")
+            .execute(MARKER)
+            .await
+            .expect("Could not create the marker");
+        let mut connection = pool.get_read().await.expect("Could not get a connection");
+        let result;
+        silent_logs! {
+            result = connection.execute("SELECT * FROM marker;").await;
+        }
+        assert!(result.is_err(), "Reads must go to the replica");
+        drop(connection);
+
+        down.store(true, Ordering::Relaxed);
+        let mut connection;
+        silent_logs! {
+            connection = pool.get_read().await.expect("Could not get a connection");
+        }
+        connection
+            .execute("SELECT * FROM marker;")
+            .await
+            .expect("Reads must fall back to the primary when the replica connections die");
+        drop(connection);
+        assert_eq!(
+            pool.available_replicas(),
+            0,
+            "The dead replica must be ejected"
+        );
+
+        let pool = RoutingPool::new(
+            driver
+                .connect_pool("sqlite://:memory:".into(), PoolConfig::new())
+                .await
+                .unwrap(),
+        )
+        .replica(replica.clone())
+        .checkout_timeout(Duration::from_millis(100));
+        down.store(false, Ordering::Relaxed);
+        let _held = replica.get().await.expect("Could not get a connection");
+        let start = Instant::now();
+        silent_logs! {
+            drop(pool.get_read().await.expect("Could not get a connection"));
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "An unresponsive replica must not stall the reads"
+        );
+        assert_eq!(
+            pool.available_replicas(),
+            0,
+            "The unresponsive replica must be ejected"
+        );
+    }
+
+    #[tokio::test]
+    async fn pool_executor() {
+        init_logs();
+        let driver = SQLiteDriver::new();
+        let mut pool = driver
+            .connect_pool(
+                "sqlite://:memory:".into(),
+                PoolConfig {
+                    max_size: 1,
+                    ..PoolConfig::new()
+                },
+            )
+            .await
+            .expect("Could not create the pool");
+        pool.execute("CREATE TABLE numbers (value INTEGER);")
+            .await
+            .expect("Could not create the table");
+        let affected = pool
+            .execute("INSERT INTO numbers (value) VALUES (1), (2), (3);")
+            .await
+            .expect("Could not insert the rows");
+        assert_eq!(affected.rows_affected, Some(3));
+        let rows = pool
+            .fetch("SELECT value FROM numbers ORDER BY value;")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Could not fetch the rows");
+        assert_eq!(rows.len(), 3);
+        assert_eq!(pool.status().in_use, 0, "The connection must be returned");
+
+        let routing = RoutingPool::new(pool.clone());
+        let (mut first, mut second) = (pool.clone(), &routing);
+        let (affected, rows) = tokio::join!(
+            first.execute("INSERT INTO numbers (value) VALUES (4);"),
+            second
+                .fetch("SELECT value FROM numbers;")
+                .try_collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            affected.expect("Could not insert the row").rows_affected,
+            Some(1)
+        );
+        assert!(rows.expect("Could not fetch the rows").len() >= 3);
+        drop(routing);
+
+        let mut pool = pool.into_arc();
+        {
+            let mut stream = pin!(pool.fetch("SELECT value FROM numbers;"));
+            stream
+                .next()
+                .await
+                .expect("Expected a row")
+                .expect("Expected a row");
+        }
+        assert_eq!(
+            pool.status().idle,
+            1,
+            "Dropping the stream returns the connection"
+        );
+        // Prepared statements belong to a single connection
+        let error = pool.prepare("SELECT 1;").await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Cannot prepare a query on a connection pool"),
+            "{error:#}"
+        );
+        let sql = || {
+            Sql::new(vec![
+                SqlPart::Text("SELECT ".into()),
+                SqlPart::Value(1.as_value()),
+            ])
+        };
+        let rows = sql().fetch(&mut pool).try_collect::<Vec<_>>().await;
+        assert!(rows.is_err(), "sql! queries are prepared");
+        assert!(
+            sql().execute(&mut pool).await.is_err(),
+            "sql! queries are prepared"
+        );
+        let mut query = "INSERT INTO numbers (value) VALUES (?);".into();
+        let result = pool.execute_batch(&mut query, [(5,), (6,)]).await;
+        assert!(result.is_err(), "Batches require a prepared query");
+        let mut connection = pool.get().await.expect("Could not get a connection");
+        let rows = sql()
+            .fetch(&mut connection)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("A checked out connection can prepare");
+        assert_eq!(rows.len(), 1);
+    }
+}
@@ -0,0 +1,114 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{Entity, Executor, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "generated_ticket")]
+pub struct Ticket {
+    #[tank(primary_key, auto_increment)]
+    pub id: Option<i64>,
+    pub title: String,
+}
+
+pub async fn auto_increment(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Ticket::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Ticket table");
+    Ticket::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Ticket table");
+
+    // Generated keys
+    let first = Ticket::insert_one(
+        executor,
+        &Ticket {
+            id: None,
+            title: "Broken login".into(),
+        },
+    )
+    .await
+    .expect("Failed to insert the first ticket")
+    .last_affected_id
+    .expect("The generated id must be reported");
+    let second = Ticket::insert_one(
+        executor,
+        &Ticket {
+            id: None,
+            title: "Slow search".into(),
+        },
+    )
+    .await
+    .expect("Failed to insert the second ticket")
+    .last_affected_id
+    .expect("The generated id must be reported");
+    assert!(second > first);
+    let ticket = Ticket::find_one(executor, expr!(Ticket::id == #second))
+        .await
+        .expect("Failed to query the second ticket")
+        .expect("The second ticket must exist");
+    assert_eq!(
+        ticket,
+        Ticket {
+            id: Some(second),
+            title: "Slow search".into(),
+        }
+    );
+
+    // Bulk insert
+    Ticket::insert_many(
+        executor,
+        &[
+            Ticket {
+                id: None,
+                title: "Typo".into(),
+            },
+            Ticket {
+                id: None,
+                title: "Dark mode".into(),
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the tickets");
+    let mut tickets = Ticket::find_many(executor, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the tickets");
+    tickets.sort_by_key(|v| v.id);
+    assert_eq!(tickets.len(), 4);
+    assert!(tickets.iter().all(|v| v.id.is_some()));
+    assert_eq!(tickets[3].title, "Dark mode");
+
+    // Bulk insert with some ids set
+    Ticket::insert_many(
+        executor,
+        &[
+            Ticket {
+                id: Some(1000),
+                title: "Export".into(),
+            },
+            Ticket {
+                id: None,
+                title: "Import".into(),
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the tickets with some ids");
+    let tickets = Ticket::find_many(executor, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the tickets");
+    assert_eq!(tickets.len(), 6);
+    assert!(tickets.iter().all(|v| v.id.is_some()));
+    assert!(tickets.contains(&Ticket {
+        id: Some(1000),
+        title: "Export".into(),
+    }));
+}
@@ -0,0 +1,59 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{Entity, Executor, expr};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "checked_reading")]
+pub struct Reading {
+    #[tank(primary_key)]
+    pub id: i64,
+    #[tank(check = expr!(Reading::humidity >= 0))]
+    pub humidity: i32,
+    #[tank(check = expr!(Reading::level < 100))]
+    pub level: i32,
+}
+
+pub async fn checks(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Reading::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Reading table");
+    Reading::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Reading table with its checks");
+
+    // Valid row
+    let reading = Reading {
+        id: 1,
+        humidity: 45,
+        level: 12,
+    };
+    Reading::insert_one(executor, &reading)
+        .await
+        .expect("Failed to insert a valid reading");
+
+    // Violations
+    let negative = Reading {
+        id: 2,
+        humidity: -1,
+        level: 12,
+    };
+    assert!(
+        Reading::insert_one(executor, &negative).await.is_err(),
+        "The check must reject a negative humidity"
+    );
+    let overflow = Reading {
+        id: 3,
+        humidity: 20,
+        level: 100,
+    };
+    assert!(
+        Reading::insert_one(executor, &overflow).await.is_err(),
+        "The check must reject a level out of range"
+    );
+}
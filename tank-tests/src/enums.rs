@@ -3,13 +3,13 @@ use rust_decimal::{Decimal, prelude::FromPrimitive};
 use std::{
     borrow::Cow,
     cell::{Cell, RefCell},
-    i128,
+    i128, mem,
     ops::Deref,
     pin::pin,
     sync::{Arc, LazyLock},
 };
 use tank::{
-    AsValue, Driver, DynQuery, Entity, Error, Executor, FixedDecimal, Query, QueryBuilder,
+    AsValue, Driver, DynQuery, Entity, Enum, Error, Executor, FixedDecimal, Query, QueryBuilder,
     QueryResult, RawQuery, Result, RowsAffected, SqlWriter, Value, anyhow, cols, expr,
     stream::{StreamExt, TryStreamExt},
 };
@@ -85,6 +85,34 @@ impl AsValue for AnotherEnum {
     }
 }
 
+#[derive(Enum, Clone, Copy, PartialEq, Eq, Debug)]
+#[tank(name = "issue_status", rename_all = "snake_case", check)]
+enum IssueStatus {
+    Open,
+    InProgress,
+    #[tank(name = "done")]
+    Closed,
+}
+
+#[derive(Enum, Clone, Copy, PartialEq, Eq, Debug)]
+#[tank(integer, check)]
+#[repr(i16)]
+enum IssuePriority {
+    Low = 1,
+    Medium = 5,
+    High = 10,
+}
+
+#[derive(Entity, PartialEq, Debug)]
+#[tank(schema = "testing", name = "derived_enum_issue")]
+struct Issue {
+    #[tank(primary_key)]
+    id: i32,
+    status: IssueStatus,
+    previous: Option<IssueStatus>,
+    priority: IssuePriority,
+}
+
 #[derive(Entity, PartialEq, Debug)]
 #[tank(primary_key = (id, another_enum))]
 struct Entry {
@@ -180,4 +208,66 @@ pub async fn enums(executor: &mut impl Executor) {
             },
         ]
     );
+
+    // Derived
+    Issue::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Issue table");
+    Issue::create_table(executor, true, true)
+        .await
+        .expect("Failed to create Issue table");
+    Issue::insert_many(
+        executor,
+        &[
+            Issue {
+                id: 1,
+                status: IssueStatus::Open,
+                previous: None,
+                priority: IssuePriority::High,
+            },
+            Issue {
+                id: 2,
+                status: IssueStatus::Closed,
+                previous: Some(IssueStatus::InProgress),
+                priority: IssuePriority::Low,
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the issues");
+    let status = IssueStatus::Closed;
+    let issue = Issue::find_one(executor, expr!(Issue::status == #status))
+        .await
+        .expect("Failed to query the issue")
+        .expect("The issue must exist");
+    assert_eq!(
+        issue,
+        Issue {
+            id: 2,
+            status: IssueStatus::Closed,
+            previous: Some(IssueStatus::InProgress),
+            priority: IssuePriority::Low,
+        }
+    );
+    let priority = IssuePriority::Medium;
+    let issues = Issue::find_many(executor, expr!(Issue::priority > #priority), None)
+        .map_ok(|v| v.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the issues");
+    assert_eq!(issues, [1]);
+    let value = executor
+        .fetch(
+            QueryBuilder::new()
+                .select(cols!(Issue::status))
+                .from(Issue::table())
+                .where_expr(expr!(Issue::id == 2))
+                .build(&executor.driver()),
+        )
+        .map_ok(|mut row| mem::take(&mut row.values[0]))
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the status");
+    assert_eq!(value, [Value::Varchar(Some("done".into()))]);
+    assert!(IssueStatus::try_from_value(Value::Varchar(Some("closed".into()))).is_err());
 }
@@ -0,0 +1,119 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{Columns, Entity, Executor, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Columns, Clone, Debug, PartialEq)]
+pub struct Stamp {
+    pub by: String,
+    pub revision: i32,
+}
+
+#[derive(Columns, Clone, Debug, PartialEq)]
+pub struct Money {
+    pub amount: i64,
+    pub currency: String,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "flatten_invoice")]
+pub struct Invoice {
+    #[tank(primary_key)]
+    pub id: i32,
+    #[tank(flatten, prefix = "total_")]
+    pub total: Money,
+    #[tank(flatten, prefix = "tax_")]
+    pub tax: Money,
+    #[tank(flatten)]
+    pub stamp: Stamp,
+    pub paid: bool,
+}
+
+pub async fn flatten(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Invoice::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Invoice table");
+    Invoice::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Invoice table");
+
+    // Insert
+    let invoices = [
+        Invoice {
+            id: 1,
+            total: Money {
+                amount: 12_000,
+                currency: "EUR".into(),
+            },
+            tax: Money {
+                amount: 2_640,
+                currency: "EUR".into(),
+            },
+            stamp: Stamp {
+                by: "alice".into(),
+                revision: 1,
+            },
+            paid: false,
+        },
+        Invoice {
+            id: 2,
+            total: Money {
+                amount: 500,
+                currency: "USD".into(),
+            },
+            tax: Money {
+                amount: 0,
+                currency: "USD".into(),
+            },
+            stamp: Stamp {
+                by: "bob".into(),
+                revision: 3,
+            },
+            paid: true,
+        },
+    ];
+    Invoice::insert_many(executor, &invoices)
+        .await
+        .expect("Failed to insert the invoices");
+
+    // Round trip
+    for expected in &invoices {
+        let id = expected.id;
+        let invoice = Invoice::find_one(executor, expr!(Invoice::id == #id))
+            .await
+            .expect("Failed to query the invoice")
+            .expect("The invoice must exist");
+        assert_eq!(invoice, *expected);
+    }
+
+    // Filter on flattened columns
+    let found = Invoice::find_many(
+        executor,
+        expr!(Invoice::total.amount > 1000 && Invoice::stamp.by == "alice"),
+        None,
+    )
+    .try_collect::<Vec<_>>()
+    .await
+    .expect("Failed to query the invoices");
+    assert_eq!(found, invoices[..1]);
+
+    // Update
+    let mut invoice = invoices[1].clone();
+    invoice.tax.amount = 50;
+    invoice.stamp.revision += 1;
+    invoice.paid = false;
+    invoice
+        .save(executor)
+        .await
+        .expect("Failed to save the invoice");
+    let updated = Invoice::find_one(executor, expr!(Invoice::id == 2))
+        .await
+        .expect("Failed to query the updated invoice")
+        .expect("The invoice must exist");
+    assert_eq!(updated, invoice);
+}
@@ -0,0 +1,79 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{Entity, Executor, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "generated_order_line")]
+pub struct OrderLine {
+    #[tank(primary_key)]
+    pub id: i64,
+    pub quantity: i32,
+    pub price: f64,
+    #[tank(generated = expr!(OrderLine::quantity * OrderLine::price), stored)]
+    pub total: f64,
+}
+
+pub async fn generated(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    OrderLine::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop OrderLine table");
+    OrderLine::create_table(executor, false, true)
+        .await
+        .expect("Failed to create OrderLine table");
+
+    // Computed by the database
+    let mut line = OrderLine {
+        id: 1,
+        quantity: 4,
+        price: 2.5,
+        total: 0.0,
+    };
+    OrderLine::insert_one(executor, &line)
+        .await
+        .expect("Failed to insert the order line");
+    OrderLine::insert_many(
+        executor,
+        &[
+            OrderLine {
+                id: 2,
+                quantity: 1,
+                price: 9.0,
+                total: 0.0,
+            },
+            OrderLine {
+                id: 3,
+                quantity: 3,
+                price: 0.5,
+                total: 0.0,
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the order lines");
+    let mut lines = OrderLine::find_many(executor, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the order lines");
+    lines.sort_by_key(|v| v.id);
+    assert_eq!(
+        lines.iter().map(|v| v.total).collect::<Vec<_>>(),
+        [10.0, 9.0, 1.5]
+    );
+
+    // Recomputed on update
+    line.quantity = 10;
+    line.save(executor)
+        .await
+        .expect("Failed to save the order line");
+    let line = OrderLine::find_one(executor, expr!(OrderLine::id == 1))
+        .await
+        .expect("Failed to query the order line")
+        .expect("The order line must exist");
+    assert_eq!(line.total, 25.0);
+}
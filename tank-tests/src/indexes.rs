@@ -0,0 +1,81 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{Entity, Executor, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(
+    schema = "testing",
+    name = "indexed_beacon",
+    index(columns = zone),
+    index(name = "indexed_beacon_serial", columns = serial, unique)
+)]
+pub struct Beacon {
+    #[tank(primary_key)]
+    pub id: i64,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    pub serial: String,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    pub zone: String,
+    pub signal: i32,
+}
+
+pub async fn indexes(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Beacon::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Beacon table");
+    Beacon::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Beacon table with its indexes");
+    let beacons = [
+        Beacon {
+            id: 1,
+            serial: "BX-100".into(),
+            zone: "north".into(),
+            signal: -40,
+        },
+        Beacon {
+            id: 2,
+            serial: "BX-200".into(),
+            zone: "south".into(),
+            signal: -72,
+        },
+        Beacon {
+            id: 3,
+            serial: "BX-300".into(),
+            zone: "north".into(),
+            signal: -55,
+        },
+    ];
+    Beacon::insert_many(executor, &beacons)
+        .await
+        .expect("Failed to insert the beacons");
+
+    // Indexed lookup
+    let mut north = Beacon::find_many(executor, expr!(Beacon::zone == "north"), None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the beacons by zone");
+    north.sort_by_key(|v| v.id);
+    assert_eq!(north, [beacons[0].clone(), beacons[2].clone()]);
+
+    // Unique index
+    #[cfg(not(feature = "disable-unique-indexes"))]
+    {
+        let duplicate = Beacon {
+            id: 4,
+            serial: "BX-100".into(),
+            zone: "east".into(),
+            signal: -60,
+        };
+        assert!(
+            Beacon::insert_one(executor, &duplicate).await.is_err(),
+            "The unique index must reject a duplicate serial"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+#![allow(unused_imports)]
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::LazyLock};
+use tank::{Entity, Executor, expr};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LineItem {
+    pub sku: String,
+    pub quantity: u32,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Delivery {
+    Standard,
+    Express { days: u8 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub gift: bool,
+    pub delivery: Delivery,
+    pub notes: BTreeMap<String, String>,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "json_basket")]
+pub struct Basket {
+    #[tank(primary_key)]
+    pub id: i64,
+    #[tank(json)]
+    pub items: Vec<LineItem>,
+    #[tank(json)]
+    pub settings: Settings,
+    #[tank(json)]
+    pub previous: Option<Settings>,
+}
+
+pub async fn json(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Basket::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Basket table");
+    Basket::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Basket table");
+
+    // Insert
+    let baskets = [
+        Basket {
+            id: 1,
+            items: vec![
+                LineItem {
+                    sku: "book-0042".into(),
+                    quantity: 2,
+                    tags: vec!["paper".into(), "sci-fi".into()],
+                },
+                LineItem {
+                    sku: "pen-0007".into(),
+                    quantity: 10,
+                    tags: vec![],
+                },
+            ],
+            settings: Settings {
+                gift: true,
+                delivery: Delivery::Express { days: 2 },
+                notes: BTreeMap::from([("door".into(), "ring twice".into())]),
+            },
+            previous: None,
+        },
+        Basket {
+            id: 2,
+            items: vec![],
+            settings: Settings {
+                gift: false,
+                delivery: Delivery::Standard,
+                notes: BTreeMap::new(),
+            },
+            previous: Some(Settings {
+                gift: true,
+                delivery: Delivery::Standard,
+                notes: BTreeMap::from([("floor".into(), "3".into())]),
+            }),
+        },
+    ];
+    Basket::insert_many(executor, &baskets)
+        .await
+        .expect("Failed to insert the baskets");
+
+    // Round trip
+    for expected in &baskets {
+        let id = expected.id;
+        let basket = Basket::find_one(executor, expr!(Basket::id == #id))
+            .await
+            .expect("Failed to query the basket")
+            .expect("The basket must exist");
+        assert_eq!(basket, *expected);
+    }
+
+    // Update
+    let mut basket = baskets[0].clone();
+    basket.items[0].quantity = 3;
+    basket.previous = Some(basket.settings.clone());
+    basket.settings.delivery = Delivery::Standard;
+    basket
+        .save(executor)
+        .await
+        .expect("Failed to save the basket");
+    let updated = Basket::find_one(executor, expr!(Basket::id == 1))
+        .await
+        .expect("Failed to query the updated basket")
+        .expect("The basket must exist");
+    assert_eq!(updated, basket);
+}
@@ -2,70 +2,96 @@ mod aggregates;
 mod ambiguity;
 mod arrays1;
 mod arrays2;
+mod auto_increment;
 mod books;
 mod cheat_sheet;
+mod checks;
 mod complex;
 mod conditions;
 mod custom;
 mod enums;
+mod flatten;
+mod generated;
 mod identifiers;
+mod indexes;
 mod insane;
 mod interval;
+mod json;
 mod keywords;
 mod kv_storage;
 mod limits;
 mod math;
 mod metrics;
+mod migrations;
 mod multiple;
+mod named_parameters;
+mod newtype;
 mod operations;
 mod orders;
 mod other;
+mod raw_sql;
 mod readme;
 mod requests;
+mod schema_diff;
 mod service;
 mod shopping;
 mod simple;
+mod structs;
 mod time;
 mod trade;
 mod transaction1;
 mod transaction2;
 mod user;
+mod views;
 
 pub use aggregates::*;
 pub use ambiguity::*;
 pub use arrays1::*;
 pub use arrays2::*;
+pub use auto_increment::*;
 pub use books::*;
 pub use cheat_sheet::*;
+pub use checks::*;
 pub use complex::*;
 pub use conditions::*;
 pub use custom::*;
 pub use enums::*;
+pub use flatten::*;
+pub use generated::*;
 pub use identifiers::*;
+pub use indexes::*;
 pub use insane::*;
 pub use interval::*;
+pub use json::*;
 pub use keywords::*;
 pub use kv_storage::*;
 pub use limits::*;
 use log::LevelFilter;
 pub use math::*;
 pub use metrics::*;
+pub use migrations::*;
 pub use multiple::*;
+pub use named_parameters::*;
+pub use newtype::*;
 pub use operations::*;
 pub use orders::*;
 pub use other::*;
+pub use raw_sql::*;
 pub use readme::*;
 pub use requests::*;
+pub use schema_diff::*;
 pub use service::*;
 pub use shopping::*;
 pub use simple::*;
 use std::env;
+pub use structs::*;
 use tank::{ConnectionPool, Driver};
 pub use time::*;
 pub use trade::*;
 pub use transaction1::*;
 pub use transaction2::*;
 pub use user::*;
+pub use views::*;
 
 pub fn init_logs() {
     let mut logger = env_logger::builder();
@@ -91,6 +117,7 @@ pub async fn execute_tests<D: Driver>(pool: &mut impl ConnectionPool<D>) {
     }
     do_test!(simple);
     do_test!(kv_storage);
+    do_test!(named_parameters);
     do_test!(trade_simple);
     do_test!(trade_multiple);
     do_test!(users);
@@ -121,10 +148,31 @@ pub async fn execute_tests<D: Driver>(pool: &mut impl ConnectionPool<D>) {
     do_test!(other);
     do_test!(service);
     do_test!(enums);
+    #[cfg(not(feature = "disable-structs"))]
+    do_test!(structs);
+    do_test!(json);
+    do_test!(flatten);
     do_test!(custom);
+    do_test!(newtype);
     do_test!(requests);
     do_test!(keywords);
     do_test!(identifiers);
+    #[cfg(not(feature = "disable-raw-sql"))]
+    do_test!(raw_sql);
+    #[cfg(not(feature = "disable-schema-diff"))]
+    do_test!(schema_diff);
+    #[cfg(not(feature = "disable-migrations"))]
+    do_test!(migrations);
+    #[cfg(not(feature = "disable-indexes"))]
+    do_test!(indexes);
+    #[cfg(not(feature = "disable-checks"))]
+    do_test!(checks);
+    #[cfg(not(feature = "disable-auto-increment"))]
+    do_test!(auto_increment);
+    #[cfg(not(feature = "disable-generated-columns"))]
+    do_test!(generated);
+    #[cfg(not(feature = "disable-views"))]
+    do_test!(views);
     do_test!(readme).expect("Readme examples test did not succeed");
     do_test!(operations).expect("Operations examples test did not succeed");
     do_test!(advanced_operations).expect("Advanced operations examples test did not succeed");
@@ -0,0 +1,232 @@
+#![allow(unused_imports)]
+use std::{env, fs, sync::LazyLock};
+use tank::{
+    AppliedMigration, Driver, DynQuery, Entity, Executor, Migration, Migrator, PooledConnection,
+    RawQuery, SqlWriter, anyhow, expr, future::FutureExt, stream::TryStreamExt,
+};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "migrated_pilot")]
+pub struct Pilot {
+    #[tank(primary_key)]
+    pub id: i64,
+    pub callsign: String,
+}
+
+fn pilots_migrator<D: Driver>(driver: &D) -> Migrator<D> {
+    let migrator =
+        Migrator::new().with_migration(Migration::rust("0001", "create pilots", |executor| {
+            async move { Pilot::create_table(executor, true, true).await }.boxed()
+        }));
+    let pilots = [
+        Pilot {
+            id: 1,
+            callsign: "Viper".into(),
+        },
+        Pilot {
+            id: 2,
+            callsign: "Iceman".into(),
+        },
+    ];
+    let mut query = DynQuery::default();
+    let writer = driver.sql_writer();
+    for pilot in &pilots {
+        writer.write_insert(&mut query, [pilot], false);
+    }
+    let migration = if let DynQuery::Raw(RawQuery(sql)) = query {
+        Migration::sql("0002", "insert pilots", sql)
+    } else {
+        // Best-effort drivers without SQL
+        Migration::rust("0002", "insert pilots", move |executor| {
+            let pilots = pilots.clone();
+            async move { Pilot::insert_many(executor, &pilots).await.map(|_| ()) }.boxed()
+        })
+    };
+    migrator.with_migration(migration.with_down(|executor| {
+        async move {
+            Pilot::delete_many(executor, expr!(Pilot::id <= 2))
+                .await
+                .map(|_| ())
+        }
+        .boxed()
+    }))
+}
+
+pub async fn migrations<D: Driver>(connection: &mut PooledConnection<D>) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    AppliedMigration::drop_table(connection, true, false)
+        .await
+        .expect("Failed to drop the migrations table");
+    Pilot::drop_table(connection, true, false)
+        .await
+        .expect("Failed to drop Pilot table");
+    let driver = connection.driver();
+
+    // Apply
+    let migrator = pilots_migrator(&driver);
+    let pending = migrator
+        .pending(connection)
+        .await
+        .expect("Failed to read the pending migrations");
+    assert_eq!(pending.len(), 2);
+    let applied = migrator
+        .run(connection)
+        .await
+        .expect("Failed to run the migrations");
+    assert_eq!(
+        applied.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+        ["0001", "0002"]
+    );
+    let pilots = Pilot::find_many(connection, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the pilots");
+    assert_eq!(pilots.len(), 2);
+
+    // Already applied
+    let applied = migrator
+        .run(connection)
+        .await
+        .expect("Failed to run the migrations again");
+    assert!(applied.is_empty());
+    let applied = migrator
+        .applied(connection)
+        .await
+        .expect("Failed to read the applied migrations");
+    assert_eq!(
+        applied
+            .iter()
+            .map(|v| (v.id.as_str(), v.name.as_str()))
+            .collect::<Vec<_>>(),
+        [("0001", "create pilots"), ("0002", "insert pilots")]
+    );
+    assert_eq!(applied[1].checksum, migrator.migrations()[1].checksum);
+
+    // Failing migration
+    let failing =
+        pilots_migrator(&driver).with_migration(Migration::rust("0003", "failing", |executor| {
+            async move {
+                Pilot::insert_one(
+                    executor,
+                    &Pilot {
+                        id: 3,
+                        callsign: "Maverick".into(),
+                    },
+                )
+                .await?;
+                Err(anyhow!("Migration failed on purpose"))
+            }
+            .boxed()
+        }));
+    assert!(failing.run(connection).await.is_err());
+    let applied = failing
+        .applied(connection)
+        .await
+        .expect("Failed to read the applied migrations");
+    assert_eq!(applied.len(), 2);
+    if driver.supports_transactional_ddl() {
+        let pilot = Pilot::find_one(connection, expr!(Pilot::id == 3))
+            .await
+            .expect("Failed to query the pilot");
+        assert_eq!(pilot, None, "The failing migration must be rolled back");
+    }
+
+    // Modified after being applied
+    let modified = Migrator::<D>::new()
+        .with_migration(Migration::rust("0001", "create pilots", |_| {
+            async { Ok(()) }.boxed()
+        }))
+        .with_migration(Migration::sql("0002", "insert pilots", "SELECT 1;"));
+    assert!(modified.pending(connection).await.is_err());
+    assert!(modified.run(connection).await.is_err());
+
+    // Lock released
+    let applied = migrator
+        .run(connection)
+        .await
+        .expect("The lock must be released after a failure");
+    assert!(applied.is_empty());
+
+    // Revert
+    assert!(
+        migrator.revert(connection, 2).await.is_err(),
+        "The migration 0001 has no down migration"
+    );
+    let reverted = migrator
+        .revert(connection, 1)
+        .await
+        .expect("Failed to revert the last migration");
+    assert_eq!(
+        reverted.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+        ["0002"]
+    );
+    let pilots = Pilot::find_many(connection, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the pilots");
+    assert!(pilots.is_empty());
+    let pending = migrator
+        .pending(connection)
+        .await
+        .expect("Failed to read the pending migrations");
+    assert_eq!(
+        pending.iter().map(|v| v.id.as_ref()).collect::<Vec<_>>(),
+        ["0002"]
+    );
+    let applied = migrator
+        .run(connection)
+        .await
+        .expect("Failed to apply the reverted migration again");
+    assert_eq!(applied.len(), 1);
+
+    // Files
+    let directory = env::temp_dir().join(format!("tank_migrations_{}", driver.name()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).expect("Failed to create the migrations directory");
+    fs::write(
+        directory.join("0002_add_rank.sql"),
+        "ALTER TABLE pilot ADD rank INT;",
+    )
+    .unwrap();
+    fs::write(
+        directory.join("0001_create_pilot.sql"),
+        "CREATE TABLE pilot (id INT);",
+    )
+    .unwrap();
+    fs::write(
+        directory.join("0002_add_rank.down.sql"),
+        "ALTER TABLE pilot DROP COLUMN rank;",
+    )
+    .unwrap();
+    fs::write(directory.join("README.md"), "Not a migration").unwrap();
+    let from_directory = Migrator::<D>::new()
+        .with_directory(&directory)
+        .expect("Failed to read the migrations directory");
+    assert_eq!(
+        from_directory
+            .migrations()
+            .iter()
+            .map(|v| (
+                v.id.as_ref(),
+                v.name.as_ref(),
+                v.sql_script().unwrap_or_default(),
+                v.down_sql_script(),
+            ))
+            .collect::<Vec<_>>(),
+        [
+            ("0001", "create pilot", "CREATE TABLE pilot (id INT);", None),
+            (
+                "0002",
+                "add rank",
+                "ALTER TABLE pilot ADD rank INT;",
+                Some("ALTER TABLE pilot DROP COLUMN rank;"),
+            ),
+        ]
+    );
+    let _ = fs::remove_dir_all(&directory);
+}
@@ -0,0 +1,84 @@
+use crate::silent_logs;
+use std::{collections::BTreeMap, sync::LazyLock};
+use tank::{AsValue, Driver, DynQuery, Entity, Executor, SqlWriter, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, PartialEq, Debug, Clone)]
+#[tank(schema = "testing", name = "named_probe", primary_key = (Self::zone, Self::id))]
+pub struct Probe {
+    pub zone: String,
+    pub id: i64,
+    pub signal: i64,
+}
+
+pub async fn named_parameters(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    silent_logs! {
+        // Silent logs for Valkey/Redis
+        Probe::drop_table(executor, true, false)
+            .await
+            .expect("Failed to drop Probe table");
+    }
+    Probe::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Probe table");
+    let probes =
+        [("north", 1, 42), ("north", 2, 17), ("south", 1, 8)].map(|(zone, id, signal)| Probe {
+            zone: zone.into(),
+            id,
+            signal,
+        });
+    Probe::insert_many(executor, &probes)
+        .await
+        .expect("Failed to insert the probes");
+
+    // Query
+    let mut query = Probe::prepare_find(
+        executor,
+        expr!(Probe::zone == :zone && Probe::id == :id),
+        None,
+    )
+    .await
+    .expect("Failed to prepare the probe query");
+    assert!(query.bind_named("signal", 42).is_err());
+    query
+        .bind_named("zone", "north")
+        .expect("Failed to bind zone")
+        .bind_named("id", 1)
+        .expect("Failed to bind id");
+    let values = executor
+        .fetch(&mut query)
+        .and_then(|row| async { Probe::from_row(row) })
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the probes with named parameters");
+    assert_eq!(values, [probes[0].clone()]);
+
+    // Batch
+    let writer = executor.driver().sql_writer();
+    let mut query = DynQuery::default();
+    writer.write_delete::<Probe>(&mut query, expr!(Probe::zone == :zone && Probe::id == :id));
+    let mut query = executor
+        .prepare(query)
+        .await
+        .expect("Failed to prepare the delete");
+    let result = executor
+        .execute_batch(
+            &mut query,
+            [("north", 2), ("south", 1)].map(|(zone, id)| {
+                BTreeMap::from([("zone", zone.as_value()), ("id", id.as_value())])
+            }),
+        )
+        .await
+        .expect("Failed to execute the delete batch");
+    assert_eq!(result.len(), 2);
+    let values = Probe::find_many(executor, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the probes");
+    assert_eq!(values, [probes[0].clone()]);
+}
@@ -0,0 +1,69 @@
+use crate::silent_logs;
+use std::sync::LazyLock;
+use tank::{Entity, Executor, Value, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Value, Clone, PartialEq, Debug)]
+pub struct ItemCode(pub String);
+
+#[derive(Value, Clone, Copy, PartialEq, Debug)]
+pub struct Quantity(pub i64);
+
+#[derive(Value, Clone, PartialEq, Debug)]
+pub struct Label(pub Option<String>);
+
+#[derive(Entity, Clone, PartialEq, Debug)]
+pub struct NewtypeItem {
+    #[tank(primary_key)]
+    pub code: ItemCode,
+    pub quantity: Quantity,
+    pub label: Label,
+    pub replaced_by: Option<ItemCode>,
+}
+
+pub async fn newtype(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    silent_logs! {
+        // Silent logs for Valkey/Redis
+        NewtypeItem::drop_table(executor, true, false)
+            .await
+            .expect("Failed to drop NewtypeItem table");
+    }
+    NewtypeItem::create_table(executor, true, true)
+        .await
+        .expect("Failed to create NewtypeItem table");
+
+    // Query
+    let items = [
+        NewtypeItem {
+            code: ItemCode("SUB-50".into()),
+            quantity: Quantity(3),
+            label: Label(Some("Subnormal".into())),
+            replaced_by: None,
+        },
+        NewtypeItem {
+            code: ItemCode("INF-51".into()),
+            quantity: Quantity(12),
+            label: Label(None),
+            replaced_by: Some(ItemCode("SUB-50".into())),
+        },
+    ];
+    NewtypeItem::insert_many(executor, &items)
+        .await
+        .expect("Failed to insert the items");
+    let code = ItemCode("INF-51".into());
+    let value = NewtypeItem::find_one(executor, expr!(NewtypeItem::code == #code))
+        .await
+        .expect("Failed to find the item");
+    assert_eq!(value.as_ref(), Some(&items[1]));
+    let minimum = Quantity(5);
+    let values = NewtypeItem::find_many(executor, expr!(NewtypeItem::quantity < #minimum), None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to find the items");
+    assert_eq!(values, [items[0].clone()]);
+}
@@ -0,0 +1,255 @@
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::LazyLock,
+};
+use tank::{AsValue, Entity, Executor, sql, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "raw_parcel")]
+pub struct Parcel {
+    #[tank(primary_key)]
+    pub id: i32,
+    pub recipient: String,
+    pub weight: i64,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Delivery {
+    recipient: String,
+    weight: i64,
+}
+
+pub async fn raw_sql(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Parcel::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Parcel table");
+    Parcel::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Parcel table");
+    let parcels = [
+        Parcel {
+            id: 1,
+            recipient: "O'Brien".into(),
+            weight: 1200,
+        },
+        Parcel {
+            id: 2,
+            recipient: "Robert'); DROP TABLE raw_parcel;--".into(),
+            weight: 80,
+        },
+        Parcel {
+            id: 3,
+            recipient: "Zoë".into(),
+            weight: 15_000,
+        },
+    ];
+    Parcel::insert_many(executor, &parcels)
+        .await
+        .expect("Failed to insert the parcels");
+
+    // Values bound by default
+    let id = 1;
+    let recipient = sql!(
+        "SELECT {ident:Parcel::recipient} FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} = {id}"
+    )
+    .fetch(executor)
+    .map_ok(|row| String::try_from_value(row.values[0].clone()))
+    .try_collect::<Vec<_>>()
+    .await
+    .expect("Failed to fetch the recipient")
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .expect("Failed to decode the recipient");
+    assert_eq!(recipient, ["O'Brien"]);
+
+    // Values written as literals
+    let recipient = "O'Brien";
+    let query = sql!(
+        "SELECT {ident:Parcel::id} FROM {ident:Parcel::table()} WHERE {ident:Parcel::recipient} = {recipient}"
+    )
+    .inline(&executor.driver())
+    .expect("Failed to write the query");
+    let ids = executor
+        .fetch(query)
+        .map_ok(|row| i64::try_from_value(row.values[0].clone()))
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to fetch the id")
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("Failed to decode the id");
+    assert_eq!(ids, [1]);
+
+    // Empty lists are rejected
+    let none: [i64; 0] = [];
+    assert!(
+        sql!("DELETE FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} NOT IN {#none}")
+            .execute(executor)
+            .await
+            .is_err()
+    );
+
+    // Prepared once, fetched later
+    let ids = [2, 3];
+    let query = sql!("SELECT * FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} IN {#ids}")
+        .prepare(executor)
+        .await
+        .expect("Failed to prepare the query");
+    let found = executor
+        .fetch(query)
+        .and_then(|row| async { Parcel::from_row(row) })
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to fetch the parcels");
+    assert_eq!(
+        found.iter().map(|v| v.id).collect::<HashSet<_>>(),
+        HashSet::from([2, 3])
+    );
+    let parcel = found
+        .into_iter()
+        .find(|v| v.id == 2)
+        .expect("The parcel 2 must exist");
+    assert_eq!(parcel, parcels[1]);
+
+    // Named arguments
+    let query = sql!(
+        "SELECT {ident:column} FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} = {id}",
+        column = "weight",
+        id = parcels[2].id,
+    )
+    .prepare(executor)
+    .await
+    .expect("Failed to prepare the query");
+    let weights = executor
+        .fetch(query)
+        .map_ok(|row| i64::try_from_value(row.values[0].clone()))
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to fetch the weight");
+    assert_eq!(weights.len(), 1);
+    assert_eq!(
+        *weights[0].as_ref().expect("Failed to decode the weight"),
+        15_000
+    );
+
+    // Named placeholders
+    let mut query = sql!(
+        "SELECT {ident:Parcel::weight} FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} IN (:first, :second, :first)"
+    )
+    .prepare(executor)
+    .await
+    .expect("Failed to prepare the query");
+    for (second, expected) in [(3, [1200, 15_000]), (2, [80, 1200])] {
+        query
+            .clear_bindings()
+            .expect("Failed to clear the bindings")
+            .bind_named("first", 1)
+            .expect("Failed to bind first")
+            .bind_named("second", second)
+            .expect("Failed to bind second");
+        let mut weights = executor
+            .fetch(&mut query)
+            .map_ok(|row| i64::try_from_value(row.values[0].clone()))
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to fetch the weights")
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to decode the weights");
+        weights.sort();
+        assert_eq!(weights, expected);
+    }
+    assert!(query.bind_named("third", 3).is_err());
+    // Batch execution
+    let mut query = sql!(
+        "INSERT INTO {ident:Parcel::table()} ({ident:Parcel::id}, {ident:Parcel::recipient}, {ident:Parcel::weight}) VALUES (:id, :recipient, :weight)"
+    )
+    .prepare(executor)
+    .await
+    .expect("Failed to prepare the insert");
+    let result = executor
+        .execute_batch(
+            &mut query,
+            [(4, "Ana", 300_i64), (5, "Bo", 450), (6, "Cy", 70)],
+        )
+        .await
+        .expect("Failed to execute the insert batch");
+    assert_eq!(result.len(), 3);
+    for affected in result {
+        if let Some(rows) = affected.rows_affected {
+            assert_eq!(rows, 1);
+        }
+    }
+    let mut query = sql!(
+        "UPDATE {ident:Parcel::table()} SET {ident:Parcel::weight} = :weight WHERE {ident:Parcel::id} = :id"
+    )
+    .prepare(executor)
+    .await
+    .expect("Failed to prepare the update");
+    let result = executor
+        .execute_batch(
+            &mut query,
+            [(4, 310_i64), (6, 75)].map(|(id, weight)| {
+                BTreeMap::from([("id", id.as_value()), ("weight", weight.as_value())])
+            }),
+        )
+        .await
+        .expect("Failed to execute the update batch");
+    assert_eq!(result.len(), 2);
+    for affected in result {
+        if let Some(rows) = affected.rows_affected {
+            assert_eq!(rows, 1);
+        }
+    }
+    let mut weights = sql!(
+        "SELECT {ident:Parcel::weight} FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} IN (4, 5, 6)"
+    )
+    .fetch(executor)
+    .map_ok(|row| i64::try_from_value(row.values[0].clone()))
+    .try_collect::<Vec<_>>()
+    .await
+    .expect("Failed to fetch the batch weights")
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .expect("Failed to decode the batch weights");
+    weights.sort();
+    assert_eq!(weights, [75, 310, 450]);
+
+    // Serde decoding
+    let mut deliveries = sql!(
+        "SELECT {ident:Parcel::recipient}, {ident:Parcel::weight} FROM {ident:Parcel::table()} WHERE {ident:Parcel::id} IN (4, 5, 6)"
+    )
+    .fetch(executor)
+    .map_ok(|row| row.deserialize::<Delivery>())
+    .try_collect::<Vec<_>>()
+    .await
+    .expect("Failed to fetch the deliveries")
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .expect("Failed to deserialize the deliveries");
+    deliveries.sort_by_key(|v| v.weight);
+    assert_eq!(
+        deliveries,
+        [
+            Delivery {
+                recipient: "Cy".into(),
+                weight: 75,
+            },
+            Delivery {
+                recipient: "Ana".into(),
+                weight: 310,
+            },
+            Delivery {
+                recipient: "Bo".into(),
+                weight: 450,
+            },
+        ]
+    );
+}
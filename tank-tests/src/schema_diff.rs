@@ -0,0 +1,136 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{Entity, Executor, MigrationStep, expr};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+mod before {
+    use tank::Entity;
+
+    #[derive(Entity)]
+    #[tank(schema = "testing", name = "crew_roster")]
+    pub struct Crew {
+        #[tank(primary_key)]
+        pub id: i64,
+        pub name: String,
+        pub notes: Option<String>,
+    }
+}
+
+mod relaxed {
+    use tank::Entity;
+
+    #[derive(Entity)]
+    #[tank(schema = "testing", name = "crew_roster")]
+    pub struct Crew {
+        #[tank(primary_key)]
+        pub id: i64,
+        pub name: Option<String>,
+        pub rank: Option<i64>,
+        pub unit: Option<String>,
+    }
+}
+
+#[derive(Entity, Debug, PartialEq)]
+#[tank(schema = "testing", name = "crew_roster")]
+pub struct Crew {
+    #[tank(primary_key)]
+    pub id: i64,
+    pub name: String,
+    pub rank: Option<i64>,
+}
+
+pub async fn schema_diff(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Crew::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Crew table");
+    let diff = before::Crew::migrate_table(executor, false, true)
+        .await
+        .expect("Failed to compare the missing table");
+    assert!(
+        matches!(
+            diff.changes.as_slice(),
+            [v] if matches!(v.step, MigrationStep::CreateTable(..))
+        ),
+        "{diff}"
+    );
+    before::Crew::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Crew table");
+    before::Crew::insert_one(
+        executor,
+        &before::Crew {
+            id: 1,
+            name: "Ada".into(),
+            notes: Some("Radio".into()),
+        },
+    )
+    .await
+    .expect("Failed to insert the crew member");
+    let diff = before::Crew::diff_table(executor)
+        .await
+        .expect("Failed to compare the table");
+    assert!(diff.is_empty(), "{diff}");
+
+    // Migrate
+    let diff = Crew::migrate_table(executor, false, true)
+        .await
+        .expect("Failed to compare the table");
+    let steps: Vec<_> = diff.changes.iter().map(|v| &v.step).collect();
+    assert!(
+        matches!(
+            steps.as_slice(),
+            [MigrationStep::AddColumn(..), MigrationStep::DropColumn(..)]
+        ),
+        "{diff}"
+    );
+    assert!(!diff.is_safe());
+    let result = Crew::migrate_table(executor, false, false).await;
+    assert!(result.is_err(), "Destructive changes must be refused");
+    Crew::migrate_table(executor, true, false)
+        .await
+        .expect("Failed to migrate the table");
+    let diff = Crew::diff_table(executor)
+        .await
+        .expect("Failed to compare the migrated table");
+    assert!(diff.is_empty(), "{diff}");
+
+    // Data survived
+    let crew = Crew::find_one(executor, expr!(Crew::id == 1))
+        .await
+        .expect("Failed to query the crew member")
+        .expect("The crew member must survive the migration");
+    assert_eq!(
+        crew,
+        Crew {
+            id: 1,
+            name: "Ada".into(),
+            rank: None,
+        }
+    );
+    // Unsupported changes
+    let diff = relaxed::Crew::diff_table(executor)
+        .await
+        .expect("Failed to compare the table");
+    if diff.unsupported().next().is_some() {
+        let result = diff.apply(executor, true).await;
+        assert!(result.is_err(), "Unsupported changes must be refused");
+        diff.skip_unsupported()
+            .apply(executor, true)
+            .await
+            .expect("Failed to apply the supported changes");
+        let diff = relaxed::Crew::diff_table(executor)
+            .await
+            .expect("Failed to compare the migrated table");
+        assert!(
+            diff.changes
+                .iter()
+                .all(|v| matches!(v.step, MigrationStep::AlterColumn(..))),
+            "{diff}"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{
+    Entity, Executor, Struct, expr,
+    stream::{StreamExt, TryStreamExt},
+};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Struct, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "struct_geo")]
+pub struct Geo {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Struct, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "struct_address")]
+pub struct Address {
+    pub street: String,
+    pub city: String,
+    #[tank(name = "zip_code")]
+    pub zip: Option<i32>,
+    pub geo: Geo,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "struct_customer")]
+pub struct Customer {
+    #[tank(primary_key)]
+    pub id: i64,
+    pub name: String,
+    pub address: Address,
+    pub billing: Option<Address>,
+}
+
+pub async fn structs(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Customer::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Customer table");
+    Customer::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Customer table");
+
+    // Insert
+    let customers = [
+        Customer {
+            id: 1,
+            name: "Giulia".into(),
+            address: Address {
+                street: "Via Appia".into(),
+                city: "Rome".into(),
+                zip: Some(100),
+                geo: Geo {
+                    lat: 41.9,
+                    lon: 12.5,
+                },
+            },
+            billing: None,
+        },
+        Customer {
+            id: 2,
+            name: "Hans".into(),
+            address: Address {
+                street: "Unter den Linden".into(),
+                city: "Berlin".into(),
+                zip: None,
+                geo: Geo {
+                    lat: 52.5,
+                    lon: 13.4,
+                },
+            },
+            billing: Some(Address {
+                street: "Marienplatz".into(),
+                city: "Munich".into(),
+                zip: Some(80331),
+                geo: Geo {
+                    lat: 48.1,
+                    lon: 11.6,
+                },
+            }),
+        },
+    ];
+    Customer::insert_many(executor, &customers)
+        .await
+        .expect("Failed to insert the customers");
+
+    // Round trip
+    let customer = Customer::find_one(executor, expr!(Customer::id == 2))
+        .await
+        .expect("Failed to query the customer")
+        .expect("The customer must exist");
+    assert_eq!(customer, customers[1]);
+    let customer = Customer::find_one(executor, expr!(Customer::id == 1))
+        .await
+        .expect("Failed to query the customer")
+        .expect("The customer must exist");
+    assert_eq!(customer, customers[0]);
+
+    // Field access
+    #[cfg(not(feature = "disable-struct-fields"))]
+    {
+        let names = Customer::find_many(executor, expr!(Customer::address.city == "Rome"), None)
+            .map_ok(|v| v.name)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to query the customers by city");
+        assert_eq!(names, ["Giulia"]);
+        let names = Customer::find_many(executor, expr!(Customer::address.geo.lat > 50), None)
+            .map_ok(|v| v.name)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to query the customers by latitude");
+        assert_eq!(names, ["Hans"]);
+    }
+
+    // Update
+    let mut customer = customers[0].clone();
+    customer.address.zip = None;
+    customer.billing = Some(customers[1].address.clone());
+    customer
+        .save(executor)
+        .await
+        .expect("Failed to save the customer");
+    let updated = Customer::find_one(executor, expr!(Customer::id == 1))
+        .await
+        .expect("Failed to query the updated customer")
+        .expect("The customer must exist");
+    assert_eq!(updated, customer);
+}
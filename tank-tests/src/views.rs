@@ -0,0 +1,222 @@
+#![allow(unused_imports)]
+use std::sync::LazyLock;
+use tank::{
+    Entity, Executor, QueryBuilder, cols, expr, join,
+    stream::{StreamExt, TryStreamExt},
+};
+use tokio::sync::Mutex;
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "view_sale")]
+pub struct Sale {
+    #[tank(primary_key)]
+    pub id: i64,
+    pub region: String,
+    pub amount: f64,
+    pub paid: bool,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(schema = "testing", name = "view_region")]
+pub struct Region {
+    #[tank(primary_key)]
+    pub code: String,
+    pub label: String,
+}
+
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(
+    schema = "testing",
+    name = "view_paid_sale",
+    view = QueryBuilder::new()
+        .select(cols!(Sale::id, Sale::region, Sale::amount))
+        .from(Sale::table())
+        .where_expr(expr!(Sale::paid == true))
+)]
+pub struct PaidSale {
+    #[tank(primary_key)]
+    pub id: i64,
+    pub region: String,
+    pub amount: f64,
+}
+
+#[cfg(not(feature = "disable-materialized-views"))]
+#[derive(Entity, Clone, Debug, PartialEq)]
+#[tank(
+    schema = "testing",
+    name = "view_region_total",
+    view = QueryBuilder::new()
+        .select(cols!(Sale::region, SUM(Sale::amount) as total))
+        .from(Sale::table())
+        .group_by([Sale::region]),
+    materialized
+)]
+pub struct RegionTotal {
+    pub region: String,
+    pub total: f64,
+}
+
+pub async fn views(executor: &mut impl Executor) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup, the views depend on the table
+    #[cfg(not(feature = "disable-materialized-views"))]
+    RegionTotal::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop RegionTotal view");
+    PaidSale::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop PaidSale view");
+    Sale::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Sale table");
+    Region::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Region table");
+    Sale::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Sale table");
+    Region::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Region table");
+    PaidSale::create_table(executor, false, true)
+        .await
+        .expect("Failed to create PaidSale view");
+    Sale::insert_many(
+        executor,
+        &[
+            Sale {
+                id: 1,
+                region: "EU".into(),
+                amount: 120.0,
+                paid: true,
+            },
+            Sale {
+                id: 2,
+                region: "EU".into(),
+                amount: 80.0,
+                paid: false,
+            },
+            Sale {
+                id: 3,
+                region: "US".into(),
+                amount: 45.5,
+                paid: true,
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the sales");
+    Region::insert_many(
+        executor,
+        &[
+            Region {
+                code: "EU".into(),
+                label: "Europe".into(),
+            },
+            Region {
+                code: "US".into(),
+                label: "United States".into(),
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the regions");
+
+    // The view follows the table
+    let mut paid = PaidSale::find_many(executor, true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the paid sales");
+    paid.sort_by_key(|v| v.id);
+    assert_eq!(
+        paid,
+        [
+            PaidSale {
+                id: 1,
+                region: "EU".into(),
+                amount: 120.0,
+            },
+            PaidSale {
+                id: 3,
+                region: "US".into(),
+                amount: 45.5,
+            },
+        ]
+    );
+    let sale = PaidSale::find_one(executor, expr!(PaidSale::amount < 100))
+        .await
+        .expect("Failed to query the paid sale")
+        .expect("The paid sale must exist");
+    assert_eq!(sale.id, 3);
+
+    // Joined like a table
+    #[cfg(not(feature = "disable-joins"))]
+    {
+        #[derive(Entity, Debug, PartialEq)]
+        struct PaidSaleRegion {
+            id: i64,
+            label: String,
+        }
+        let result = executor
+            .fetch(
+                QueryBuilder::new()
+                    .select(cols!(P.id, R.label))
+                    .from(join!(PaidSale P JOIN Region R ON P.region == R.code))
+                    .order_by(cols!(P.id ASC))
+                    .build(&executor.driver()),
+            )
+            .map_ok(PaidSaleRegion::from_row)
+            .map(Result::flatten)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to query the paid sales joined with the regions");
+        assert_eq!(
+            result,
+            [
+                PaidSaleRegion {
+                    id: 1,
+                    label: "Europe".into(),
+                },
+                PaidSaleRegion {
+                    id: 3,
+                    label: "United States".into(),
+                },
+            ]
+        );
+    }
+
+    // Materialized views keep their rows until refreshed
+    #[cfg(not(feature = "disable-materialized-views"))]
+    {
+        RegionTotal::create_table(executor, false, true)
+            .await
+            .expect("Failed to create RegionTotal view");
+        Sale::insert_one(
+            executor,
+            &Sale {
+                id: 4,
+                region: "US".into(),
+                amount: 4.5,
+                paid: true,
+            },
+        )
+        .await
+        .expect("Failed to insert the sale");
+        let total = RegionTotal::find_one(executor, expr!(RegionTotal::region == "US"))
+            .await
+            .expect("Failed to query the region total")
+            .expect("The region total must exist");
+        assert_eq!(total.total, 45.5);
+        RegionTotal::refresh(executor)
+            .await
+            .expect("Failed to refresh RegionTotal view");
+        let total = RegionTotal::find_one(executor, expr!(RegionTotal::region == "US"))
+            .await
+            .expect("Failed to query the region total")
+            .expect("The region total must exist");
+        assert_eq!(total.total, 50.0);
+    }
+}
@@ -41,6 +41,15 @@ impl Connection for ValkeyConnection {
             commands: Default::default(),
         }))
     }
+
+    async fn ping(&mut self) -> Result<()> {
+        redis::cmd("PING")
+            .query_async::<String>(&mut self.connection)
+            .await
+            .map_err(Error::new)
+            .context("While pinging Valkey")?;
+        Ok(())
+    }
 }
 
 impl Executor for ValkeyConnection {
@@ -68,7 +77,7 @@ impl Executor for ValkeyConnection {
             if prepared.is_empty() {
                 return;
             }
-            let pipeline = prepared.make_pipeline();
+            let pipeline = prepared.make_pipeline()?;
             let context = || {
                 format!(
                     "While executing the query: {}",
@@ -1,30 +1,87 @@
-use redis::{Cmd, Pipeline};
-use std::fmt::{self, Debug, Display};
-use tank_core::{AsValue, ColumnDef, Prepared, Result};
+use crate::{ValkeyDriver, ValkeySqlWriter};
+use anyhow::anyhow;
+use redis::{Arg, Cmd, Pipeline};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Display},
+    str,
+};
+use tank_core::{AsValue, ColumnDef, Driver, DynQuery, Fragment, Prepared, Result, SqlWriter};
+
+/// Delimits the named parameters written in the keys, replaced by the bound values when running.
+const PARAMETER_MARKER: char = '\0';
 
 #[derive(Default, Debug)]
 pub struct ValkeyPrepared {
     pub(crate) commands: Vec<Cmd>,
     pub(crate) columns: Vec<&'static ColumnDef>,
+    pub(crate) names: Vec<String>,
+    pub(crate) params: Vec<(String, String)>,
 }
 
 impl ValkeyPrepared {
     pub fn is_empty(&self) -> bool {
         self.commands.is_empty()
     }
-    pub fn make_pipeline(&self) -> Pipeline {
+    pub fn make_pipeline(&self) -> Result<Pipeline> {
+        if let Some(name) = self
+            .names
+            .iter()
+            .find(|name| !self.params.iter().any(|(k, _)| k == *name))
+        {
+            return Err(anyhow!("The parameter `{name}` is not bound"));
+        }
         let mut pipeline = Pipeline::new();
         for cmd in &self.commands {
-            pipeline.add_command(cmd.clone());
+            if self.names.is_empty() {
+                pipeline.add_command(cmd.clone());
+                continue;
+            }
+            let mut bound = Cmd::new();
+            for arg in cmd.args_iter() {
+                match arg {
+                    Arg::Simple(v) => bound.arg(self.bind_arg(v).as_ref()),
+                    // Only the scan commands have a cursor, they are not generated
+                    _ => bound.cursor_arg(0),
+                };
+            }
+            pipeline.add_command(bound);
         }
-        pipeline
+        Ok(pipeline)
     }
-    pub fn into_pipeline(self) -> Pipeline {
+    pub fn into_pipeline(self) -> Result<Pipeline> {
+        if !self.names.is_empty() {
+            return self.make_pipeline();
+        }
         let mut pipeline = Pipeline::new();
         for cmd in self.commands {
             pipeline.add_command(cmd);
         }
-        pipeline
+        Ok(pipeline)
+    }
+    /// Text written in the key in place of the named parameter.
+    pub(crate) fn parameter_marker(name: &str) -> String {
+        format!("{PARAMETER_MARKER}{name}{PARAMETER_MARKER}")
+    }
+    /// Remember the named parameters written in the key.
+    pub(crate) fn add_key_parameters(&mut self, key: &str) {
+        for name in key.split(PARAMETER_MARKER).skip(1).step_by(2) {
+            if !self.names.iter().any(|v| v == name) {
+                self.names.push(name.to_string());
+            }
+        }
+    }
+    fn bind_arg<'a>(&self, arg: &'a [u8]) -> Cow<'a, [u8]> {
+        match str::from_utf8(arg) {
+            Ok(v) if v.contains(PARAMETER_MARKER) => {
+                let mut v = v.to_string();
+                for (name, value) in &self.params {
+                    v = v.replace(&Self::parameter_marker(name), value);
+                }
+                Cow::Owned(v.into_bytes())
+            }
+            _ => Cow::Borrowed(arg),
+        }
     }
 }
 
@@ -37,6 +94,7 @@ impl Prepared for ValkeyPrepared {
     where
         Self: Sized,
     {
+        self.params.clear();
         Ok(self)
     }
 
@@ -53,6 +111,30 @@ impl Prepared for ValkeyPrepared {
     {
         Ok(self)
     }
+
+    fn bind_named(&mut self, name: &str, value: impl AsValue) -> Result<&mut Self>
+    where
+        Self: Sized,
+    {
+        if !self.names.iter().any(|v| v == name) {
+            return Err(anyhow!(
+                "The query does not have a parameter named `{name}`"
+            ));
+        }
+        // Written the same way as the constant values of the keys
+        let mut out = DynQuery::default();
+        ValkeyDriver::default().sql_writer().write_value(
+            &mut ValkeySqlWriter::make_context(Fragment::SqlSelectWhere),
+            &mut out,
+            &value.as_value(),
+        );
+        let value = out.as_str().into_owned();
+        match self.params.iter_mut().find(|(k, _)| k == name) {
+            Some((_, target)) => *target = value,
+            None => self.params.push((name.to_string(), value)),
+        }
+        Ok(self)
+    }
 }
 
 impl Display for ValkeyPrepared {
@@ -71,6 +71,9 @@ impl SqlWriter for ValkeySqlWriter {
     fn write_string(&self, _context: &mut Context, out: &mut DynQuery, value: &str) {
         out.push_str(value);
     }
+    fn write_named_parameter(&self, _context: &mut Context, out: &mut DynQuery, name: &str) {
+        out.push_str(&ValkeyPrepared::parameter_marker(name));
+    }
 
     fn write_create_schema<E>(&self, out: &mut DynQuery, _if_not_exists: bool)
     where
@@ -93,6 +96,24 @@ impl SqlWriter for ValkeySqlWriter {
         Self: Sized,
         E: Entity,
     {
+        if E::columns().iter().any(|c| c.check.is_some()) || !E::check_defs().is_empty() {
+            log::warn!(
+                "Valkey/Redis does not support check constraints, the ones of {} are ignored",
+                E::table().full_name(self.separator()),
+            );
+        }
+        if let Some(column) = E::columns().iter().find(|c| c.auto_increment) {
+            log::error!(
+                "Valkey/Redis does not support auto increment columns, `{}` must be assigned by the application",
+                column.name()
+            );
+        }
+        if let Some(column) = E::columns().iter().find(|c| c.generated.is_some()) {
+            log::error!(
+                "Valkey/Redis does not support generated columns, `{}` must be computed by the application",
+                column.name()
+            );
+        }
         Self::prepare_query(out, &mut Default::default());
     }
 
@@ -105,6 +126,41 @@ impl SqlWriter for ValkeySqlWriter {
         Self::prepare_query(out, &mut Default::default());
     }
 
+    fn write_create_view<E>(&self, out: &mut DynQuery, _if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "Valkey/Redis does not support views, cannot create {}",
+            E::table().full_name(self.separator())
+        );
+        Self::prepare_query(out, &mut Default::default());
+    }
+
+    fn write_drop_view<E>(&self, out: &mut DynQuery, _if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "Valkey/Redis does not support views, cannot drop {}",
+            E::table().full_name(self.separator())
+        );
+        Self::prepare_query(out, &mut Default::default());
+    }
+
+    fn write_refresh_view<E>(&self, _out: &mut DynQuery)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        log::error!(
+            "Valkey/Redis does not support views, cannot refresh {}",
+            E::table().full_name(self.separator())
+        );
+    }
+
     fn write_select<'a, Data>(&self, out: &mut DynQuery, query: &impl SelectQuery<Data>)
     where
         Self: Sized,
@@ -137,6 +193,7 @@ impl SqlWriter for ValkeySqlWriter {
             return;
         }
         let prepared = Self::prepare_query(out, &mut context);
+        prepared.add_key_parameters(&is_pk_condition.key);
         let mut columns = query.get_select();
         let columns_count = columns.clone().into_iter().count();
         let key = is_pk_condition.key.as_str();
@@ -307,6 +364,7 @@ impl SqlWriter for ValkeySqlWriter {
         }
         let prepared = Self::prepare_query(out, &mut context);
         let key = is_pk_condition.key;
+        prepared.add_key_parameters(&key);
         prepared.commands.push(Cmd::del(&key));
         for column in E::columns().iter().filter(|c| !c.value.is_scalar()) {
             let child_key = format!("{key}{}{}", self.separator(), column.name());
@@ -55,6 +55,31 @@ impl ExpressionVisitor for IsField {
     }
 }
 
+/// Constant or named parameter, the values that can be written in a key.
+#[derive(Default, Debug)]
+pub struct IsKeyValue;
+impl ExpressionVisitor for IsKeyValue {
+    fn visit_operand(
+        &mut self,
+        writer: &dyn SqlWriter,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &Operand,
+    ) -> bool {
+        matches!(value, Operand::NamedParameter(..))
+            || IsConstant.visit_operand(writer, context, out, value)
+    }
+    fn visit_binary_op(
+        &mut self,
+        writer: &dyn SqlWriter,
+        context: &mut Context,
+        out: &mut DynQuery,
+        value: &BinaryOp<&dyn Expression, &dyn Expression>,
+    ) -> bool {
+        IsConstant.visit_binary_op(writer, context, out, value)
+    }
+}
+
 pub struct IsPKCondition {
     keys_with_names: bool,
     pub key: String,
@@ -119,7 +144,7 @@ impl ExpressionVisitor for IsPKCondition {
             }
             BinaryOpType::Equal => {
                 let mut is_column = IsField::default();
-                let mut is_constant = IsConstant::default();
+                let mut is_constant = IsKeyValue;
                 let value = if value
                     .lhs
                     .accept_visitor(&mut is_column, writer, context, out)
@@ -38,4 +38,9 @@ impl Connection for YourDBConnection {
     async fn begin(&mut self) -> Result<YourDBTransaction<'_>> {
         Err(anyhow!("Transactions are not supported by YourDB"))
     }
+
+    async fn ping(&mut self) -> Result<()> {
+        // Override if the database has a cheaper probe than `SELECT 1`
+        self.execute("SELECT 1").await.map(|_| ())
+    }
 }
@@ -1,14 +1,18 @@
 use std::fmt::{self, Display, Formatter};
-use tank_core::{AsValue, Prepared, Result};
+use tank_core::{AsValue, ParameterNames, Prepared, Result};
 
 #[derive(Debug)]
 pub struct YourDBPrepared {
     pub(crate) index: u64,
+    pub(crate) names: ParameterNames,
 }
 
 impl YourDBPrepared {
     pub(crate) fn new() -> Self {
-        Self { index: 0 }
+        Self {
+            index: 0,
+            names: Default::default(),
+        }
     }
 }
 
@@ -27,6 +31,13 @@ impl Prepared for YourDBPrepared {
         self.index = index + 1;
         Ok(self)
     }
+    fn parameter_names(&self) -> Option<&ParameterNames> {
+        Some(&self.names)
+    }
+    fn set_parameter_names(&mut self, names: ParameterNames) -> Result<()> {
+        self.names = names;
+        Ok(())
+    }
 }
 
 impl Display for YourDBPrepared {
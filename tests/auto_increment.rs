@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{DynQuery, Entity, GenericSqlWriter, SqlWriter};
+
+    #[derive(Entity)]
+    #[tank(schema = "support", name = "ticket")]
+    struct Ticket {
+        #[tank(primary_key, auto_increment)]
+        id: Option<i64>,
+        title: String,
+    }
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_ticket_create_table() {
+        assert!(Ticket::columns()[0].auto_increment);
+        assert!(!Ticket::columns()[1].auto_increment);
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Ticket>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "support"."ticket" (
+                "id" BIGINT PRIMARY KEY GENERATED BY DEFAULT AS IDENTITY,
+                "title" VARCHAR NOT NULL);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_ticket_insert() {
+        let mut query = DynQuery::default();
+        WRITER.write_insert(
+            &mut query,
+            [&Ticket {
+                id: None,
+                title: "Broken login".into(),
+            }],
+            false,
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                INSERT INTO "support"."ticket" ("title") VALUES
+                ('Broken login');
+            "#}
+            .trim()
+        );
+        let mut query = DynQuery::default();
+        WRITER.write_insert(
+            &mut query,
+            [
+                &Ticket {
+                    id: Some(7),
+                    title: "Slow search".into(),
+                },
+                &Ticket {
+                    id: None,
+                    title: "Typo".into(),
+                },
+            ],
+            false,
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                INSERT INTO "support"."ticket" ("id", "title") VALUES
+                (7, 'Slow search'),
+                (DEFAULT, 'Typo');
+            "#}
+            .trim()
+        );
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{DynQuery, Entity, GenericSqlWriter, SqlWriter, expr};
+
+    #[derive(Entity)]
+    #[tank(
+        schema = "hotel",
+        name = "booking",
+        check = expr!(Booking::check_in < Booking::check_out),
+        check = expr!(Booking::guests <= 8)
+    )]
+    struct Booking {
+        #[tank(primary_key)]
+        id: i64,
+        check_in: i32,
+        check_out: i32,
+        #[tank(check = expr!(Booking::guests > 0))]
+        guests: i16,
+        #[tank(check = expr!(Booking::price >= 0))]
+        price: f64,
+    }
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_booking_checks() {
+        let columns = Booking::columns();
+        assert!(columns[0].check.is_none());
+        assert!(columns[1].check.is_none());
+        assert!(columns[3].check.is_some());
+        assert!(columns[4].check.is_some());
+        assert_eq!(Booking::check_defs().len(), 2);
+    }
+
+    #[test]
+    fn test_booking_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Booking>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "hotel"."booking" (
+                "id" BIGINT PRIMARY KEY,
+                "check_in" INTEGER NOT NULL,
+                "check_out" INTEGER NOT NULL,
+                "guests" SMALLINT NOT NULL,
+                "price" DOUBLE NOT NULL,
+                CHECK ("guests" > 0),
+                CHECK ("price" >= 0),
+                CHECK ("check_in" < "check_out"),
+                CHECK ("guests" <= 8));
+            "#}
+            .trim()
+        );
+    }
+}
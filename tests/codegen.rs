@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{
+        Action, ColumnInfo, ColumnRef, PrimaryKeyType, TableRef, Value, entity_source, rust_type,
+    };
+
+    fn column(name: &str, value: Value) -> ColumnInfo {
+        ColumnInfo {
+            column_ref: ColumnRef {
+                name: name.to_string().into(),
+                table: "Invoice_Line".into(),
+                schema: "billing".into(),
+            },
+            value,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rust_type() {
+        assert_eq!(rust_type(&Value::Int32(None)).unwrap(), "i32");
+        assert_eq!(
+            rust_type(&Value::Decimal(None, 10, 2)).unwrap(),
+            "tank::FixedDecimal<10, 2>"
+        );
+        assert_eq!(
+            rust_type(&Value::Decimal(None, 0, 0)).unwrap(),
+            "rust_decimal::Decimal"
+        );
+        assert_eq!(
+            rust_type(&Value::List(None, Box::new(Value::Varchar(None)))).unwrap(),
+            "Vec<String>"
+        );
+        assert_eq!(
+            rust_type(&Value::Array(None, Box::new(Value::Float32(None)), 3)).unwrap(),
+            "[f32; 3]"
+        );
+        assert_eq!(
+            rust_type(&Value::Map(
+                None,
+                Box::new(Value::Varchar(None)),
+                Box::new(Value::Int64(None))
+            ))
+            .unwrap(),
+            "std::collections::HashMap<String, i64>"
+        );
+        assert_eq!(rust_type(&Value::Blob(None)).unwrap(), "Box<[u8]>");
+        assert_eq!(rust_type(&Value::Unknown(None)), None);
+    }
+
+    #[test]
+    fn test_entity_source() {
+        let table = TableRef {
+            name: "Invoice_Line".into(),
+            schema: "billing".into(),
+            ..Default::default()
+        };
+        let columns = [
+            ColumnInfo {
+                primary_key: PrimaryKeyType::PartOfPrimaryKey,
+                ..column("invoice", Value::Int64(None))
+            },
+            ColumnInfo {
+                primary_key: PrimaryKeyType::PartOfPrimaryKey,
+                ..column("Position", Value::Int16(None))
+            },
+            ColumnInfo {
+                comment: "Sold product".into(),
+                unique: true,
+                references: Some(ColumnRef {
+                    name: "code".into(),
+                    table: "product".into(),
+                    schema: "store".into(),
+                }),
+                on_delete: Some(Action::Restrict),
+                ..column("product", Value::Varchar(None))
+            },
+            ColumnInfo {
+                default: Some("'1'::integer".into()),
+                ..column("quantity", Value::Int32(None))
+            },
+            ColumnInfo {
+                nullable: true,
+                default: Some("0.0".into()),
+                ..column("price", Value::Decimal(None, 10, 2))
+            },
+            ColumnInfo {
+                nullable: true,
+                default: Some("now()".into()),
+                ..column("type", Value::TimestampWithTimezone(None))
+            },
+            ColumnInfo {
+                column_type: "GEOMETRY".into(),
+                ..column("area", Value::Unknown(None))
+            },
+        ];
+        assert_eq!(
+            entity_source(&table, &columns),
+            indoc! {r#"
+                #[derive(Entity, Debug, Clone, PartialEq)]
+                #[tank(schema = "billing", name = "Invoice_Line", primary_key = ("invoice", "Position"))]
+                pub struct InvoiceLine {
+                    pub invoice: i64,
+                    #[tank(name = "Position")]
+                    pub position: i16,
+                    /// Sold product
+                    #[tank(unique, references = store.product(code), on_delete = restrict)]
+                    pub product: String,
+                    #[tank(default = tank::Value::Int32(Some(1)))]
+                    pub quantity: i32,
+                    #[tank(default = tank::Value::Decimal(Some("0.0".parse().unwrap()), 10, 2))]
+                    pub price: Option<tank::FixedDecimal<10, 2>>,
+                    // Default: now()
+                    #[tank(name = "type")]
+                    pub type_: Option<time::OffsetDateTime>,
+                    // Unsupported type: GEOMETRY
+                    pub area: String,
+                }
+            "#}
+        );
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{AsValue, DynQuery, Entity, Enum, GenericSqlWriter, SqlWriter, Value};
+
+    #[derive(Enum, Clone, Copy, PartialEq, Eq, Debug)]
+    #[tank(rename_all = "SCREAMING_SNAKE_CASE", check)]
+    enum Shipping {
+        Standard,
+        NextDay,
+        #[tank(name = "pickup")]
+        InStore,
+    }
+
+    #[derive(Enum, Clone, Copy, PartialEq, Eq, Debug)]
+    #[tank(schema = "shop", name = "sizes")]
+    enum Size {
+        Small,
+        Large,
+    }
+
+    #[derive(Enum, Clone, Copy, PartialEq, Eq, Debug)]
+    #[tank(integer, check)]
+    #[repr(u8)]
+    enum Level {
+        Bronze = 1,
+        Silver,
+        Gold = 10,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "shop", name = "parcel")]
+    struct Parcel {
+        #[tank(primary_key)]
+        id: i64,
+        shipping: Shipping,
+        size: Option<Size>,
+        level: Level,
+    }
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_enum_values() {
+        assert_eq!(
+            Shipping::NextDay.as_value(),
+            Value::Varchar(Some("NEXT_DAY".into()))
+        );
+        assert_eq!(
+            Shipping::InStore.as_value(),
+            Value::Varchar(Some("pickup".into()))
+        );
+        assert_eq!(Level::Silver.as_value(), Value::UInt8(Some(2)));
+        assert_eq!(Level::as_empty_value(), Value::UInt8(None));
+        assert_eq!(
+            Shipping::try_from_value(Value::Varchar(Some("STANDARD".into()))).unwrap(),
+            Shipping::Standard
+        );
+        assert_eq!(
+            Level::try_from_value(Value::Int64(Some(10))).unwrap(),
+            Level::Gold
+        );
+        assert_eq!(Option::<Size>::try_from_value(Value::Null).unwrap(), None);
+    }
+
+    #[test]
+    fn test_enum_decode_errors() {
+        assert_eq!(
+            Shipping::try_from_value(Value::Varchar(Some("EXPRESS".into())))
+                .unwrap_err()
+                .to_string(),
+            "Unknown value `EXPRESS` for the enum Shipping, expected one of: STANDARD, NEXT_DAY, pickup"
+        );
+        assert_eq!(
+            Level::try_from_value(Value::UInt8(Some(3)))
+                .unwrap_err()
+                .to_string(),
+            "Unknown discriminant 3 for the enum Level, expected one of: 1, 2, 10"
+        );
+        assert!(Size::try_from_value(Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_enum_def() {
+        let columns = Parcel::columns();
+        assert!(columns[0].enum_def.is_none());
+        let shipping = columns[1].enum_def.expect("Shipping is an enum");
+        assert_eq!(shipping.name, "shipping");
+        assert!(shipping.is_textual());
+        assert!(shipping.check);
+        assert_eq!(
+            shipping.labels().collect::<Vec<_>>(),
+            ["STANDARD", "NEXT_DAY", "pickup"]
+        );
+        let size = columns[2].enum_def.expect("Size is an enum");
+        assert_eq!((size.schema, size.name), ("shop", "sizes"));
+        assert!(columns[2].nullable);
+        let level = columns[3].enum_def.expect("Level is an enum");
+        assert!(!level.is_textual());
+        assert_eq!(level.labels().count(), 0);
+    }
+
+    #[test]
+    fn test_parcel_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Parcel>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "shop"."parcel" (
+                "id" BIGINT PRIMARY KEY,
+                "shipping" VARCHAR NOT NULL,
+                "size" VARCHAR,
+                "level" UTINYINT NOT NULL,
+                CHECK ("shipping" IN ('STANDARD','NEXT_DAY','pickup')),
+                CHECK ("level" IN (1,2,10)));
+            "#}
+            .trim()
+        );
+    }
+}
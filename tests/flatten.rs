@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::borrow::Cow;
+    use tank::{
+        Columns, DynQuery, Entity, GenericSqlWriter, QueryBuilder, Row, SqlWriter, Value, expr,
+    };
+
+    #[derive(Columns, Clone, PartialEq, Debug)]
+    struct Audit {
+        created_by: String,
+        #[tank(name = "version")]
+        revision: Option<i32>,
+    }
+
+    #[derive(Columns, Clone, PartialEq, Debug)]
+    struct Money {
+        amount: f64,
+        currency: String,
+        #[tank(flatten, prefix = "audit_")]
+        audit: Audit,
+    }
+
+    #[derive(Entity, Clone, PartialEq, Debug)]
+    #[tank(schema = "shop", name = "order")]
+    struct Order {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(flatten, prefix = "price_")]
+        price: Money,
+        #[tank(flatten, prefix = "shipping_")]
+        shipping: Money,
+        #[tank(flatten)]
+        audit: Audit,
+        note: Option<String>,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn order() -> Order {
+        Order {
+            id: 7,
+            price: Money {
+                amount: 19.5,
+                currency: "EUR".into(),
+                audit: Audit {
+                    created_by: "pricing".into(),
+                    revision: Some(2),
+                },
+            },
+            shipping: Money {
+                amount: 4.0,
+                currency: "EUR".into(),
+                audit: Audit {
+                    created_by: "courier".into(),
+                    revision: None,
+                },
+            },
+            audit: Audit {
+                created_by: "alice".into(),
+                revision: Some(1),
+            },
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_flatten_columns() {
+        let names = Order::columns()
+            .iter()
+            .map(|c| c.column_ref.name.as_ref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "id",
+                "note",
+                "price_amount",
+                "price_currency",
+                "price_audit_created_by",
+                "price_audit_version",
+                "shipping_amount",
+                "shipping_currency",
+                "shipping_audit_created_by",
+                "shipping_audit_version",
+                "created_by",
+                "version",
+            ]
+        );
+        let column = &Order::columns()[5];
+        assert_eq!(column.column_ref.table, "order");
+        assert_eq!(column.column_ref.schema, "shop");
+        assert!(column.nullable);
+        assert!(!Order::columns()[2].nullable);
+        assert_eq!(Order::primary_key_def().len(), 1);
+        assert!(Money::contains("audit_version"));
+        assert!(!Money::contains("version"));
+        assert_eq!(
+            Money::field_column(&["audit", "revision", "x"]),
+            Some((Cow::Borrowed("audit_version"), 2))
+        );
+        assert_eq!(Money::field_column(&["audit"]), None);
+    }
+
+    #[test]
+    fn test_flatten_row_values() {
+        let values = order().row_values();
+        assert_eq!(values.len(), 12);
+        assert_eq!(values[0], Value::Int64(Some(7)));
+        assert_eq!(values[1], Value::Varchar(None));
+        assert_eq!(values[2], Value::Float64(Some(19.5)));
+        assert_eq!(values[5], Value::Int32(Some(2)));
+        assert_eq!(values[9], Value::Int32(None));
+        assert_eq!(values[10], Value::Varchar(Some("alice".into())));
+    }
+
+    #[test]
+    fn test_flatten_from_row() {
+        let order = order();
+        let row = Row::new(
+            Order::columns()
+                .iter()
+                .map(|c| c.column_ref.name.to_string())
+                .rev()
+                .collect(),
+            order.row_values().into_iter().rev().collect(),
+        );
+        assert_eq!(Order::from_row(row).unwrap(), order);
+        let row = Row::new(
+            ["id", "price_amount", "price_currency"]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            [
+                Value::Int64(Some(1)),
+                Value::Float64(Some(1.0)),
+                Value::Varchar(Some("USD".into())),
+            ]
+            .into(),
+        );
+        assert!(Order::from_row(row).is_err());
+    }
+
+    #[test]
+    fn test_flatten_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Order>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "shop"."order" (
+                "id" BIGINT PRIMARY KEY,
+                "note" VARCHAR,
+                "price_amount" DOUBLE NOT NULL,
+                "price_currency" VARCHAR NOT NULL,
+                "price_audit_created_by" VARCHAR NOT NULL,
+                "price_audit_version" INTEGER,
+                "shipping_amount" DOUBLE NOT NULL,
+                "shipping_currency" VARCHAR NOT NULL,
+                "shipping_audit_created_by" VARCHAR NOT NULL,
+                "shipping_audit_version" INTEGER,
+                "created_by" VARCHAR NOT NULL,
+                "version" INTEGER);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_flatten_field_access() {
+        let mut query = DynQuery::default();
+        WRITER.write_select(
+            &mut query,
+            &QueryBuilder::new()
+                .select([Order::id, Order::note])
+                .from(Order::table())
+                .where_expr(expr!(
+                    Order::price.amount > 10
+                        && Order::price.audit.revision == 2
+                        && Order::audit.created_by == "alice"
+                )),
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                SELECT "id", "note"
+                FROM "shop"."order"
+                WHERE "price_amount" > 10 AND "price_audit_version" = 2 AND "created_by" = 'alice';
+            "#}
+            .trim()
+        );
+    }
+}
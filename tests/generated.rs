@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{DynQuery, Entity, GenericSqlWriter, SqlWriter, expr};
+
+    #[derive(Entity)]
+    #[tank(schema = "shop", name = "order_line")]
+    struct OrderLine {
+        #[tank(primary_key)]
+        id: i64,
+        quantity: i32,
+        price: f64,
+        #[tank(generated = expr!(OrderLine::quantity * OrderLine::price), stored)]
+        total: f64,
+        #[tank(generated = expr!(OrderLine::quantity > 10))]
+        bulk: bool,
+    }
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_order_line_create_table() {
+        let columns = OrderLine::columns();
+        assert!(columns[2].generated.is_none());
+        assert!(columns[3].generated.is_some());
+        assert!(columns[3].stored);
+        assert!(columns[4].generated.is_some());
+        assert!(!columns[4].stored);
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<OrderLine>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "shop"."order_line" (
+                "id" BIGINT PRIMARY KEY,
+                "quantity" INTEGER NOT NULL,
+                "price" DOUBLE NOT NULL,
+                "total" DOUBLE GENERATED ALWAYS AS ("quantity" * "price") STORED,
+                "bulk" BOOLEAN GENERATED ALWAYS AS ("quantity" > 10) VIRTUAL);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_order_line_insert() {
+        let line = OrderLine {
+            id: 1,
+            quantity: 3,
+            price: 2.5,
+            total: 0.0,
+            bulk: false,
+        };
+        let mut query = DynQuery::default();
+        WRITER.write_insert(&mut query, [&line], true);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                INSERT INTO "shop"."order_line" ("id", "quantity", "price") VALUES
+                (1, 3, 2.5)
+                ON CONFLICT ("id") DO UPDATE SET
+                "quantity" = EXCLUDED."quantity",
+                "price" = EXCLUDED."price";
+            "#}
+            .trim()
+        );
+    }
+}
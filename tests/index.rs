@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{DynQuery, Entity, GenericSqlWriter, Order, SqlWriter};
+
+    #[derive(Entity)]
+    #[tank(
+        schema = "fleet",
+        name = "aircraft",
+        index(columns = (tail_number)),
+        index(name = "aircraft_active_squadron", columns = (squadron, "commissioned" desc), unique, where = Aircraft::active == true),
+        index(columns = tags, using = "gin")
+    )]
+    struct Aircraft {
+        #[tank(primary_key)]
+        id: i64,
+        tail_number: String,
+        squadron: String,
+        commissioned: i32,
+        active: bool,
+        tags: Vec<String>,
+    }
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_aircraft_indexes() {
+        let indexes = Aircraft::indexes();
+        assert_eq!(indexes.len(), 3);
+        assert_eq!(indexes[0].name, "aircraft_tail_number_idx");
+        assert_eq!(
+            indexes[0]
+                .columns
+                .iter()
+                .map(|(c, o)| (c.name(), *o))
+                .collect::<Vec<_>>(),
+            [("tail_number", Order::ASC)]
+        );
+        assert!(!indexes[0].unique);
+        assert!(indexes[0].condition.is_none());
+        assert_eq!(indexes[0].using, "");
+        assert_eq!(indexes[1].name, "aircraft_active_squadron");
+        assert_eq!(
+            indexes[1]
+                .columns
+                .iter()
+                .map(|(c, o)| (c.name(), *o))
+                .collect::<Vec<_>>(),
+            [("squadron", Order::ASC), ("commissioned", Order::DESC)]
+        );
+        assert!(indexes[1].unique);
+        assert!(indexes[1].condition.is_some());
+        assert_eq!(indexes[2].name, "aircraft_tags_idx");
+        assert_eq!(indexes[2].using, "gin");
+    }
+
+    #[test]
+    fn test_aircraft_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Aircraft>(&mut query, true);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE IF NOT EXISTS "fleet"."aircraft" (
+                "id" BIGINT PRIMARY KEY,
+                "tail_number" VARCHAR NOT NULL,
+                "squadron" VARCHAR NOT NULL,
+                "commissioned" INTEGER NOT NULL,
+                "active" BOOLEAN NOT NULL,
+                "tags" VARCHAR[] NOT NULL);
+                CREATE INDEX IF NOT EXISTS "aircraft_tail_number_idx" ON "fleet"."aircraft" ("tail_number");
+                CREATE UNIQUE INDEX IF NOT EXISTS "aircraft_active_squadron" ON "fleet"."aircraft" ("squadron", "commissioned" DESC) WHERE "active" = true;
+                CREATE INDEX IF NOT EXISTS "aircraft_tags_idx" ON "fleet"."aircraft" USING gin ("tags");
+            "#}
+            .trim()
+        );
+    }
+}
@@ -204,7 +204,7 @@ mod tests {
             _column: u128,
         }
 
-        let join = join!((Charlie JOIN Alpha ON Charlie::_column < Alpha::_b) JOIN Bravo ON Alpha::_a == Bravo::_second);
+        let join = join!((Charlie JOIN Alpha ON Charlie::_column < Alpha::_a) JOIN Bravo ON Alpha::_b == Bravo::_second);
         assert!(matches!(
             join,
             Join {
@@ -227,7 +227,7 @@ mod tests {
                             ..
                         },
                         rhs: ColumnRef {
-                            name: Cow::Borrowed("b"),
+                            name: Cow::Borrowed("a"),
                             table: Cow::Borrowed("alpha"),
                             ..
                         },
@@ -242,7 +242,7 @@ mod tests {
                 on: Some(BinaryOp {
                     op: BinaryOpType::Equal,
                     lhs: ColumnRef {
-                        name: Cow::Borrowed("a"),
+                        name: Cow::Borrowed("b"),
                         ..
                     },
                     rhs: ColumnRef {
@@ -258,7 +258,7 @@ mod tests {
         join.write_table_name(&WRITER, &mut Default::default(), &mut query);
         assert_eq!(
             query.as_str(),
-            r#""another_table" JOIN "my_data"."alpha" ON "another_table"."column" < "my_data"."alpha"."b" JOIN "bravo" ON "my_data"."alpha"."a" = "bravo"."second""#
+            r#""another_table" JOIN "my_data"."alpha" ON "another_table"."column" < "my_data"."alpha"."a" JOIN "bravo" ON "my_data"."alpha"."b" = "bravo"."second""#
         );
     }
 
@@ -0,0 +1,157 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use indoc::indoc;
+    use serde::{Deserialize, Serialize};
+    use tank::{AsValue, DynQuery, Entity, GenericSqlWriter, Json, Row, SqlWriter, Value};
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+    struct LineItem {
+        sku: String,
+        quantity: u32,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+    struct Settings {
+        theme: String,
+        #[serde(default)]
+        beta: bool,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "shop", name = "cart")]
+    struct Cart {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(json)]
+        items: Vec<LineItem>,
+        #[tank(json)]
+        settings: Settings,
+        #[tank(json)]
+        previous: Option<Settings>,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn items() -> Vec<LineItem> {
+        vec![
+            LineItem {
+                sku: "book-0042".into(),
+                quantity: 2,
+            },
+            LineItem {
+                sku: "pen-0007".into(),
+                quantity: 10,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_json_value() {
+        let json = serde_json::json!([
+            {"sku": "book-0042", "quantity": 2},
+            {"sku": "pen-0007", "quantity": 10},
+        ]);
+        assert_eq!(Json(items()).as_value(), Value::Json(Some(json.clone())));
+        assert_eq!(Json(None::<Settings>).as_value(), Value::Json(None));
+        assert_eq!(
+            Json::<Vec<LineItem>>::try_from_value(Value::Json(Some(json.clone())))
+                .unwrap()
+                .0,
+            items()
+        );
+        assert_eq!(
+            Json::<Vec<LineItem>>::try_from_value(Value::Varchar(Some(json.to_string().into())))
+                .unwrap()
+                .0,
+            items()
+        );
+        assert_eq!(
+            Json::<Option<Settings>>::try_from_value(Value::Null)
+                .unwrap()
+                .0,
+            None
+        );
+        assert_eq!(
+            Json::<Settings>::parse(r#"{"theme": "dark"}"#).unwrap().0,
+            Settings {
+                theme: "dark".into(),
+                beta: false,
+            }
+        );
+        assert!(Json::<Settings>::try_from_value(Value::Json(Some(json))).is_err());
+        assert!(Json::<Settings>::try_from_value(Value::Int32(Some(1))).is_err());
+    }
+
+    #[test]
+    fn test_json_columns() {
+        let columns = Cart::columns();
+        assert_eq!(columns[1].value, Value::Json(None));
+        assert!(!columns[1].nullable);
+        assert_eq!(columns[2].value, Value::Json(None));
+        assert!(!columns[2].nullable);
+        assert_eq!(columns[3].value, Value::Json(None));
+        assert!(columns[3].nullable);
+        assert_eq!(columns[1].column_type.get("postgres"), Some(&"JSONB"));
+    }
+
+    #[test]
+    fn test_json_row_values() {
+        let cart = Cart {
+            id: 1,
+            items: items(),
+            settings: Settings {
+                theme: "dark".into(),
+                beta: true,
+            },
+            previous: None,
+        };
+        let values = cart.row_values();
+        assert!(matches!(
+            values[1],
+            Value::Json(Some(serde_json::Value::Array(..)))
+        ));
+        assert_eq!(
+            values[2],
+            Value::Json(Some(serde_json::json!({"theme": "dark", "beta": true})))
+        );
+        assert_eq!(values[3], Value::Json(None));
+    }
+
+    #[test]
+    fn test_json_from_row() {
+        let row = Row::new(
+            ["id", "items", "settings", "previous"]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            [
+                Value::Int64(Some(1)),
+                Value::Varchar(Some(r#"[{"sku":"pen-0007","quantity":10}]"#.into())),
+                Value::Json(Some(serde_json::json!({"theme": "light"}))),
+                Value::Json(None),
+            ]
+            .into(),
+        );
+        let cart = Cart::from_row(row).unwrap();
+        assert_eq!(cart.items, items()[1..]);
+        assert_eq!(cart.settings.theme, "light");
+        assert_eq!(cart.previous, None);
+    }
+
+    #[test]
+    fn test_cart_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Cart>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "shop"."cart" (
+                "id" BIGINT PRIMARY KEY,
+                "items" JSON NOT NULL,
+                "settings" JSON NOT NULL,
+                "previous" JSON);
+            "#}
+            .trim()
+        );
+    }
+}
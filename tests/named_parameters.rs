@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use tank::{
+        DynQuery, Entity, GenericSqlWriter, Operand, ParameterNames, QueryBuilder, SqlWriter, expr,
+        rewrite_named_parameters,
+    };
+
+    #[derive(Entity)]
+    #[tank(schema = "shop", name = "invoice")]
+    struct Invoice {
+        #[tank(primary_key)]
+        id: i64,
+        customer: String,
+        total: f64,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_rewrite_named_parameters() {
+        let (sql, names) = rewrite_named_parameters(
+            "SELECT * FROM invoice WHERE customer = :customer AND (total > @min OR total < $max) AND id != :customer",
+            &WRITER,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM invoice WHERE customer = ? AND (total > ? OR total < ?) AND id != ?"
+        );
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            [(0, "customer"), (1, "min"), (2, "max"), (3, "customer")]
+        );
+        assert_eq!(names.indexes("customer").collect::<Vec<_>>(), [0, 3]);
+        assert_eq!(names.indexes("total").count(), 0);
+        assert_eq!(
+            names.offset(1).indexes("min").collect::<Vec<_>>(),
+            [2],
+            "The indexes must be shifted"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_named_parameters_untouched() {
+        let sql = indoc::indoc! {r#"
+            SELECT ':quoted', "col:umn", `@tick`, total::TEXT, @@version, $1, a$b
+            FROM invoice -- WHERE id = :comment
+            /* AND customer = :block */
+            WHERE customer = $$ :dollar $$ AND total = $tag$ :tagged $tag$
+                AND meta = {'key':true}
+        "#};
+        let (rewritten, names) = rewrite_named_parameters(sql, &WRITER).unwrap();
+        assert_eq!(rewritten, sql);
+        assert_eq!(names, ParameterNames::new());
+    }
+
+    #[test]
+    fn test_rewrite_named_parameters_mixed() {
+        assert!(
+            rewrite_named_parameters(
+                "SELECT * FROM invoice WHERE id = ? AND customer = :customer",
+                &WRITER
+            )
+            .is_err()
+        );
+        assert!(
+            rewrite_named_parameters(
+                "SELECT * FROM invoice WHERE id = $1 AND customer = :customer",
+                &WRITER
+            )
+            .is_err()
+        );
+        assert!(
+            rewrite_named_parameters(
+                "SELECT * FROM invoice WHERE id = ? AND customer = '?'",
+                &WRITER
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_rewrite_named_parameters_writer() {
+        struct BackslashWriter;
+        impl SqlWriter for BackslashWriter {
+            fn as_dyn(&self) -> &dyn SqlWriter {
+                self
+            }
+            fn named_parameter_prefixes(&self) -> &'static [char] {
+                &[':', '$']
+            }
+            fn string_backslash_escapes(&self) -> bool {
+                true
+            }
+        }
+        let (sql, names) = rewrite_named_parameters(
+            r"SELECT @rownum := @rownum + 1, 'it\'s :quoted' FROM invoice WHERE customer = :customer",
+            &BackslashWriter,
+        )
+        .unwrap();
+        assert_eq!(
+            sql,
+            r"SELECT @rownum := @rownum + 1, 'it\'s :quoted' FROM invoice WHERE customer = ?"
+        );
+        assert_eq!(names.iter().collect::<Vec<_>>(), [(0, "customer")]);
+        let sql = "SET @total = 10";
+        assert_eq!(
+            rewrite_named_parameters(sql, &BackslashWriter).unwrap().0,
+            sql
+        );
+        assert_eq!(
+            rewrite_named_parameters(r"SELECT 'C:\' WHERE a = :a", &WRITER)
+                .unwrap()
+                .0,
+            r"SELECT 'C:\' WHERE a = ?",
+            "Backslashes are not escapes by default"
+        );
+    }
+
+    #[test]
+    fn test_expr_named_parameters() {
+        let condition =
+            expr!(Invoice::customer == :customer && Invoice::total >= @min || Invoice::id == $id);
+        let mut query = DynQuery::default();
+        WRITER.write_select(
+            &mut query,
+            &QueryBuilder::new()
+                .select([Invoice::id])
+                .from(Invoice::table())
+                .where_expr(&condition),
+        );
+        assert_eq!(
+            query.as_str(),
+            "SELECT \"id\"\nFROM \"shop\".\"invoice\"\nWHERE \"customer\" = :customer AND \"total\" >= :min OR \"id\" = :id;"
+        );
+        let (sql, names) = rewrite_named_parameters(&query.as_str(), &WRITER).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT \"id\"\nFROM \"shop\".\"invoice\"\nWHERE \"customer\" = ? AND \"total\" >= ? OR \"id\" = ?;"
+        );
+        assert_eq!(names.len(), 3);
+        assert_eq!(expr!(:customer), Operand::NamedParameter("customer"));
+    }
+}
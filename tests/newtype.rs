@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{
+        AsValue, DynQuery, Entity, GenericSqlWriter, QueryBuilder, Row, SqlWriter, Value, expr,
+    };
+    use uuid::Uuid;
+
+    #[derive(tank::Value, Clone, Copy, PartialEq, Debug)]
+    struct UserId(Uuid);
+
+    #[derive(tank::Value, Clone, PartialEq, Debug)]
+    struct Email {
+        address: String,
+    }
+
+    #[derive(tank::Value, Clone, PartialEq, Debug)]
+    struct Nickname(Option<String>);
+
+    mod units {
+        #[derive(tank::Value, Clone, PartialEq, Debug)]
+        pub struct Meters<T>(pub T);
+    }
+
+    type Score = i32;
+    type ManagerId = Option<UserId>;
+    use units::Meters as Distance;
+
+    #[derive(Entity, Clone, PartialEq, Debug)]
+    #[tank(schema = "app", name = "user")]
+    struct User {
+        #[tank(primary_key)]
+        id: UserId,
+        email: Email,
+        nickname: Nickname,
+        score: Score,
+        manager: ManagerId,
+        friends: Vec<UserId>,
+        walked: Distance<f64>,
+    }
+
+    mod shadowed {
+        use tank::{Entity, FixedDecimal};
+
+        type Uuid = String;
+        type Date = i64;
+
+        #[derive(Entity)]
+        pub struct Event {
+            id: Uuid,
+            day: Date,
+            days: Vec<Date>,
+            price: FixedDecimal<10, 2>,
+        }
+    }
+    use shadowed::Event;
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn user() -> User {
+        User {
+            id: UserId(Uuid::from_u128(1)),
+            email: Email {
+                address: "ada@example.com".into(),
+            },
+            nickname: Nickname(None),
+            score: 42,
+            manager: Some(UserId(Uuid::from_u128(2))),
+            friends: vec![UserId(Uuid::from_u128(3))],
+            walked: Distance(12.5),
+        }
+    }
+
+    #[test]
+    fn test_newtype_value() {
+        let id = UserId(Uuid::from_u128(7));
+        assert_eq!(UserId::as_empty_value(), Value::Uuid(None));
+        assert_eq!(id.as_value(), Value::Uuid(Some(Uuid::from_u128(7))));
+        assert_eq!(
+            UserId::try_from_value(Value::Uuid(Some(Uuid::from_u128(7)))).unwrap(),
+            id
+        );
+        assert_eq!(
+            UserId::parse("00000000-0000-0000-0000-000000000007").unwrap(),
+            id
+        );
+        assert!(UserId::try_from_value(Value::Boolean(Some(true))).is_err());
+        assert!(!UserId::NULLABLE);
+        assert!(Nickname::NULLABLE);
+        assert_eq!(
+            Email::try_from_value(Value::Varchar(Some("a@b.c".into()))).unwrap(),
+            Email {
+                address: "a@b.c".into()
+            }
+        );
+        assert_eq!(Distance::<u16>::as_empty_value(), Value::UInt16(None));
+    }
+
+    #[test]
+    fn test_newtype_columns() {
+        let columns = User::columns();
+        assert_eq!(columns[0].value, Value::Uuid(None));
+        assert!(!columns[0].nullable);
+        assert_eq!(columns[1].value, Value::Varchar(None));
+        assert!(!columns[1].nullable);
+        assert_eq!(columns[2].value, Value::Varchar(None));
+        assert!(columns[2].nullable);
+        assert_eq!(columns[3].value, Value::Int32(None));
+        assert!(!columns[3].nullable);
+        assert_eq!(columns[4].value, Value::Uuid(None));
+        assert!(columns[4].nullable);
+        assert_eq!(
+            columns[5].value,
+            Value::List(None, Box::new(Value::Uuid(None)))
+        );
+        assert_eq!(columns[6].value, Value::Float64(None));
+    }
+
+    #[test]
+    fn test_newtype_row() {
+        let user = user();
+        let row = Row::new(
+            User::columns()
+                .iter()
+                .map(|c| c.column_ref.name.to_string())
+                .collect(),
+            user.row_values(),
+        );
+        assert_eq!(row.values[2], Value::Varchar(None));
+        assert_eq!(User::from_row(row).unwrap(), user);
+    }
+
+    #[test]
+    fn test_newtype_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<User>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "app"."user" (
+                "id" UUID PRIMARY KEY,
+                "email" VARCHAR NOT NULL,
+                "nickname" VARCHAR,
+                "score" INTEGER NOT NULL,
+                "manager" UUID,
+                "friends" UUID[] NOT NULL,
+                "walked" DOUBLE NOT NULL);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_newtype_expression() {
+        let id = UserId(Uuid::from_u128(1));
+        let mut query = DynQuery::default();
+        WRITER.write_select(
+            &mut query,
+            &QueryBuilder::new()
+                .select([User::email])
+                .from(User::table())
+                .where_expr(expr!(User::id == #id && User::score > 10)),
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                SELECT "email"
+                FROM "app"."user"
+                WHERE "id" = '00000000-0000-0000-0000-000000000001' AND "score" > 10;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_shadowed_names() {
+        let columns = Event::columns();
+        assert_eq!(columns[0].value, Value::Varchar(None));
+        assert_eq!(columns[1].value, Value::Int64(None));
+        assert_eq!(
+            columns[2].value,
+            Value::List(None, Box::new(Value::Int64(None)))
+        );
+        assert_eq!(columns[3].value, Value::Decimal(None, 10, 2));
+    }
+}
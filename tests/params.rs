@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use tank::{AsValue, IntoParams, Params, Value};
+
+    #[test]
+    fn test_positional_params() {
+        assert_eq!(
+            (1, "first", 2.5).into_params(),
+            Params::Positional(vec![
+                Value::Int32(Some(1)),
+                Value::Varchar(Some("first".into())),
+                Value::Float64(Some(2.5)),
+            ])
+        );
+        assert_eq!(
+            [10_i64, 20].into_params(),
+            Params::Positional(vec![Value::Int64(Some(10)), Value::Int64(Some(20))])
+        );
+        assert_eq!(
+            vec![true].into_params(),
+            Params::Positional(vec![Value::Boolean(Some(true))])
+        );
+        assert_eq!(().into_params(), Params::Positional(vec![]));
+    }
+
+    #[test]
+    fn test_named_params() {
+        assert_eq!(
+            BTreeMap::from([("id", 1.as_value()), ("name", "a".as_value())]).into_params(),
+            Params::Named(vec![
+                ("id".into(), Value::Int32(Some(1))),
+                ("name".into(), Value::Varchar(Some("a".into()))),
+            ])
+        );
+        let Params::Named(values) = HashMap::from([("total".to_string(), 5_u8)]).into_params()
+        else {
+            panic!("A map must produce named params");
+        };
+        assert_eq!(values, [("total".to_string(), Value::UInt8(Some(5)))]);
+    }
+}
@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::borrow::Cow;
+    use tank::{
+        Action, ColumnChanges, ColumnInfo, ColumnRef, Entity, GenericSqlWriter, MigrationStep,
+        PrimaryKeyType, SchemaDiff, SqlWriter, Value,
+    };
+
+    #[derive(Entity)]
+    #[tank(name = "product", unique = (Self::name, Self::vendor))]
+    struct Product {
+        #[tank(primary_key)]
+        id: i64,
+        name: String,
+        vendor: Option<String>,
+        #[tank(default = 0)]
+        stock: i32,
+        #[tank(references = vendor(id), on_delete = cascade)]
+        vendor_id: Option<i64>,
+        #[tank(unique)]
+        code: String,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn column(name: &'static str, value: Value, nullable: bool) -> ColumnInfo {
+        ColumnInfo {
+            column_ref: ColumnRef {
+                name: Cow::Borrowed(name),
+                table: Cow::Borrowed("product"),
+                schema: Cow::Borrowed(""),
+            },
+            column_type: String::new(),
+            value,
+            nullable,
+            ..Default::default()
+        }
+    }
+
+    fn current() -> Vec<ColumnInfo> {
+        vec![
+            ColumnInfo {
+                primary_key: PrimaryKeyType::PrimaryKey,
+                ..column("id", Value::Int64(None), false)
+            },
+            column("name", Value::Varchar(None), true),
+            column("stock", Value::Int64(None), false),
+            ColumnInfo {
+                references: Some(ColumnRef {
+                    name: "id".into(),
+                    table: "vendor".into(),
+                    schema: "".into(),
+                }),
+                on_delete: Some(Action::Cascade),
+                ..column("vendor_id", Value::Int64(None), true)
+            },
+            ColumnInfo {
+                unique: true,
+                ..column("code", Value::Varchar(None), false)
+            },
+            column("legacy", Value::Blob(None), true),
+        ]
+    }
+
+    #[test]
+    fn schema_diff_create_table() {
+        let diff = SchemaDiff::new::<Product, _>(&WRITER, &[]);
+        assert_eq!(diff.changes.len(), 1);
+        assert!(matches!(
+            diff.changes[0].step,
+            MigrationStep::CreateTable(ref table) if table.name == "product"
+        ));
+        assert!(diff.is_safe());
+        assert!(diff.to_string().starts_with(r#"CREATE TABLE "product" ("#));
+    }
+
+    #[test]
+    fn schema_diff_up_to_date() {
+        let mut current = current();
+        current.pop();
+        current[1].nullable = false;
+        current[2].value = Value::Int32(None);
+        current[2].default = Some("0".into());
+        current.insert(
+            2,
+            column("vendor", Value::Unknown(Some("TEXT".into())), true),
+        );
+        let diff = SchemaDiff::new::<Product, _>(&WRITER, &current);
+        assert!(diff.is_empty(), "{diff}");
+    }
+
+    #[test]
+    fn schema_diff_changes() {
+        let diff = SchemaDiff::new::<Product, _>(&WRITER, &current());
+        let steps: Vec<_> = diff.changes.iter().map(|v| &v.step).collect();
+        assert!(matches!(
+            steps.as_slice(),
+            [
+                MigrationStep::AddColumn(..),
+                MigrationStep::AlterColumn(..),
+                MigrationStep::AlterColumn(..),
+                MigrationStep::AddUnique(..),
+                MigrationStep::DropColumn(..),
+            ]
+        ));
+        assert!(matches!(
+            steps[1],
+            MigrationStep::AlterColumn(
+                column,
+                ColumnChanges {
+                    column_type: false,
+                    nullable: true,
+                    default: false,
+                }
+            ) if column.name == "name"
+        ));
+        assert!(matches!(
+            steps[2],
+            MigrationStep::AlterColumn(
+                column,
+                ColumnChanges {
+                    column_type: true,
+                    nullable: false,
+                    default: true,
+                }
+            ) if column.name == "stock"
+        ));
+        assert!(!diff.is_safe());
+        assert_eq!(
+            diff.changes
+                .iter()
+                .map(|v| v.destructive)
+                .collect::<Vec<_>>(),
+            [false, true, true, true, true]
+        );
+        assert_eq!(
+            diff.to_string(),
+            indoc! {r#"
+                ALTER TABLE "product" ADD COLUMN "vendor" VARCHAR;
+                -- Destructive
+                ALTER TABLE "product" ALTER COLUMN "name" SET NOT NULL;
+                -- Destructive
+                ALTER TABLE "product" ALTER COLUMN "stock" TYPE INTEGER;
+                ALTER TABLE "product" ALTER COLUMN "stock" SET DEFAULT 0;
+                -- Destructive
+                ALTER TABLE "product" ADD UNIQUE ("name", "vendor");
+                -- Destructive
+                ALTER TABLE "product" DROP COLUMN "legacy";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn schema_diff_cannot_alter_columns() {
+        struct NoAlterWriter;
+        impl SqlWriter for NoAlterWriter {
+            fn as_dyn(&self) -> &dyn SqlWriter {
+                self
+            }
+            fn can_alter_columns(&self) -> bool {
+                false
+            }
+        }
+        let diff = SchemaDiff::new::<Product, _>(&NoAlterWriter, &current());
+        let altered = diff
+            .changes
+            .iter()
+            .filter(|v| matches!(v.step, MigrationStep::AlterColumn(..)))
+            .collect::<Vec<_>>();
+        assert_eq!(altered.len(), 2);
+        assert!(altered.iter().all(|v| v.sql.is_empty()));
+        assert!(
+            diff.to_string()
+                .contains("-- Not supported: AlterColumn(ColumnRef { name: \"name\"")
+        );
+        assert_eq!(diff.unsupported().count(), 2);
+        let len = diff.changes.len();
+        let diff = diff.skip_unsupported();
+        assert_eq!(diff.changes.len(), len - 2);
+        assert_eq!(diff.unsupported().count(), 0);
+    }
+
+    #[test]
+    fn schema_diff_add_foreign_key() {
+        let mut current = current();
+        current[3].references = None;
+        current.remove(4);
+        let diff = SchemaDiff::new::<Product, _>(&WRITER, &current);
+        let sql: Vec<_> = diff.changes.iter().map(|v| v.sql.as_str()).collect();
+        assert!(sql.contains(
+            &r#"ALTER TABLE "product" ADD FOREIGN KEY ("vendor_id") REFERENCES "vendor"("id") ON DELETE CASCADE;"#
+        ));
+        assert!(
+            sql.contains(&r#"ALTER TABLE "product" ADD COLUMN "code" VARCHAR NOT NULL UNIQUE;"#)
+        );
+    }
+
+    #[test]
+    fn schema_diff_writer() {
+        let mut query = tank::DynQuery::default();
+        WRITER.write_alter_table_drop_column(&mut query, Product::table(), "legacy");
+        WRITER.write_alter_table_add_unique(
+            &mut query,
+            Product::table(),
+            &[&Product::columns()[5]],
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                ALTER TABLE "product" DROP COLUMN "legacy";
+                ALTER TABLE "product" ADD UNIQUE ("code");
+            "#}
+            .trim()
+        );
+    }
+}
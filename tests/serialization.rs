@@ -0,0 +1,138 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde::Deserialize;
+    use std::{collections::BTreeMap, sync::Arc};
+    use tank::{Row, Value};
+    use time::macros::date;
+    use uuid::Uuid;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Order {
+        id: i64,
+        customer: String,
+        total: f64,
+        note: Option<String>,
+        tags: Vec<String>,
+    }
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    enum Status {
+        Open,
+        Closed,
+    }
+
+    fn row() -> Row {
+        Row::new(
+            Arc::new([
+                "id".into(),
+                "customer".into(),
+                "total".into(),
+                "note".into(),
+                "tags".into(),
+            ]),
+            [
+                Value::Int64(Some(7)),
+                Value::Varchar(Some("Ana".into())),
+                Value::Float64(Some(12.5)),
+                Value::Varchar(None),
+                Value::List(
+                    Some(vec![
+                        Value::Varchar(Some("gift".into())),
+                        Value::Varchar(Some("express".into())),
+                    ]),
+                    Box::new(Value::Varchar(None)),
+                ),
+            ]
+            .into(),
+        )
+    }
+
+    #[test]
+    fn test_row_deserialize() {
+        let row = row();
+        assert_eq!(
+            row.deserialize::<Order>().unwrap(),
+            Order {
+                id: 7,
+                customer: "Ana".into(),
+                total: 12.5,
+                note: None,
+                tags: vec!["gift".into(), "express".into()],
+            }
+        );
+        let (id, customer) = Row::new(
+            Arc::new(["id".into(), "customer".into()]),
+            [Value::Int32(Some(3)), Value::Varchar(Some("Bo".into()))].into(),
+        )
+        .deserialize::<(u64, String)>()
+        .unwrap();
+        assert_eq!((id, customer.as_str()), (3, "Bo"));
+        let map = row.deserialize::<BTreeMap<String, Value>>().unwrap();
+        assert_eq!(map["customer"], Value::Varchar(Some("Ana".into())));
+        assert_eq!(map.len(), 5);
+        assert!(row.deserialize::<(i64, bool)>().is_err());
+        let statuses = Row::new(
+            Arc::new(["first".into(), "second".into()]),
+            [
+                Value::Varchar(Some("Open".into())),
+                Value::Varchar(Some("Closed".into())),
+            ]
+            .into(),
+        )
+        .deserialize::<Vec<Status>>()
+        .unwrap();
+        assert_eq!(statuses, [Status::Open, Status::Closed]);
+    }
+
+    #[test]
+    fn test_row_serialize() {
+        assert_eq!(
+            serde_json::to_value(row()).unwrap(),
+            serde_json::json!({
+                "id": 7,
+                "customer": "Ana",
+                "total": 12.5,
+                "note": null,
+                "tags": ["gift", "express"],
+            })
+        );
+        let uuid = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        assert_eq!(
+            serde_json::to_value(Row::new(
+                Arc::new(["day".into(), "uuid".into()]),
+                [
+                    Value::Date(Some(date!(2025 - 03 - 14))),
+                    Value::Uuid(Some(uuid)),
+                ]
+                .into(),
+            ))
+            .unwrap(),
+            serde_json::json!({
+                "day": "2025-03-14",
+                "uuid": "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            })
+        );
+    }
+
+    #[test]
+    fn test_row_roundtrip() {
+        let row: Row = serde_json::from_str(r#"{"id": 1, "name": "first", "ok": true}"#).unwrap();
+        assert_eq!(&*row.labels, ["id", "name", "ok"]);
+        assert_eq!(
+            &*row.values,
+            [
+                Value::UInt64(Some(1)),
+                Value::Varchar(Some("first".into())),
+                Value::Boolean(Some(true)),
+            ]
+        );
+        let value: Value = serde_json::from_str("[1.5, 2.25]").unwrap();
+        assert_eq!(
+            value,
+            Value::List(
+                Some(vec![Value::Float64(Some(1.5)), Value::Float64(Some(2.25))]),
+                Box::new(Value::Float64(None)),
+            )
+        );
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use tank::{DynQuery, Entity, GenericSqlWriter, Sql, SqlPart, Value, sql};
+
+    #[derive(Entity)]
+    #[tank(schema = "post", name = "parcel")]
+    struct Parcel {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(name = "to")]
+        recipient: String,
+        weight: Option<f64>,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn inline(sql: &Sql) -> String {
+        let mut query = DynQuery::default();
+        sql.write_inline(&WRITER, &mut query)
+            .expect("Failed to write the query");
+        query.as_str().to_string()
+    }
+
+    fn prepared(sql: &Sql) -> (String, Vec<Value>) {
+        let mut query = DynQuery::default();
+        let values = sql
+            .write_prepared(&WRITER, &mut query)
+            .expect("Failed to write the query");
+        (query.as_str().to_string(), values)
+    }
+
+    #[test]
+    fn test_sql_values() {
+        let recipient = "O'Brien";
+        let min_weight = 2.5;
+        let query = sql!(
+            "SELECT * FROM parcel WHERE recipient = {recipient} AND weight > {min_weight * 2.0}"
+        );
+        assert_eq!(
+            inline(&query),
+            "SELECT * FROM parcel WHERE recipient = 'O''Brien' AND weight > 5.0"
+        );
+        assert_eq!(
+            prepared(&query),
+            (
+                "SELECT * FROM parcel WHERE recipient = ? AND weight > ?".into(),
+                vec![
+                    Value::Varchar(Some("O'Brien".into())),
+                    Value::Float64(Some(5.0))
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_sql_identifiers() {
+        let column = "we\"ight";
+        let query = sql!(
+            "SELECT {ident:Parcel::recipient}, {ident:column} FROM {ident:Parcel::table()} WHERE {ident : Parcel::id} = 1"
+        );
+        assert_eq!(
+            inline(&query),
+            r#"SELECT "to", "we""ight" FROM "post"."parcel" WHERE "id" = 1"#
+        );
+    }
+
+    #[test]
+    fn test_sql_lists() {
+        let ids = vec![1, 2, 3];
+        let none: [i32; 0] = [];
+        let query = sql!("DELETE FROM parcel WHERE id IN {#ids}");
+        assert_eq!(inline(&query), "DELETE FROM parcel WHERE id IN (1, 2, 3)");
+        let (text, values) = prepared(&query);
+        assert_eq!(text, "DELETE FROM parcel WHERE id IN (?, ?, ?)");
+        assert_eq!(values.len(), 3);
+        let query = sql!("DELETE FROM parcel WHERE id NOT IN {# none}");
+        let mut out = DynQuery::default();
+        assert!(query.write_inline(&WRITER, &mut out).is_err());
+        assert!(query.write_prepared(&WRITER, &mut out).is_err());
+    }
+
+    #[test]
+    fn test_sql_named_and_braces() {
+        let query = sql!("SELECT '{{}}', {a} + {b}", a = 1, b = 2_u8);
+        assert_eq!(inline(&query), "SELECT '{}', 1 + 2");
+        assert!(matches!(
+            query.parts.as_slice(),
+            [
+                SqlPart::Text(..),
+                SqlPart::Value(Value::Int32(Some(1))),
+                SqlPart::Text(..),
+                SqlPart::Value(Value::UInt8(Some(2))),
+            ]
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{
+        AsValue, DynQuery, Entity, GenericSqlWriter, QueryBuilder, SqlWriter, Struct, TableRef,
+        Value, expr,
+    };
+
+    #[derive(Struct, Clone, PartialEq, Debug)]
+    struct Geo {
+        lat: f64,
+        lon: f64,
+    }
+
+    #[derive(Struct, Clone, PartialEq, Debug)]
+    #[tank(schema = "shop", name = "postal_address")]
+    struct Address {
+        street: String,
+        city: String,
+        #[tank(name = "zip_code")]
+        zip: Option<i32>,
+        geo: Geo,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "shop", name = "customer")]
+    struct Customer {
+        #[tank(primary_key)]
+        id: i64,
+        address: Address,
+        billing: Option<Address>,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn rome() -> Address {
+        Address {
+            street: "Via Appia".into(),
+            city: "Rome".into(),
+            zip: Some(100),
+            geo: Geo {
+                lat: 41.9,
+                lon: 12.5,
+            },
+        }
+    }
+
+    #[test]
+    fn test_struct_value() {
+        let Value::Struct(Some(fields), types, name) = rome().as_value() else {
+            panic!("Address must be a struct");
+        };
+        assert_eq!(
+            name,
+            TableRef {
+                name: "postal_address".into(),
+                schema: "shop".into(),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            fields.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>(),
+            ["street", "city", "zip_code", "geo"]
+        );
+        assert_eq!(fields[2].1, Value::Int32(Some(100)));
+        assert_eq!(types[2], ("zip_code".into(), Value::Int32(None)));
+        assert!(matches!(&types[3].1, Value::Struct(None, _, geo) if geo.name == "geo"));
+        assert!(matches!(
+            Customer::columns()[1].value,
+            Value::Struct(None, ..)
+        ));
+        assert!(Customer::columns()[2].nullable);
+    }
+
+    #[test]
+    fn test_struct_round_trip() {
+        assert_eq!(Address::try_from_value(rome().as_value()).unwrap(), rome());
+        let json = serde_json::json!({
+            "street": "Via Appia",
+            "city": "Rome",
+            "zip_code": 100,
+            "geo": {"lat": 41.9, "lon": 12.5},
+        });
+        assert_eq!(
+            Address::try_from_value(Value::Json(Some(json.clone()))).unwrap(),
+            rome()
+        );
+        assert_eq!(
+            Address::try_from_value(Value::Varchar(Some(json.to_string().into()))).unwrap(),
+            rome()
+        );
+        let without_zip = serde_json::json!({
+            "street": "Via Appia",
+            "city": "Rome",
+            "geo": {"lat": 41.9, "lon": 12.5},
+        });
+        assert_eq!(
+            Address::try_from_value(Value::Json(Some(without_zip)))
+                .unwrap()
+                .zip,
+            None
+        );
+        assert_eq!(
+            Option::<Address>::try_from_value(Value::Null).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_struct_decode_errors() {
+        let error = Address::try_from_value(Value::Json(Some(
+            serde_json::json!({"street": "Via Appia", "geo": {"lat": 41.9, "lon": 12.5}}),
+        )))
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "While decoding the field `city` of the struct Address"
+        );
+        assert!(Address::try_from_value(Value::Int32(Some(1))).is_err());
+    }
+
+    #[test]
+    fn test_customer_create_table() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_table::<Customer>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE TABLE "shop"."customer" (
+                "id" BIGINT PRIMARY KEY,
+                "address" STRUCT("street" VARCHAR, "city" VARCHAR, "zip_code" INTEGER, "geo" STRUCT("lat" DOUBLE, "lon" DOUBLE)) NOT NULL,
+                "billing" STRUCT("street" VARCHAR, "city" VARCHAR, "zip_code" INTEGER, "geo" STRUCT("lat" DOUBLE, "lon" DOUBLE)));
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_customer_field_access() {
+        let mut query = DynQuery::default();
+        WRITER.write_select(
+            &mut query,
+            &QueryBuilder::new()
+                .select(Customer::columns())
+                .from(Customer::table())
+                .where_expr(expr!(
+                    Customer::address.city == "Rome" && Customer::address.geo.lat > 40
+                )),
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                SELECT "id", "address", "billing"
+                FROM "shop"."customer"
+                WHERE ("address")."city" = 'Rome' AND ("address")."geo"."lat" > 40;
+            "#}
+            .trim()
+        );
+    }
+}
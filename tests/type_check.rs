@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::borrow::Cow;
+    use tank::{
+        AsValue, ColumnRef, Dataset, DynQuery, Entity, FieldType, GenericSqlWriter, QueryBuilder,
+        SqlWriter, TypeKind, Value, expr, field_hash, join,
+    };
+    use time::Date;
+    use uuid::Uuid;
+
+    #[derive(tank::Value, Clone, Copy)]
+    struct Meters(f32);
+
+    #[derive(Entity)]
+    #[tank(schema = "fleet", name = "ship")]
+    struct Ship {
+        #[tank(primary_key)]
+        id: Uuid,
+        name: String,
+        crew: Option<u16>,
+        length: Meters,
+        launched: Date,
+        decks: Vec<i32>,
+        active: bool,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "fleet", name = "voyage")]
+    struct Voyage {
+        #[tank(primary_key)]
+        ship: Uuid,
+        port: Option<String>,
+        passengers: i64,
+    }
+
+    /// Columns declared by hand, their type is not known.
+    struct Dock;
+    #[allow(non_upper_case_globals)]
+    impl Dock {
+        const berth: ColumnRef = ColumnRef {
+            name: Cow::Borrowed("berth"),
+            table: Cow::Borrowed("dock"),
+            schema: Cow::Borrowed("fleet"),
+        };
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn select(condition: impl tank::Expression) -> String {
+        let mut query = DynQuery::default();
+        WRITER.write_select(
+            &mut query,
+            &QueryBuilder::new()
+                .select([Ship::name])
+                .from(Ship::table())
+                .where_expr(condition),
+        );
+        query.as_str().to_string()
+    }
+
+    #[test]
+    fn test_type_kind() {
+        assert!(TypeKind::Numeric.is_comparable(TypeKind::Numeric));
+        assert!(!TypeKind::Numeric.is_comparable(TypeKind::Text));
+        assert!(!TypeKind::Boolean.is_comparable(TypeKind::Numeric));
+        assert!(TypeKind::Date.is_comparable(TypeKind::Text));
+        assert!(TypeKind::Text.is_comparable(TypeKind::Uuid));
+        assert!(!TypeKind::Uuid.is_comparable(TypeKind::Date));
+        assert!(TypeKind::Unknown.is_comparable(TypeKind::Boolean));
+        assert!(
+            TypeKind::List(&TypeKind::Numeric).is_comparable(TypeKind::List(&TypeKind::Unknown))
+        );
+        assert!(!TypeKind::List(&TypeKind::Numeric).is_comparable(TypeKind::List(&TypeKind::Text)));
+        assert!(TypeKind::Text.is_text());
+        assert!(!TypeKind::Date.is_text());
+        assert!(TypeKind::List(&TypeKind::Numeric).contains(TypeKind::Numeric));
+        assert!(!TypeKind::List(&TypeKind::Numeric).contains(TypeKind::Text));
+        assert!(!TypeKind::Numeric.contains(TypeKind::Numeric));
+        assert!(TypeKind::Unknown.contains(TypeKind::Text));
+    }
+
+    #[test]
+    fn test_type_kind_of_fields() {
+        assert!(matches!(<u16 as AsValue>::KIND, TypeKind::Numeric));
+        assert!(matches!(<Option<u16> as AsValue>::KIND, TypeKind::Numeric));
+        assert!(matches!(<Meters as AsValue>::KIND, TypeKind::Numeric));
+        assert!(matches!(
+            <Vec<i32> as AsValue>::KIND,
+            TypeKind::List(TypeKind::Numeric)
+        ));
+        assert!(matches!(<Date as AsValue>::KIND, TypeKind::Date));
+        assert!(matches!(<Value as AsValue>::KIND, TypeKind::Unknown));
+        assert!(matches!(
+            <[bool; 2] as AsValue>::KIND,
+            TypeKind::List(TypeKind::Boolean)
+        ));
+        assert!(matches!(
+            <<Ship as FieldType<{ field_hash("crew") }>>::Type as AsValue>::KIND,
+            TypeKind::Numeric
+        ));
+        assert!(matches!(
+            <<Ship as FieldType<{ field_hash("launched") }>>::Type as AsValue>::KIND,
+            TypeKind::Date
+        ));
+    }
+
+    #[test]
+    fn test_type_check_comparisons() {
+        let name = "Endurance";
+        let min_crew = 20u8;
+        let decks = vec![1, 2, 3];
+        let unknown = Value::Varchar(Some("1".into()));
+        assert_eq!(
+            select(expr!(
+                Ship::crew >= #min_crew
+                    && #name == Ship::name
+                    && Ship::length > -12.5
+                    && Ship::launched < "1915-01-01"
+                    && Ship::id != "00000000-0000-0000-0000-000000000000"
+                    && Ship::active == true
+                    && Ship::crew == #unknown
+            )),
+            indoc! {r#"
+                SELECT "name"
+                FROM "fleet"."ship"
+                WHERE "crew" >= 20 AND 'Endurance' = "name" AND "length" > -12.5 AND "launched" < '1915-01-01' AND "id" != '00000000-0000-0000-0000-000000000000' AND "active" = true AND "crew" = '1';
+            "#}
+            .trim()
+        );
+        assert_eq!(
+            select(expr!(
+                Ship::name == "E%" as LIKE
+                    && Ship::crew == (10, 20, #min_crew) as IN
+                    && Ship::crew != #decks as IN
+                    && Ship::crew == NULL
+            )),
+            indoc! {r#"
+                SELECT "name"
+                FROM "fleet"."ship"
+                WHERE "name" LIKE 'E%' AND "crew" IN (10,20,20) AND "crew" NOT IN [1,2,3] AND "crew" IS NULL;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_type_check_unknown_columns() {
+        let berth = 7;
+        assert_eq!(
+            select(expr!(
+                Dock::berth == "A7" && Dock::berth > #berth && Dock::berth == (1, "B") as IN
+            )),
+            indoc! {r#"
+                SELECT "name"
+                FROM "fleet"."ship"
+                WHERE "berth" = 'A7' AND "berth" > 7 AND "berth" IN (1,'B');
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_type_check_join() {
+        let join =
+            join!(Ship JOIN Voyage ON Ship::id == Voyage::ship && Voyage::passengers < Ship::crew);
+        let mut query = DynQuery::default();
+        join.write_table_name(&WRITER, &mut Default::default(), &mut query);
+        assert_eq!(
+            query.as_str(),
+            r#""fleet"."ship" JOIN "fleet"."voyage" ON "fleet"."ship"."id" = "fleet"."voyage"."ship" AND "fleet"."voyage"."passengers" < "fleet"."ship"."crew""#
+        );
+    }
+}
@@ -7,7 +7,8 @@ mod tests {
         Context, Dataset, DeclareTableRef, DynQuery, EitherIterator, Entity, FixedDecimal,
         Fragment, GenericSqlWriter, Interval, QueryBuilder, QueryResult, References, Row,
         RowsAffected, SqlWriter, TableRef, Value, as_c_string, column_def, consume_while,
-        extract_number, quote_cow, separated_by, value_to_json, write_escaped,
+        extract_number, quote_cow, separated_by, split_sql_statements, value_to_json,
+        write_escaped,
     };
     use time::{Date, Month, OffsetDateTime, Time, UtcOffset};
 
@@ -763,6 +764,37 @@ mod tests {
         assert_eq!(right.collect::<Vec<_>>(), vec![4, 5]);
     }
 
+    #[test]
+    fn util_split_sql_statements() {
+        assert_eq!(
+            split_sql_statements("CREATE TABLE a (v INT);\nINSERT INTO a VALUES (1);"),
+            ["CREATE TABLE a (v INT);", "INSERT INTO a VALUES (1);"]
+        );
+        assert_eq!(
+            split_sql_statements("INSERT INTO a VALUES ('x;y', \"z;\"\"w\"); SELECT 'it''s;'"),
+            [
+                "INSERT INTO a VALUES ('x;y', \"z;\"\"w\");",
+                "SELECT 'it''s;'"
+            ]
+        );
+        assert_eq!(
+            split_sql_statements(
+                "-- first; comment\nSELECT 1; /* second; comment */ ;\n  ;SELECT 2;\n-- trailing;"
+            ),
+            ["-- first; comment\nSELECT 1;", "SELECT 2;"]
+        );
+        assert_eq!(
+            split_sql_statements(
+                "DO $$ BEGIN CREATE TYPE t AS ENUM ('a'); EXCEPTION WHEN duplicate_object THEN NULL; END $$;\nSELECT $1, $tag$;$tag$;"
+            ),
+            [
+                "DO $$ BEGIN CREATE TYPE t AS ENUM ('a'); EXCEPTION WHEN duplicate_object THEN NULL; END $$;",
+                "SELECT $1, $tag$;$tag$;"
+            ]
+        );
+        assert!(split_sql_statements(" ; -- nothing\n").is_empty());
+    }
+
     #[test]
     fn util_consume_while() {
         let mut input = "12345abc";
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{DynQuery, Entity, GenericSqlWriter, QueryBuilder, SqlWriter, ViewDef, cols, expr};
+
+    #[derive(Entity)]
+    #[tank(schema = "shop", name = "orders")]
+    struct Order {
+        #[tank(primary_key)]
+        id: i64,
+        customer: String,
+        amount: f64,
+        paid: bool,
+    }
+
+    #[derive(Entity)]
+    #[tank(
+        schema = "shop",
+        name = "paid_orders",
+        view = QueryBuilder::new()
+            .select(cols!(Order::id, Order::customer, Order::amount as total))
+            .from(Order::table())
+            .where_expr(expr!(Order::paid == true))
+    )]
+    struct PaidOrder {
+        id: i64,
+        customer: String,
+        total: f64,
+    }
+
+    #[derive(Entity)]
+    #[tank(
+        name = "big_spenders",
+        view = r#"
+            SELECT "customer", SUM("amount") AS "spent"
+            FROM "shop"."orders"
+            GROUP BY "customer"
+            HAVING SUM("amount") > 1000
+        "#,
+        materialized
+    )]
+    struct BigSpender {
+        customer: String,
+        spent: f64,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_view_definition() {
+        assert_eq!(Order::view(), None);
+        assert_eq!(
+            PaidOrder::view(),
+            Some(&ViewDef {
+                materialized: false
+            })
+        );
+        assert_eq!(BigSpender::view(), Some(&ViewDef { materialized: true }));
+    }
+
+    #[test]
+    fn test_paid_orders_create_view() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_view::<PaidOrder>(&mut query, true);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE VIEW IF NOT EXISTS "shop"."paid_orders" AS
+                SELECT "id", "customer", "amount" AS "total"
+                FROM "shop"."orders"
+                WHERE "paid" = true;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_big_spenders_create_view() {
+        let mut query = DynQuery::default();
+        WRITER.write_create_view::<BigSpender>(&mut query, false);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                CREATE MATERIALIZED VIEW "big_spenders" AS
+                SELECT "customer", SUM("amount") AS "spent"
+                            FROM "shop"."orders"
+                            GROUP BY "customer"
+                            HAVING SUM("amount") > 1000;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_view_drop_and_refresh() {
+        let mut query = DynQuery::default();
+        WRITER.write_drop_view::<PaidOrder>(&mut query, true);
+        WRITER.write_drop_view::<BigSpender>(&mut query, false);
+        WRITER.write_refresh_view::<BigSpender>(&mut query);
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                DROP VIEW IF EXISTS "shop"."paid_orders";
+                DROP MATERIALIZED VIEW "big_spenders";
+                REFRESH MATERIALIZED VIEW "big_spenders";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_paid_orders_select() {
+        let mut query = DynQuery::default();
+        WRITER.write_select(
+            &mut query,
+            &QueryBuilder::new()
+                .select(PaidOrder::columns())
+                .from(PaidOrder::table())
+                .where_expr(expr!(PaidOrder::total > 50)),
+        );
+        assert_eq!(
+            query.as_str(),
+            indoc! {r#"
+                SELECT "id", "customer", "total"
+                FROM "shop"."paid_orders"
+                WHERE "total" > 50;
+            "#}
+            .trim()
+        );
+    }
+}